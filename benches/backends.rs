@@ -0,0 +1,188 @@
+//! Read and write throughput of every backend through `LockApi` and
+//! `AsyncLockApi`, uncontended and with `THREADS` threads hammering one lock.
+//!
+//! Run with `cargo bench --features bench`.
+
+use std::{
+    cell::RefCell,
+    hint::black_box,
+    sync::{Arc, Barrier},
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use locket::{AsyncLockApi, LockApi, LockApiReadGuard, LockApiWriteGuard};
+
+const THREADS: usize = 4;
+
+fn uncontended<L>(c: &mut Criterion, name: &str)
+where
+    L: LockApi<u64>,
+{
+    let mut group = c.benchmark_group(format!("uncontended/{name}"));
+    let lock = L::new(0);
+    group.bench_function("read", |b| {
+        b.iter(|| *LockApi::read(black_box(&lock)).unwrap().get())
+    });
+    group.bench_function("write", |b| {
+        b.iter(|| *LockApi::write(black_box(&lock)).unwrap().get_mut() += 1)
+    });
+    group.finish();
+}
+
+// Every thread runs `work` for `iters` acquisitions; the slowest thread is
+// reported.
+fn run_threads(iters: u64, work: impl Fn(u64) + Sync) -> Duration {
+    let barrier = Barrier::new(THREADS);
+    thread::scope(|scope| {
+        let threads: Vec<_> = (0..THREADS)
+            .map(|_| {
+                scope.spawn(|| {
+                    barrier.wait();
+                    let start = Instant::now();
+                    work(iters);
+                    start.elapsed()
+                })
+            })
+            .collect();
+        threads
+            .into_iter()
+            .map(|thread| thread.join().unwrap())
+            .max()
+            .unwrap_or_default()
+    })
+}
+
+fn contended<L>(c: &mut Criterion, name: &str)
+where
+    L: LockApi<u64> + Sync,
+{
+    let mut group = c.benchmark_group(format!("contended/{name}"));
+    let lock = L::new(0);
+    group.bench_function("read", |b| {
+        b.iter_custom(|iters| {
+            run_threads(iters, |iters| {
+                for _ in 0..iters {
+                    black_box(*LockApi::read(&lock).unwrap().get());
+                }
+            })
+        })
+    });
+    group.bench_function("write", |b| {
+        b.iter_custom(|iters| {
+            run_threads(iters, |iters| {
+                for _ in 0..iters {
+                    *LockApi::write(&lock).unwrap().get_mut() += 1;
+                }
+            })
+        })
+    });
+    group.finish();
+}
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+}
+
+fn uncontended_async<L>(c: &mut Criterion, name: &str)
+where
+    L: AsyncLockApi<u64>,
+{
+    let mut group = c.benchmark_group(format!("uncontended-async/{name}"));
+    let rt = runtime();
+    let lock = L::new(0);
+    group.bench_function("read", |b| {
+        b.to_async(&rt)
+            .iter(|| async { *AsyncLockApi::read(black_box(&lock)).await.unwrap().get() })
+    });
+    group.bench_function("write", |b| {
+        b.to_async(&rt).iter(|| async {
+            *AsyncLockApi::write(black_box(&lock))
+                .await
+                .unwrap()
+                .get_mut() += 1
+        })
+    });
+    group.finish();
+}
+
+// Each thread drives its own single-threaded runtime, so the lock futures
+// need not be `Send`.
+fn contended_async<L>(c: &mut Criterion, name: &str)
+where
+    L: AsyncLockApi<u64> + Sync,
+{
+    let mut group = c.benchmark_group(format!("contended-async/{name}"));
+    let lock = L::new(0);
+    group.bench_function("read", |b| {
+        b.iter_custom(|iters| {
+            run_threads(iters, |iters| {
+                runtime().block_on(async {
+                    for _ in 0..iters {
+                        black_box(*AsyncLockApi::read(&lock).await.unwrap().get());
+                    }
+                })
+            })
+        })
+    });
+    group.bench_function("write", |b| {
+        b.iter_custom(|iters| {
+            run_threads(iters, |iters| {
+                runtime().block_on(async {
+                    for _ in 0..iters {
+                        *AsyncLockApi::write(&lock).await.unwrap().get_mut() += 1;
+                    }
+                })
+            })
+        })
+    });
+    group.finish();
+}
+
+fn sync_backends(c: &mut Criterion) {
+    uncontended::<RefCell<u64>>(c, "RefCell");
+
+    uncontended::<parking_lot::Mutex<u64>>(c, "parking_lot::Mutex");
+    uncontended::<parking_lot::FairMutex<u64>>(c, "parking_lot::FairMutex");
+    uncontended::<parking_lot::RwLock<u64>>(c, "parking_lot::RwLock");
+    uncontended::<std::sync::Mutex<u64>>(c, "std::Mutex");
+    uncontended::<std::sync::RwLock<u64>>(c, "std::RwLock");
+    uncontended::<spin::Mutex<u64>>(c, "spin::Mutex");
+    uncontended::<spin::RwLock<u64>>(c, "spin::RwLock");
+    uncontended::<locket::AtomicLock<u64>>(c, "AtomicLock");
+    uncontended::<locket::SeqLock<u64>>(c, "SeqLock");
+    uncontended::<locket::DoubleBuffered<u64>>(c, "DoubleBuffered");
+    // The cost of the `Arc` forwarding impl.
+    uncontended::<Arc<parking_lot::Mutex<u64>>>(c, "Arc<parking_lot::Mutex>");
+
+    contended::<parking_lot::Mutex<u64>>(c, "parking_lot::Mutex");
+    contended::<parking_lot::FairMutex<u64>>(c, "parking_lot::FairMutex");
+    contended::<parking_lot::RwLock<u64>>(c, "parking_lot::RwLock");
+    contended::<std::sync::Mutex<u64>>(c, "std::Mutex");
+    contended::<std::sync::RwLock<u64>>(c, "std::RwLock");
+    contended::<spin::Mutex<u64>>(c, "spin::Mutex");
+    contended::<spin::RwLock<u64>>(c, "spin::RwLock");
+    contended::<locket::AtomicLock<u64>>(c, "AtomicLock");
+    contended::<locket::SeqLock<u64>>(c, "SeqLock");
+    contended::<locket::DoubleBuffered<u64>>(c, "DoubleBuffered");
+}
+
+fn async_backends(c: &mut Criterion) {
+    uncontended_async::<tokio::sync::Mutex<u64>>(c, "tokio::Mutex");
+    uncontended_async::<tokio::sync::RwLock<u64>>(c, "tokio::RwLock");
+    uncontended_async::<async_lock::Mutex<u64>>(c, "async_lock::Mutex");
+    uncontended_async::<async_lock::RwLock<u64>>(c, "async_lock::RwLock");
+    uncontended_async::<locket::PolicyRwLock<u64>>(c, "PolicyRwLock");
+
+    contended_async::<tokio::sync::Mutex<u64>>(c, "tokio::Mutex");
+    contended_async::<tokio::sync::RwLock<u64>>(c, "tokio::RwLock");
+    contended_async::<async_lock::Mutex<u64>>(c, "async_lock::Mutex");
+    contended_async::<async_lock::RwLock<u64>>(c, "async_lock::RwLock");
+    contended_async::<locket::PolicyRwLock<u64>>(c, "PolicyRwLock");
+}
+
+criterion_group!(benches, sync_backends, async_backends);
+criterion_main!(benches);
@@ -0,0 +1,132 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Ident, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, Path};
+
+/// Generates `<Name>Locket`, holding every field of the struct in its own shared
+/// lock, with an accessor per field.
+///
+/// The lock defaults to locket's `DefaultLock`, the preferred thread-safe
+/// backend among its enabled features, or `BorrowLock` without one. It can be
+/// chosen with `#[locket(lock = parking_lot::RwLock)]`; the generated type can
+/// be renamed with `#[locket(name = StateHandle)]`.
+///
+/// Fields named `new` or `clone` are rejected, since their accessors would
+/// clash with the generated constructor and `Clone` impl.
+#[proc_macro_derive(Locket, attributes(locket))]
+pub fn derive_locket(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let mut lock: Path = parse_quote!(::locket::__private::DeriveLock);
+    let mut name: Ident = format_ident!("{}Locket", input.ident);
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("locket"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("lock") {
+                lock = meta.value()?.parse()?;
+                Ok(())
+            } else if meta.path.is_ident("name") {
+                name = meta.value()?.parse()?;
+                Ok(())
+            } else {
+                Err(meta.error("expected `lock` or `name`"))
+            }
+        })?;
+    }
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "Locket can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Locket can only be derived for structs",
+            ))
+        }
+    };
+
+    for ident in fields.iter().filter_map(|field| field.ident.as_ref()) {
+        if ident == "new" || ident == "clone" {
+            return Err(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "a field named `{ident}` would clash with the generated `{ident}`; rename it"
+                ),
+            ));
+        }
+    }
+
+    let vis = &input.vis;
+    let source = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let idents = fields
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+
+    let mut bounds = where_clause.cloned().unwrap_or_else(|| parse_quote!(where));
+    for ty in &types {
+        bounds
+            .predicates
+            .push(parse_quote!(#lock<#ty>: ::locket::LockApi<#ty>));
+    }
+
+    let doc = format!("Per-field locked version of [`{source}`].");
+
+    Ok(quote! {
+        #[doc = #doc]
+        #vis struct #name #impl_generics #where_clause {
+            #(#idents: ::locket::__private::Arc<#lock<#types>>,)*
+        }
+
+        impl #impl_generics #name #ty_generics #bounds {
+            #vis fn new(value: #source #ty_generics) -> Self {
+                let #source { #(#idents,)* } = value;
+                #name {
+                    #(#idents: ::locket::__private::Arc::new(
+                        <#lock<#types> as ::locket::LockApi<#types>>::new(#idents),
+                    ),)*
+                }
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #(
+                #vis fn #idents(&self) -> &::locket::__private::Arc<#lock<#types>> {
+                    &self.#idents
+                }
+            )*
+        }
+
+        impl #impl_generics ::core::clone::Clone for #name #ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                #name {
+                    #(#idents: ::core::clone::Clone::clone(&self.#idents),)*
+                }
+            }
+        }
+
+        impl #impl_generics ::core::convert::From<#source #ty_generics> for #name #ty_generics #bounds {
+            fn from(value: #source #ty_generics) -> Self {
+                #name::new(value)
+            }
+        }
+    })
+}
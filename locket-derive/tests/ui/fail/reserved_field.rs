@@ -0,0 +1,13 @@
+use locket::Locket;
+
+#[derive(Locket)]
+struct Constructor {
+    new: u32,
+}
+
+#[derive(Locket)]
+struct Cloned {
+    clone: u32,
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use locket::Locket;
+
+#[derive(Locket)]
+struct Tuple(u32);
+
+#[derive(Locket)]
+enum Either {
+    Left,
+    Right,
+}
+
+#[derive(Locket)]
+#[locket(backend = Mutex)]
+struct Unknown {
+    value: u32,
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use locket::Locket;
+
+#[derive(Locket)]
+pub struct State {
+    count: u32,
+    name: String,
+}
+
+fn assert_send_sync<T: Send + Sync>(_: &T) {}
+
+fn main() {
+    let state = StateLocket::new(State {
+        count: 0,
+        name: "state".into(),
+    });
+    assert_send_sync(&state);
+
+    let shared = state.clone();
+    std::thread::spawn(move || {
+        *locket::LockApi::write(&**shared.count()).unwrap() += 1;
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(*locket::LockApi::read(&**state.count()).unwrap(), 1);
+    assert_eq!(*locket::LockApi::read(&**state.name()).unwrap(), "state");
+}
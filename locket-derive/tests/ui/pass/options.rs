@@ -0,0 +1,15 @@
+use std::sync::Mutex;
+
+use locket::Locket;
+
+#[derive(Locket)]
+#[locket(lock = Mutex, name = Handle)]
+struct Config<T> {
+    value: T,
+}
+
+fn main() {
+    let handle: Handle<u8> = Config { value: 1u8 }.into();
+    let value: &std::sync::Arc<Mutex<u8>> = handle.value();
+    assert_eq!(*value.lock().unwrap(), 1);
+}
@@ -0,0 +1,89 @@
+// Aliases picking a backend from the enabled features, so applications can
+// name one type and switch backends in their manifest.
+
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use core::cell::RefCell;
+
+/// The preferred thread-safe read-write lock among the enabled backends:
+/// wasm-safe locks on wasm32, then parking_lot, std and spin.
+#[cfg(all(feature = "wasm", target_arch = "wasm32"))]
+pub type DefaultLock<T> = crate::wasm::RwLock<T>;
+
+/// The preferred thread-safe read-write lock among the enabled backends:
+/// wasm-safe locks on wasm32, then parking_lot, std and spin.
+#[cfg(all(
+    feature = "parking_lot",
+    not(all(feature = "wasm", target_arch = "wasm32"))
+))]
+pub type DefaultLock<T> = parking_lot::RwLock<T>;
+
+/// The preferred thread-safe read-write lock among the enabled backends:
+/// wasm-safe locks on wasm32, then parking_lot, std and spin.
+#[cfg(all(
+    not(feature = "parking_lot"),
+    feature = "std-lock",
+    not(all(feature = "wasm", target_arch = "wasm32"))
+))]
+pub type DefaultLock<T> = std::sync::RwLock<T>;
+
+/// The preferred thread-safe read-write lock among the enabled backends:
+/// wasm-safe locks on wasm32, then parking_lot, std and spin.
+#[cfg(all(
+    not(feature = "parking_lot"),
+    not(feature = "std-lock"),
+    feature = "spin",
+    not(all(feature = "wasm", target_arch = "wasm32"))
+))]
+pub type DefaultLock<T> = spin::RwLock<T>;
+
+/// A [`DefaultLock`] shared between threads.
+#[cfg(all(
+    feature = "alloc",
+    any(
+        feature = "parking_lot",
+        feature = "std-lock",
+        feature = "spin",
+        all(feature = "wasm", target_arch = "wasm32")
+    )
+))]
+pub type SharedLocket<T> = alloc::sync::Arc<DefaultLock<T>>;
+
+/// A locket for a single thread.
+#[cfg(feature = "alloc")]
+pub type LocalLocket<T> = Rc<RefCell<T>>;
+
+/// The preferred async read-write lock among the enabled backends: tokio,
+/// async-lock, async-std, then [`PolicyRwLock`](crate::PolicyRwLock).
+#[cfg(feature = "tokio")]
+pub type DefaultAsyncLock<T> = tokio::sync::RwLock<T>;
+
+/// The preferred async read-write lock among the enabled backends: tokio,
+/// async-lock, async-std, then [`PolicyRwLock`](crate::PolicyRwLock).
+#[cfg(all(not(feature = "tokio"), feature = "async-lock"))]
+pub type DefaultAsyncLock<T> = async_lock::RwLock<T>;
+
+/// The preferred async read-write lock among the enabled backends: tokio,
+/// async-lock, async-std, then [`PolicyRwLock`](crate::PolicyRwLock).
+#[cfg(all(
+    not(feature = "tokio"),
+    not(feature = "async-lock"),
+    feature = "async-std"
+))]
+pub type DefaultAsyncLock<T> = async_std::sync::RwLock<T>;
+
+/// The preferred async read-write lock among the enabled backends: tokio,
+/// async-lock, async-std, then [`PolicyRwLock`](crate::PolicyRwLock).
+#[cfg(all(
+    not(feature = "tokio"),
+    not(feature = "async-lock"),
+    not(feature = "async-std"),
+    feature = "std",
+    feature = "async"
+))]
+pub type DefaultAsyncLock<T> = crate::rwlock::PolicyRwLock<T>;
+
+/// A [`DefaultAsyncLock`] shared between tasks.
+#[cfg(all(feature = "std", feature = "async"))]
+pub type DefaultAsyncLocket<T> = alloc::sync::Arc<DefaultAsyncLock<T>>;
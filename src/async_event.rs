@@ -0,0 +1,112 @@
+use core::future::Future;
+
+use crate::{async_locking::AsyncLockApi, error::Result, locking::LockApiReadGuard};
+
+pub trait AsyncEventApi {
+    type Listener<'a>: Future<Output = ()>
+    where
+        Self: 'a;
+
+    fn listen(&self) -> Self::Listener<'_>;
+
+    fn notify_one(&self);
+
+    fn notify_all(&self);
+
+    fn new() -> Self;
+}
+
+pub trait AsyncCondvarApi: AsyncEventApi {
+    /// Releases `guard`, waits for a notification and reacquires the write lock.
+    /// The listener is registered before the guard is dropped, so a notification
+    /// sent in between is not lost.
+    fn wait<'a, T, L>(
+        &'a self,
+        lock: &'a L,
+        guard: L::WriteGuard<'a>,
+    ) -> impl Future<Output = Result<L::WriteGuard<'a>>> + 'a
+    where
+        L: AsyncLockApi<T>,
+        T: 'a,
+    {
+        async move {
+            let listener = self.listen();
+            drop(guard);
+            listener.await;
+            lock.write().await
+        }
+    }
+
+    fn wait_while<'a, T, L, F>(
+        &'a self,
+        lock: &'a L,
+        mut guard: L::WriteGuard<'a>,
+        mut condition: F,
+    ) -> impl Future<Output = Result<L::WriteGuard<'a>>> + 'a
+    where
+        L: AsyncLockApi<T>,
+        T: 'a,
+        F: FnMut(&T) -> bool + 'a,
+    {
+        async move {
+            while condition(guard.get()) {
+                guard = self.wait(lock, guard).await?;
+            }
+            Ok(guard)
+        }
+    }
+}
+
+impl<E> AsyncCondvarApi for E where E: AsyncEventApi {}
+
+#[cfg(feature = "event-listener")]
+mod event_listener_impl {
+    use super::AsyncEventApi;
+    use event_listener::{Event, EventListener};
+
+    impl AsyncEventApi for Event {
+        type Listener<'a> = EventListener;
+
+        fn listen(&self) -> Self::Listener<'_> {
+            self.listen()
+        }
+
+        fn notify_one(&self) {
+            self.notify(1);
+        }
+
+        fn notify_all(&self) {
+            self.notify(usize::MAX);
+        }
+
+        fn new() -> Self {
+            Event::new()
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_impl {
+    use super::AsyncEventApi;
+    use tokio::sync::{futures::Notified, Notify};
+
+    impl AsyncEventApi for Notify {
+        type Listener<'a> = Notified<'a>;
+
+        fn listen(&self) -> Self::Listener<'_> {
+            self.notified()
+        }
+
+        fn notify_one(&self) {
+            self.notify_one()
+        }
+
+        fn notify_all(&self) {
+            self.notify_waiters()
+        }
+
+        fn new() -> Self {
+            Notify::new()
+        }
+    }
+}
@@ -1,11 +1,15 @@
 use super::async_locking::AsyncLockApi;
 use crate::Downgrade;
-use alloc::{rc::Rc, sync::Arc};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, rc::Rc, sync::Arc};
+#[cfg(feature = "alloc")]
+use core::pin::Pin;
 
 pub trait AsyncLocket<T>: AsyncLockApi<T> + Downgrade + Clone {}
 
 impl<T, L> AsyncLocket<T> for L where L: AsyncLockApi<T> + Downgrade + Clone {}
 
+#[cfg(feature = "alloc")]
 impl<L, T> AsyncLockApi<T> for Arc<L>
 where
     L: AsyncLockApi<T>,
@@ -26,11 +30,16 @@ where
         (**self).write()
     }
 
+    fn lock_id(&self) -> usize {
+        (**self).lock_id()
+    }
+
     fn new(inner: T) -> Self {
         Arc::new(L::new(inner))
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<L, T> AsyncLockApi<T> for Rc<L>
 where
     L: AsyncLockApi<T>,
@@ -51,7 +60,106 @@ where
         (**self).write()
     }
 
+    fn lock_id(&self) -> usize {
+        (**self).lock_id()
+    }
+
     fn new(inner: T) -> Self {
         Rc::new(L::new(inner))
     }
 }
+
+#[cfg(feature = "alloc")]
+impl<L, T> AsyncLockApi<T> for Box<L>
+where
+    L: AsyncLockApi<T>,
+    for<'a> L: 'a,
+{
+    type ReadGuard<'a> = L::ReadGuard<'a>;
+
+    type WriteGuard<'a> = L::WriteGuard<'a>;
+
+    type ReadFuture<'a> = L::ReadFuture<'a>;
+    type WriteFuture<'a> = L::WriteFuture<'a>;
+
+    fn read(&self) -> Self::ReadFuture<'_> {
+        (**self).read()
+    }
+
+    fn write(&self) -> Self::WriteFuture<'_> {
+        (**self).write()
+    }
+
+    fn lock_id(&self) -> usize {
+        (**self).lock_id()
+    }
+
+    fn new(inner: T) -> Self {
+        Box::new(L::new(inner))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L, T> AsyncLockApi<T> for Pin<Arc<L>>
+where
+    L: AsyncLockApi<T>,
+    for<'a> L: 'a,
+{
+    type ReadGuard<'a> = L::ReadGuard<'a>;
+
+    type WriteGuard<'a> = L::WriteGuard<'a>;
+
+    type ReadFuture<'a> = L::ReadFuture<'a>;
+    type WriteFuture<'a> = L::WriteFuture<'a>;
+
+    fn read(&self) -> Self::ReadFuture<'_> {
+        (**self).read()
+    }
+
+    fn write(&self) -> Self::WriteFuture<'_> {
+        (**self).write()
+    }
+
+    fn lock_id(&self) -> usize {
+        (**self).lock_id()
+    }
+
+    fn new(inner: T) -> Self {
+        Arc::pin(L::new(inner))
+    }
+}
+
+#[cfg(feature = "portable-atomic")]
+mod portable_atomic_impl {
+    use crate::async_locking::AsyncLockApi;
+    use portable_atomic_util::Arc;
+
+    impl<L, T> AsyncLockApi<T> for Arc<L>
+    where
+        L: AsyncLockApi<T>,
+        for<'a> L: 'a,
+    {
+        type ReadGuard<'a> = L::ReadGuard<'a>;
+
+        type WriteGuard<'a> = L::WriteGuard<'a>;
+
+        type ReadFuture<'a> = L::ReadFuture<'a>;
+        type WriteFuture<'a> = L::WriteFuture<'a>;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            (**self).read()
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            (**self).write()
+        }
+
+        fn lock_id(&self) -> usize {
+            (**self).lock_id()
+        }
+
+        fn new(inner: T) -> Self {
+            Arc::new(L::new(inner))
+        }
+    }
+}
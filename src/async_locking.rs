@@ -27,6 +27,11 @@ pub trait AsyncLockApi<T> {
     fn write(&self) -> Self::WriteFuture<'_>;
 
     fn new(inner: T) -> Self;
+
+    /// See [`LockApi::lock_id`](crate::LockApi::lock_id).
+    fn lock_id(&self) -> usize {
+        self as *const Self as *const () as usize
+    }
 }
 
 impl<T> AsyncLockApi<T> for RefCell<T>
@@ -42,11 +47,11 @@ where
     type WriteFuture<'a> = core::future::Ready<Result<Self::WriteGuard<'a>>>;
 
     fn read(&self) -> Self::ReadFuture<'_> {
-        core::future::ready(self.try_borrow().map_err(|_| LockError))
+        core::future::ready(self.try_borrow().map_err(|_| LockError::WouldBlock))
     }
 
     fn write(&self) -> Self::WriteFuture<'_> {
-        core::future::ready(self.try_borrow_mut().map_err(|_| LockError))
+        core::future::ready(self.try_borrow_mut().map_err(|_| LockError::WouldBlock))
     }
 
     fn new(inner: T) -> Self {
@@ -54,6 +59,32 @@ where
     }
 }
 
+#[cfg(feature = "atomic_refcell")]
+impl<T> AsyncLockApi<T> for atomic_refcell::AtomicRefCell<T>
+where
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = atomic_refcell::AtomicRef<'a, T>;
+
+    type WriteGuard<'a> = atomic_refcell::AtomicRefMut<'a, T>;
+
+    type ReadFuture<'a> = core::future::Ready<Result<Self::ReadGuard<'a>>>;
+
+    type WriteFuture<'a> = core::future::Ready<Result<Self::WriteGuard<'a>>>;
+
+    fn read(&self) -> Self::ReadFuture<'_> {
+        core::future::ready(self.try_borrow().map_err(|_| LockError::WouldBlock))
+    }
+
+    fn write(&self) -> Self::WriteFuture<'_> {
+        core::future::ready(self.try_borrow_mut().map_err(|_| LockError::WouldBlock))
+    }
+
+    fn new(inner: T) -> Self {
+        atomic_refcell::AtomicRefCell::new(inner)
+    }
+}
+
 #[cfg(feature = "async-lock")]
 mod async_lock_impl {
     use super::AsyncLockApi;
@@ -192,7 +223,7 @@ mod tokio_impl {
     use super::AsyncLockApi;
     use crate::{
         error::Result,
-        locking::{LockApiReadGuard, LockApiWriteGuard},
+        locking::{FairLock, LockApiReadGuard, LockApiWriteGuard},
     };
     use core::{
         future::Future,
@@ -243,6 +274,8 @@ mod tokio_impl {
         }
     }
 
+    impl<T> FairLock for Mutex<T> {}
+
     // RwLock
 
     impl<'a, T> LockApiReadGuard<'a, T> for RwLockReadGuard<'a, T> {
@@ -288,6 +321,8 @@ mod tokio_impl {
             RwLock::new(inner)
         }
     }
+
+    impl<T> FairLock for RwLock<T> {}
 }
 
 #[cfg(all(feature = "async-std", not(feature = "async-lock")))]
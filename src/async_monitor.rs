@@ -0,0 +1,152 @@
+use core::marker::PhantomData;
+
+use crate::{
+    async_event::AsyncEventApi,
+    async_locking::AsyncLockApi,
+    error::Result,
+    locking::{LockApiReadGuard, LockApiWriteGuard},
+};
+
+/// The async counterpart of [`Monitor`](crate::Monitor): an async lock and an
+/// [`AsyncEventApi`] event, such as tokio's `Notify` or event-listener's
+/// `Event`, in one.
+///
+/// ```ignore
+/// let jobs = AsyncMonitor::<Vec<Job>, tokio::sync::RwLock<_>, Notify>::new(Vec::new());
+/// jobs.write_and_notify(|jobs| jobs.push(job)).await?;
+/// let mut jobs = jobs.wait_until(|jobs| !jobs.is_empty()).await?;
+/// ```
+pub struct AsyncMonitor<T, L, E> {
+    lock: L,
+    event: E,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T, L, E> AsyncMonitor<T, L, E>
+where
+    L: AsyncLockApi<T>,
+    E: AsyncEventApi,
+{
+    pub fn new(inner: T) -> AsyncMonitor<T, L, E> {
+        AsyncMonitor::from_parts(L::new(inner), E::new())
+    }
+
+    pub fn from_parts(lock: L, event: E) -> AsyncMonitor<T, L, E> {
+        AsyncMonitor {
+            lock,
+            event,
+            _value: PhantomData,
+        }
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.lock
+    }
+
+    pub fn event(&self) -> &E {
+        &self.event
+    }
+
+    pub fn into_inner(self) -> L {
+        self.lock
+    }
+
+    /// Takes the write lock once `predicate` holds, checking it under the
+    /// lock first and again after every notification.
+    pub async fn wait_until<F>(&self, mut predicate: F) -> Result<L::WriteGuard<'_>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let guard = self.lock.write().await?;
+        self.wait_while(guard, |value| !predicate(value)).await
+    }
+
+    /// Releases `guard`, waits for a notification and reacquires the write
+    /// lock. Wakeups may be spurious; prefer
+    /// [`wait_until`](AsyncMonitor::wait_until).
+    pub async fn wait<'a>(&'a self, guard: L::WriteGuard<'a>) -> Result<L::WriteGuard<'a>> {
+        let listener = self.event.listen();
+        drop(guard);
+        listener.await;
+        self.lock.write().await
+    }
+
+    /// Waits with `guard` for as long as `condition` holds.
+    pub async fn wait_while<'a, F>(
+        &'a self,
+        mut guard: L::WriteGuard<'a>,
+        mut condition: F,
+    ) -> Result<L::WriteGuard<'a>>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        while condition(guard.get()) {
+            guard = self.wait(guard).await?;
+        }
+        Ok(guard)
+    }
+
+    pub fn notify_one(&self) {
+        self.event.notify_one()
+    }
+
+    pub fn notify_all(&self) {
+        self.event.notify_all()
+    }
+
+    /// Updates the value and wakes every waiter once the lock is released.
+    pub async fn write_and_notify<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R> {
+        let ret = f(self.lock.write().await?.get_mut());
+        self.event.notify_all();
+        Ok(ret)
+    }
+}
+
+impl<T, L, E> Default for AsyncMonitor<T, L, E>
+where
+    T: Default,
+    L: AsyncLockApi<T>,
+    E: AsyncEventApi,
+{
+    fn default() -> Self {
+        AsyncMonitor::new(T::default())
+    }
+}
+
+impl<T, L, E> AsyncLockApi<T> for AsyncMonitor<T, L, E>
+where
+    L: AsyncLockApi<T>,
+    E: AsyncEventApi,
+{
+    type ReadGuard<'a>
+        = L::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = L::WriteGuard<'a>
+    where
+        Self: 'a;
+
+    type ReadFuture<'a>
+        = L::ReadFuture<'a>
+    where
+        Self: 'a;
+
+    type WriteFuture<'a>
+        = L::WriteFuture<'a>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Self::ReadFuture<'_> {
+        self.lock.read()
+    }
+
+    fn write(&self) -> Self::WriteFuture<'_> {
+        self.lock.write()
+    }
+
+    fn new(inner: T) -> Self {
+        AsyncMonitor::new(inner)
+    }
+}
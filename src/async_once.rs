@@ -0,0 +1,101 @@
+use core::future::Future;
+
+pub trait AsyncOnceApi<T> {
+    fn get(&self) -> Option<&T>;
+
+    fn get_or_init<'a, F, Fut>(&'a self, init: F) -> impl Future<Output = &'a T> + 'a
+    where
+        F: FnOnce() -> Fut + 'a,
+        Fut: Future<Output = T> + 'a,
+        T: 'a;
+
+    fn get_or_try_init<'a, F, Fut, E>(
+        &'a self,
+        init: F,
+    ) -> impl Future<Output = Result<&'a T, E>> + 'a
+    where
+        F: FnOnce() -> Fut + 'a,
+        Fut: Future<Output = Result<T, E>> + 'a,
+        T: 'a,
+        E: 'a;
+
+    fn new() -> Self;
+}
+
+#[cfg(feature = "async-lock")]
+mod async_lock_impl {
+    use super::AsyncOnceApi;
+    use async_lock::OnceCell;
+    use core::future::Future;
+
+    impl<T> AsyncOnceApi<T> for OnceCell<T> {
+        fn get(&self) -> Option<&T> {
+            self.get()
+        }
+
+        fn get_or_init<'a, F, Fut>(&'a self, init: F) -> impl Future<Output = &'a T> + 'a
+        where
+            F: FnOnce() -> Fut + 'a,
+            Fut: Future<Output = T> + 'a,
+            T: 'a,
+        {
+            self.get_or_init(init)
+        }
+
+        fn get_or_try_init<'a, F, Fut, E>(
+            &'a self,
+            init: F,
+        ) -> impl Future<Output = Result<&'a T, E>> + 'a
+        where
+            F: FnOnce() -> Fut + 'a,
+            Fut: Future<Output = Result<T, E>> + 'a,
+            T: 'a,
+            E: 'a,
+        {
+            self.get_or_try_init(init)
+        }
+
+        fn new() -> Self {
+            OnceCell::new()
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_impl {
+    use super::AsyncOnceApi;
+    use core::future::Future;
+    use tokio::sync::OnceCell;
+
+    impl<T> AsyncOnceApi<T> for OnceCell<T> {
+        fn get(&self) -> Option<&T> {
+            self.get()
+        }
+
+        fn get_or_init<'a, F, Fut>(&'a self, init: F) -> impl Future<Output = &'a T> + 'a
+        where
+            F: FnOnce() -> Fut + 'a,
+            Fut: Future<Output = T> + 'a,
+            T: 'a,
+        {
+            self.get_or_init(init)
+        }
+
+        fn get_or_try_init<'a, F, Fut, E>(
+            &'a self,
+            init: F,
+        ) -> impl Future<Output = Result<&'a T, E>> + 'a
+        where
+            F: FnOnce() -> Fut + 'a,
+            Fut: Future<Output = Result<T, E>> + 'a,
+            T: 'a,
+            E: 'a,
+        {
+            self.get_or_try_init(init)
+        }
+
+        fn new() -> Self {
+            OnceCell::new()
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use pin_project_lite::pin_project;
+
+use crate::error::{LockError, Result};
+
+/// A source of delays for async timeouts, so they do not depend on one
+/// executor. Implemented for `futures-timer` and `async-io` behind the
+/// features of the same name.
+pub trait AsyncTimer {
+    type Delay: Future<Output = ()>;
+
+    fn delay(duration: Duration) -> Self::Delay;
+}
+
+/// Delays from `futures-timer`, which runs its own timer thread and works
+/// with any executor.
+#[cfg(feature = "futures-timer")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FuturesTimer;
+
+#[cfg(feature = "futures-timer")]
+impl AsyncTimer for FuturesTimer {
+    type Delay = futures_timer::Delay;
+
+    fn delay(duration: Duration) -> Self::Delay {
+        futures_timer::Delay::new(duration)
+    }
+}
+
+/// Delays from `async-io`, the reactor behind smol and async-std.
+#[cfg(feature = "async-io")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsyncIoTimer;
+
+#[cfg(feature = "async-io")]
+impl AsyncTimer for AsyncIoTimer {
+    type Delay = AsyncIoDelay;
+
+    fn delay(duration: Duration) -> Self::Delay {
+        AsyncIoDelay {
+            timer: async_io::Timer::after(duration),
+        }
+    }
+}
+
+#[cfg(feature = "async-io")]
+pin_project! {
+    pub struct AsyncIoDelay {
+        #[pin]
+        timer: async_io::Timer,
+    }
+}
+
+#[cfg(feature = "async-io")]
+impl Future for AsyncIoDelay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        self.project().timer.poll(cx).map(|_| ())
+    }
+}
+
+/// The timer used by [`AsyncTimedLockApi`], `futures-timer` if both are
+/// enabled.
+#[cfg(feature = "futures-timer")]
+pub type DefaultTimer = FuturesTimer;
+
+/// The timer used by [`AsyncTimedLockApi`], `futures-timer` if both are
+/// enabled.
+#[cfg(all(feature = "async-io", not(feature = "futures-timer")))]
+pub type DefaultTimer = AsyncIoTimer;
+
+/// Async acquisition bounded in time, failing with [`LockError::Timeout`].
+/// Dropping the pending acquisition when the delay fires is safe for every
+/// backend, so this holds for all async lockets.
+#[cfg(any(feature = "futures-timer", feature = "async-io"))]
+pub trait AsyncTimedLockApi<T>: crate::async_locking::AsyncLockApi<T> {
+    fn read_timeout_async(
+        &self,
+        timeout: Duration,
+    ) -> Timeout<Self::ReadFuture<'_>, <DefaultTimer as AsyncTimer>::Delay> {
+        Timeout::new(self.read(), DefaultTimer::delay(timeout))
+    }
+
+    fn write_timeout_async(
+        &self,
+        timeout: Duration,
+    ) -> Timeout<Self::WriteFuture<'_>, <DefaultTimer as AsyncTimer>::Delay> {
+        Timeout::new(self.write(), DefaultTimer::delay(timeout))
+    }
+}
+
+#[cfg(any(feature = "futures-timer", feature = "async-io"))]
+impl<L, T> AsyncTimedLockApi<T> for L where L: crate::async_locking::AsyncLockApi<T> {}
+
+pin_project! {
+    /// Resolves to the acquisition, or to [`LockError::Timeout`] if `delay`
+    /// finishes first. Any delay future works, e.g. `tokio::time::sleep`.
+    pub struct Timeout<F, D> {
+        #[pin]
+        future: F,
+        #[pin]
+        delay: D,
+    }
+}
+
+impl<F, D> Timeout<F, D> {
+    pub fn new(future: F, delay: D) -> Timeout<F, D> {
+        Timeout { future, delay }
+    }
+}
+
+impl<F, D, G> Future for Timeout<F, D>
+where
+    F: Future<Output = Result<G>>,
+    D: Future<Output = ()>,
+{
+    type Output = Result<G>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if let Poll::Ready(ret) = this.future.poll(cx) {
+            return Poll::Ready(ret);
+        }
+        match this.delay.poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Err(LockError::Timeout)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,425 @@
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    backoff::Backoff,
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    stats::LockStats,
+    try_lock::TryLockApi,
+};
+
+/// A value which can be stored in a native atomic.
+pub trait AtomicValue: Copy {
+    type Atomic;
+
+    fn into_atomic(self) -> Self::Atomic;
+
+    fn load(atomic: &Self::Atomic) -> Self;
+
+    fn store(atomic: &Self::Atomic, value: Self);
+}
+
+macro_rules! atomic_value {
+    ($($ty:ty => $atomic:ident, $width:literal;)*) => {
+        $(
+            #[cfg(target_has_atomic = $width)]
+            impl AtomicValue for $ty {
+                type Atomic = core::sync::atomic::$atomic;
+
+                fn into_atomic(self) -> Self::Atomic {
+                    core::sync::atomic::$atomic::new(self)
+                }
+
+                fn load(atomic: &Self::Atomic) -> Self {
+                    atomic.load(Ordering::Acquire)
+                }
+
+                fn store(atomic: &Self::Atomic, value: Self) {
+                    atomic.store(value, Ordering::Release)
+                }
+            }
+        )*
+    };
+}
+
+atomic_value! {
+    bool => AtomicBool, "8";
+    u8 => AtomicU8, "8";
+    i8 => AtomicI8, "8";
+    u16 => AtomicU16, "16";
+    i16 => AtomicI16, "16";
+    u32 => AtomicU32, "32";
+    i32 => AtomicI32, "32";
+    u64 => AtomicU64, "64";
+    i64 => AtomicI64, "64";
+    usize => AtomicUsize, "ptr";
+    isize => AtomicIsize, "ptr";
+}
+
+#[cfg(all(feature = "bytemuck", target_has_atomic = "64"))]
+pub use self::pod::Pod;
+
+#[cfg(all(feature = "bytemuck", target_has_atomic = "64"))]
+mod pod {
+    use super::AtomicValue;
+    use core::{
+        mem::size_of,
+        sync::atomic::{AtomicU64, Ordering},
+    };
+
+    /// Stores any plain-old-data value of at most 8 bytes in an `AtomicU64`.
+    ///
+    /// `T` has to be valid for every bit pattern, since [`AtomicValue::load`]
+    /// accepts any `AtomicU64`, so types with invalid values are rejected:
+    ///
+    /// ```compile_fail
+    /// use locket::{AtomicLock, Pod};
+    ///
+    /// let flag = AtomicLock::new(Pod(true));
+    /// ```
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+    #[repr(transparent)]
+    pub struct Pod<T>(pub T);
+
+    fn to_bits<T: bytemuck::Pod>(value: T) -> u64 {
+        const { assert!(size_of::<T>() <= 8, "Pod values must fit in 8 bytes") };
+        let mut bytes = [0u8; 8];
+        bytes[..size_of::<T>()].copy_from_slice(bytemuck::bytes_of(&value));
+        u64::from_ne_bytes(bytes)
+    }
+
+    fn from_bits<T: bytemuck::Pod>(bits: u64) -> T {
+        bytemuck::pod_read_unaligned(&bits.to_ne_bytes()[..size_of::<T>()])
+    }
+
+    impl<T> AtomicValue for Pod<T>
+    where
+        T: bytemuck::Pod,
+    {
+        type Atomic = AtomicU64;
+
+        fn into_atomic(self) -> Self::Atomic {
+            AtomicU64::new(to_bits(self.0))
+        }
+
+        fn load(atomic: &Self::Atomic) -> Self {
+            Pod(from_bits(atomic.load(Ordering::Acquire)))
+        }
+
+        fn store(atomic: &Self::Atomic, value: Self) {
+            atomic.store(to_bits(value.0), Ordering::Release)
+        }
+    }
+}
+
+/// A lock over a value stored in a native atomic. Reads are a single atomic
+/// load; writers take an exclusive flag and store the value when the guard is
+/// dropped.
+pub struct AtomicLock<T>
+where
+    T: AtomicValue,
+{
+    value: T::Atomic,
+    writer: AtomicBool,
+    backoff: Backoff,
+    #[cfg(all(feature = "async", feature = "alloc"))]
+    wakers: crate::wakers::WakerList,
+}
+
+impl<T> AtomicLock<T>
+where
+    T: AtomicValue,
+{
+    pub fn new(inner: T) -> AtomicLock<T> {
+        AtomicLock::with_backoff(inner, Backoff::Spin)
+    }
+
+    pub fn with_backoff(inner: T, backoff: Backoff) -> AtomicLock<T> {
+        AtomicLock {
+            value: inner.into_atomic(),
+            writer: AtomicBool::new(false),
+            backoff,
+            #[cfg(all(feature = "async", feature = "alloc"))]
+            wakers: crate::wakers::WakerList::new(),
+        }
+    }
+
+    pub fn backoff(&self) -> Backoff {
+        self.backoff
+    }
+
+    pub fn load(&self) -> T {
+        T::load(&self.value)
+    }
+
+    pub fn into_inner(self) -> T {
+        T::load(&self.value)
+    }
+
+    fn try_begin_write(&self) -> Option<AtomicWriteGuard<'_, T>> {
+        self.writer
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        Some(AtomicWriteGuard {
+            value: self.load(),
+            lock: self,
+        })
+    }
+}
+
+impl<T: AtomicValue + Default> Default for AtomicLock<T> {
+    fn default() -> Self {
+        AtomicLock::new(T::default())
+    }
+}
+
+impl<T> LockApi<T> for AtomicLock<T>
+where
+    T: AtomicValue,
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = AtomicReadGuard<'a, T>;
+
+    type WriteGuard<'a> = AtomicWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        Ok(AtomicReadGuard {
+            value: self.load(),
+            _lock: PhantomData,
+        })
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        let mut snooze = self.backoff.start();
+        loop {
+            if let Some(guard) = self.try_begin_write() {
+                return Ok(guard);
+            }
+            snooze.snooze();
+        }
+    }
+
+    fn new(inner: T) -> Self {
+        AtomicLock::new(inner)
+    }
+}
+
+impl<T> IntoInner<T> for AtomicLock<T>
+where
+    T: AtomicValue,
+{
+    fn into_inner(self) -> Result<T> {
+        Ok(AtomicLock::into_inner(self))
+    }
+}
+
+impl<T> TryLockApi<T> for AtomicLock<T>
+where
+    T: AtomicValue,
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        LockApi::read(self)
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.try_begin_write().ok_or(LockError::WouldBlock)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> crate::timed::TimedLockApi<T> for AtomicLock<T>
+where
+    T: AtomicValue,
+    for<'a> T: 'a,
+{
+}
+
+// Readers do not hold the lock, so only a writer shows up.
+impl<T> LockStats for AtomicLock<T>
+where
+    T: AtomicValue,
+{
+    fn is_locked(&self) -> bool {
+        self.writer.load(Ordering::Relaxed)
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        self.writer.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> core::fmt::Debug for AtomicLock<T>
+where
+    T: AtomicValue + core::fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("AtomicLock")
+            .field("data", &crate::peek::peek(self))
+            .finish()
+    }
+}
+
+impl<T> PoisonApi for AtomicLock<T> where T: AtomicValue {}
+
+pub struct AtomicReadGuard<'a, T>
+where
+    T: AtomicValue,
+{
+    value: T,
+    _lock: PhantomData<&'a AtomicLock<T>>,
+}
+
+impl<T> Deref for AtomicReadGuard<'_, T>
+where
+    T: AtomicValue,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for AtomicReadGuard<'a, T>
+where
+    T: AtomicValue,
+{
+    fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+pub struct AtomicWriteGuard<'a, T>
+where
+    T: AtomicValue,
+{
+    value: T,
+    lock: &'a AtomicLock<T>,
+}
+
+impl<T> Drop for AtomicWriteGuard<'_, T>
+where
+    T: AtomicValue,
+{
+    fn drop(&mut self) {
+        T::store(&self.lock.value, self.value);
+        self.lock.writer.store(false, Ordering::Release);
+        #[cfg(all(feature = "async", feature = "alloc"))]
+        self.lock.wakers.wake_all();
+    }
+}
+
+impl<T> Deref for AtomicWriteGuard<'_, T>
+where
+    T: AtomicValue,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for AtomicWriteGuard<'_, T>
+where
+    T: AtomicValue,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for AtomicWriteGuard<'a, T>
+where
+    T: AtomicValue,
+{
+    fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for AtomicWriteGuard<'a, T>
+where
+    T: AtomicValue,
+{
+    fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+#[cfg(all(feature = "async", feature = "alloc"))]
+pub use self::async_impl::AtomicWriteFuture;
+
+#[cfg(all(feature = "async", feature = "alloc"))]
+mod async_impl {
+    use super::{AtomicLock, AtomicReadGuard, AtomicValue, AtomicWriteGuard};
+    use crate::{async_locking::AsyncLockApi, error::Result};
+    use core::{
+        future::Future,
+        marker::PhantomData,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    impl<T> AsyncLockApi<T> for AtomicLock<T>
+    where
+        T: AtomicValue,
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = AtomicReadGuard<'a, T>;
+
+        type WriteGuard<'a> = AtomicWriteGuard<'a, T>;
+
+        type ReadFuture<'a> = core::future::Ready<Result<Self::ReadGuard<'a>>>;
+
+        type WriteFuture<'a> = AtomicWriteFuture<'a, T>;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            core::future::ready(Ok(AtomicReadGuard {
+                value: self.load(),
+                _lock: PhantomData,
+            }))
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            AtomicWriteFuture { lock: self }
+        }
+
+        fn new(inner: T) -> Self {
+            AtomicLock::new(inner)
+        }
+    }
+
+    /// Retries acquiring the write side whenever a write guard is dropped.
+    pub struct AtomicWriteFuture<'a, T>
+    where
+        T: AtomicValue,
+    {
+        lock: &'a AtomicLock<T>,
+    }
+
+    impl<'a, T> Future for AtomicWriteFuture<'a, T>
+    where
+        T: AtomicValue,
+    {
+        type Output = Result<AtomicWriteGuard<'a, T>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if let Some(guard) = self.lock.try_begin_write() {
+                return Poll::Ready(Ok(guard));
+            }
+            self.lock.wakers.register(cx.waker());
+            match self.lock.try_begin_write() {
+                Some(guard) => Poll::Ready(Ok(guard)),
+                None => Poll::Pending,
+            }
+        }
+    }
+}
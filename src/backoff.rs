@@ -0,0 +1,78 @@
+use core::hint;
+
+/// How the spinning backends ([`DoubleBuffered`](crate::DoubleBuffered),
+/// [`SeqLock`](crate::SeqLock) and [`AtomicLock`](crate::AtomicLock)) wait
+/// between attempts to take a contended lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Backoff {
+    /// A single spin hint per attempt.
+    #[default]
+    Spin,
+    /// The given number of spin hints per attempt.
+    SpinHints(u32),
+    /// Yields the thread to the OS scheduler. Without the `std` feature this
+    /// falls back to a spin hint.
+    Yield,
+    /// Doubles the spin hints per attempt up to `2^limit`, then yields like
+    /// [`Backoff::Yield`].
+    Exponential { limit: u32 },
+}
+
+impl Backoff {
+    pub(crate) fn start(self) -> Snooze {
+        Snooze {
+            backoff: self,
+            step: 0,
+        }
+    }
+}
+
+/// The state of one acquisition's backoff.
+pub(crate) struct Snooze {
+    backoff: Backoff,
+    step: u32,
+}
+
+impl Snooze {
+    pub(crate) fn snooze(&mut self) {
+        match self.backoff {
+            Backoff::Spin => hint::spin_loop(),
+            Backoff::SpinHints(count) => spin(count),
+            Backoff::Yield => yield_now(),
+            Backoff::Exponential { limit } => {
+                if self.step <= limit.min(31) {
+                    spin(1 << self.step);
+                    self.step += 1;
+                } else {
+                    yield_now();
+                }
+            }
+        }
+    }
+}
+
+fn spin(count: u32) {
+    for _ in 0..count {
+        hint::spin_loop();
+    }
+}
+
+fn yield_now() {
+    #[cfg(feature = "std")]
+    std::thread::yield_now();
+    #[cfg(not(feature = "std"))]
+    hint::spin_loop();
+}
+
+/// A `spin` relax strategy issuing `N` spin hints per attempt. The `spin`
+/// crate's own `Spin`, `Yield` and `Loop` strategies work as well.
+#[cfg(feature = "spin")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SpinHints<const N: u32>;
+
+#[cfg(feature = "spin")]
+impl<const N: u32> spin::relax::RelaxStrategy for SpinHints<N> {
+    fn relax() {
+        spin(N);
+    }
+}
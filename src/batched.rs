@@ -0,0 +1,138 @@
+use alloc::{boxed::Box, collections::VecDeque, sync::Arc};
+use core::{panic::AssertUnwindSafe, time::Duration};
+use std::{
+    panic,
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{
+    error::{LockError, Result},
+    locking::{LockApi, LockApiWriteGuard},
+    try_lock::TryLockApi,
+};
+
+type Mutation<T> = Box<dyn FnOnce(&mut T) + Send>;
+
+// A lock released by a plain reader or writer does not wake publishers, so
+// they try to combine again after this long.
+const RECHECK: Duration = Duration::from_millis(1);
+
+/// Flat-combining writes: callers of [`apply`](Batched::apply) publish their
+/// mutation and whichever of them gets the lock runs every pending mutation in
+/// one critical section, while the others wait for their result.
+pub struct Batched<L, T> {
+    inner: L,
+    queue: Mutex<VecDeque<Mutation<T>>>,
+    combined: Condvar,
+}
+
+impl<L, T> Batched<L, T> {
+    pub fn wrap(inner: L) -> Batched<L, T> {
+        Batched {
+            inner,
+            queue: Mutex::new(VecDeque::new()),
+            combined: Condvar::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    fn queue(&self) -> MutexGuard<'_, VecDeque<Mutation<T>>> {
+        self.queue.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn combine(&self, value: &mut T) {
+        loop {
+            // Pop in a statement of its own so the queue is unlocked while the
+            // mutation runs.
+            let next = self.queue().pop_front();
+            match next {
+                Some(mutation) => mutation(value),
+                None => break,
+            }
+        }
+    }
+}
+
+impl<L, T> Batched<L, T>
+where
+    L: TryLockApi<T>,
+{
+    /// Runs `mutation` under the write lock, possibly on another thread, and
+    /// returns its result. A panic in `mutation` is caught on the combining
+    /// thread and resumed on this one.
+    pub fn apply<F, R>(&self, mutation: F) -> Result<R>
+    where
+        F: FnOnce(&mut T) -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let slot = Arc::new(Mutex::new(None));
+        let result = slot.clone();
+        let mut queue = self.queue();
+        queue.push_back(Box::new(move |value| {
+            let output = panic::catch_unwind(AssertUnwindSafe(|| mutation(value)));
+            *result.lock().unwrap_or_else(PoisonError::into_inner) = Some(output);
+        }));
+
+        // The queue stays locked from checking the slot until waiting, and a
+        // combiner notifies under it, so a finished round is not missed.
+        loop {
+            if let Some(output) = slot.lock().unwrap_or_else(PoisonError::into_inner).take() {
+                return Ok(output.unwrap_or_else(|payload| panic::resume_unwind(payload)));
+            }
+            match self.inner.try_write() {
+                Ok(mut guard) => {
+                    drop(queue);
+                    self.combine(guard.get_mut());
+                    drop(guard);
+                    queue = self.queue();
+                    self.combined.notify_all();
+                }
+                Err(LockError::WouldBlock) => {
+                    queue = self
+                        .combined
+                        .wait_timeout(queue, RECHECK)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+impl<L, T> LockApi<T> for Batched<L, T>
+where
+    L: TryLockApi<T>,
+{
+    type ReadGuard<'a>
+        = L::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = L::WriteGuard<'a>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.inner.read()
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        let mut guard = self.inner.write()?;
+        // A plain writer takes its turn as combiner as well.
+        self.combine(guard.get_mut());
+        Ok(guard)
+    }
+
+    fn new(inner: T) -> Self {
+        Batched::wrap(L::new(inner))
+    }
+}
@@ -0,0 +1,178 @@
+#[cfg(feature = "alloc")]
+use alloc::{rc::Rc, sync::Arc};
+use core::cell::RefCell;
+
+use crate::{async_locking::AsyncLockApi, error::Result};
+
+/// Synchronous access to an async locket, for the parts of a program which do
+/// not run on an executor. The calling thread is blocked until the lock is
+/// taken, so do not call these from async code: tokio panics when they are
+/// called inside its runtime.
+pub trait BlockingAsyncLockApi<T>: AsyncLockApi<T> {
+    fn blocking_read(&self) -> Result<Self::ReadGuard<'_>>;
+
+    fn blocking_write(&self) -> Result<Self::WriteGuard<'_>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<L, T> BlockingAsyncLockApi<T> for Arc<L>
+where
+    L: BlockingAsyncLockApi<T>,
+    for<'a> L: 'a,
+{
+    fn blocking_read(&self) -> Result<Self::ReadGuard<'_>> {
+        (**self).blocking_read()
+    }
+
+    fn blocking_write(&self) -> Result<Self::WriteGuard<'_>> {
+        (**self).blocking_write()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L, T> BlockingAsyncLockApi<T> for Rc<L>
+where
+    L: BlockingAsyncLockApi<T>,
+    for<'a> L: 'a,
+{
+    fn blocking_read(&self) -> Result<Self::ReadGuard<'_>> {
+        (**self).blocking_read()
+    }
+
+    fn blocking_write(&self) -> Result<Self::WriteGuard<'_>> {
+        (**self).blocking_write()
+    }
+}
+
+impl<T> BlockingAsyncLockApi<T> for RefCell<T>
+where
+    for<'a> T: 'a,
+{
+    fn blocking_read(&self) -> Result<Self::ReadGuard<'_>> {
+        crate::locking::LockApi::read(self)
+    }
+
+    fn blocking_write(&self) -> Result<Self::WriteGuard<'_>> {
+        crate::locking::LockApi::write(self)
+    }
+}
+
+#[cfg(feature = "atomic_refcell")]
+impl<T> BlockingAsyncLockApi<T> for atomic_refcell::AtomicRefCell<T>
+where
+    for<'a> T: 'a,
+{
+    fn blocking_read(&self) -> Result<Self::ReadGuard<'_>> {
+        crate::locking::LockApi::read(self)
+    }
+
+    fn blocking_write(&self) -> Result<Self::WriteGuard<'_>> {
+        crate::locking::LockApi::write(self)
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_impl {
+    use super::BlockingAsyncLockApi;
+    use crate::error::Result;
+    use tokio::sync::{Mutex, RwLock};
+
+    impl<T> BlockingAsyncLockApi<T> for Mutex<T>
+    where
+        T: Send,
+        for<'a> T: 'a,
+    {
+        fn blocking_read(&self) -> Result<Self::ReadGuard<'_>> {
+            Ok(self.blocking_lock())
+        }
+
+        fn blocking_write(&self) -> Result<Self::WriteGuard<'_>> {
+            Ok(self.blocking_lock())
+        }
+    }
+
+    impl<T> BlockingAsyncLockApi<T> for RwLock<T>
+    where
+        T: Send + Sync,
+        for<'a> T: 'a,
+    {
+        fn blocking_read(&self) -> Result<Self::ReadGuard<'_>> {
+            Ok(RwLock::blocking_read(self))
+        }
+
+        fn blocking_write(&self) -> Result<Self::WriteGuard<'_>> {
+            Ok(RwLock::blocking_write(self))
+        }
+    }
+}
+
+#[cfg(feature = "async-lock")]
+mod async_lock_impl {
+    use super::BlockingAsyncLockApi;
+    use crate::error::Result;
+    use async_lock::{Mutex, RwLock};
+
+    impl<T> BlockingAsyncLockApi<T> for Mutex<T>
+    where
+        T: Send,
+        for<'a> T: 'a,
+    {
+        fn blocking_read(&self) -> Result<Self::ReadGuard<'_>> {
+            Ok(self.lock_blocking())
+        }
+
+        fn blocking_write(&self) -> Result<Self::WriteGuard<'_>> {
+            Ok(self.lock_blocking())
+        }
+    }
+
+    impl<T> BlockingAsyncLockApi<T> for RwLock<T>
+    where
+        T: Send + Sync,
+        for<'a> T: 'a,
+    {
+        fn blocking_read(&self) -> Result<Self::ReadGuard<'_>> {
+            Ok(self.read_blocking())
+        }
+
+        fn blocking_write(&self) -> Result<Self::WriteGuard<'_>> {
+            Ok(self.write_blocking())
+        }
+    }
+}
+
+// async-std has no blocking accessors, so its own executor drives the futures.
+#[cfg(all(feature = "async-std", not(feature = "async-lock")))]
+mod async_std_impl {
+    use super::BlockingAsyncLockApi;
+    use crate::{async_locking::AsyncLockApi, error::Result};
+    use async_std::sync::{Mutex, RwLock};
+
+    impl<T> BlockingAsyncLockApi<T> for Mutex<T>
+    where
+        T: Send,
+        for<'a> T: 'a,
+    {
+        fn blocking_read(&self) -> Result<Self::ReadGuard<'_>> {
+            async_std::task::block_on(AsyncLockApi::read(self))
+        }
+
+        fn blocking_write(&self) -> Result<Self::WriteGuard<'_>> {
+            async_std::task::block_on(AsyncLockApi::write(self))
+        }
+    }
+
+    impl<T> BlockingAsyncLockApi<T> for RwLock<T>
+    where
+        T: Send + Sync,
+        for<'a> T: 'a,
+    {
+        fn blocking_read(&self) -> Result<Self::ReadGuard<'_>> {
+            async_std::task::block_on(AsyncLockApi::read(self))
+        }
+
+        fn blocking_write(&self) -> Result<Self::WriteGuard<'_>> {
+            async_std::task::block_on(AsyncLockApi::write(self))
+        }
+    }
+}
@@ -0,0 +1,298 @@
+use core::{
+    cell::UnsafeCell,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicIsize, Ordering},
+};
+
+use crate::{
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    stats::LockStats,
+    try_lock::TryLockApi,
+};
+
+const WRITER: isize = -1;
+
+/// A `RefCell`-style lock: conflicting access fails with
+/// [`LockError::WouldBlock`] instead of waiting, while the async side waits
+/// to be woken when a guard is dropped. Unlike `RefCell` it is `Sync`, which
+/// makes it a usable default on single-threaded targets such as wasm32.
+pub struct BorrowLock<T: ?Sized> {
+    // 0 when free, the number of readers when positive and `WRITER` while
+    // written.
+    state: AtomicIsize,
+    #[cfg(all(feature = "async", feature = "alloc"))]
+    wakers: crate::wakers::WakerList,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for BorrowLock<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for BorrowLock<T> {}
+
+impl<T> BorrowLock<T> {
+    pub const fn new(inner: T) -> BorrowLock<T> {
+        BorrowLock {
+            state: AtomicIsize::new(0),
+            #[cfg(all(feature = "async", feature = "alloc"))]
+            wakers: crate::wakers::WakerList::new(),
+            data: UnsafeCell::new(inner),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> BorrowLock<T> {
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.state.load(Ordering::Relaxed) != 0
+    }
+
+    fn try_begin_read(&self) -> Option<BorrowReadGuard<'_, T>> {
+        self.state
+            .fetch_update(Ordering::Acquire, Ordering::Relaxed, |state| {
+                (0..isize::MAX).contains(&state).then_some(state + 1)
+            })
+            .ok()?;
+        Some(BorrowReadGuard { lock: self })
+    }
+
+    fn try_begin_write(&self) -> Option<BorrowWriteGuard<'_, T>> {
+        self.state
+            .compare_exchange(0, WRITER, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        Some(BorrowWriteGuard { lock: self })
+    }
+}
+
+impl<T: Default> Default for BorrowLock<T> {
+    fn default() -> Self {
+        BorrowLock::new(T::default())
+    }
+}
+
+impl<T> LockApi<T> for BorrowLock<T>
+where
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = BorrowReadGuard<'a, T>;
+
+    type WriteGuard<'a> = BorrowWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.try_begin_read().ok_or(LockError::WouldBlock)
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.try_begin_write().ok_or(LockError::WouldBlock)
+    }
+
+    fn new(inner: T) -> Self {
+        BorrowLock::new(inner)
+    }
+}
+
+// Locking never waits, so the plain accessors already behave like this.
+impl<T> TryLockApi<T> for BorrowLock<T>
+where
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        LockApi::read(self)
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        LockApi::write(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> crate::timed::TimedLockApi<T> for BorrowLock<T> where for<'a> T: 'a {}
+
+impl<T> IntoInner<T> for BorrowLock<T> {
+    fn into_inner(self) -> Result<T> {
+        Ok(BorrowLock::into_inner(self))
+    }
+}
+
+impl<T: ?Sized> PoisonApi for BorrowLock<T> {}
+
+impl<T: ?Sized> LockStats for BorrowLock<T> {
+    fn is_locked(&self) -> bool {
+        BorrowLock::is_locked(self)
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        self.state.load(Ordering::Relaxed) == WRITER
+    }
+
+    fn reader_count(&self) -> Option<usize> {
+        Some(self.state.load(Ordering::Relaxed).max(0) as usize)
+    }
+}
+
+impl<T> core::fmt::Debug for BorrowLock<T>
+where
+    T: core::fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("BorrowLock")
+            .field("data", &crate::peek::peek(self))
+            .finish()
+    }
+}
+
+pub struct BorrowReadGuard<'a, T: ?Sized> {
+    lock: &'a BorrowLock<T>,
+}
+
+impl<T: ?Sized> Drop for BorrowReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.state.fetch_sub(1, Ordering::Release);
+        #[cfg(all(feature = "async", feature = "alloc"))]
+        self.lock.wakers.wake_all();
+    }
+}
+
+impl<T: ?Sized> Deref for BorrowReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the reader count keeps writers out.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for BorrowReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+pub struct BorrowWriteGuard<'a, T: ?Sized> {
+    lock: &'a BorrowLock<T>,
+}
+
+impl<T: ?Sized> Drop for BorrowWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.state.store(0, Ordering::Release);
+        #[cfg(all(feature = "async", feature = "alloc"))]
+        self.lock.wakers.wake_all();
+    }
+}
+
+impl<T: ?Sized> Deref for BorrowWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the guard holds the lock exclusively.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for BorrowWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the guard holds the lock exclusively.
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for BorrowWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for BorrowWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+#[cfg(all(feature = "async", feature = "alloc"))]
+pub use self::async_impl::{BorrowReadFuture, BorrowWriteFuture};
+
+#[cfg(all(feature = "async", feature = "alloc"))]
+mod async_impl {
+    use super::{BorrowLock, BorrowReadGuard, BorrowWriteGuard};
+    use crate::{async_locking::AsyncLockApi, error::Result};
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    // Neither future blocks the thread, so they are safe to drive from
+    // single-threaded executors like `wasm_bindgen_futures::spawn_local`.
+    impl<T> AsyncLockApi<T> for BorrowLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = BorrowReadGuard<'a, T>;
+
+        type WriteGuard<'a> = BorrowWriteGuard<'a, T>;
+
+        type ReadFuture<'a> = BorrowReadFuture<'a, T>;
+
+        type WriteFuture<'a> = BorrowWriteFuture<'a, T>;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            BorrowReadFuture { lock: self }
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            BorrowWriteFuture { lock: self }
+        }
+
+        fn new(inner: T) -> Self {
+            BorrowLock::new(inner)
+        }
+    }
+
+    /// Retries acquiring the read side whenever a guard is dropped.
+    pub struct BorrowReadFuture<'a, T: ?Sized> {
+        lock: &'a BorrowLock<T>,
+    }
+
+    impl<'a, T: ?Sized> Future for BorrowReadFuture<'a, T> {
+        type Output = Result<BorrowReadGuard<'a, T>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if let Some(guard) = self.lock.try_begin_read() {
+                return Poll::Ready(Ok(guard));
+            }
+            self.lock.wakers.register(cx.waker());
+            match self.lock.try_begin_read() {
+                Some(guard) => Poll::Ready(Ok(guard)),
+                None => Poll::Pending,
+            }
+        }
+    }
+
+    /// Retries acquiring the write side whenever a guard is dropped.
+    pub struct BorrowWriteFuture<'a, T: ?Sized> {
+        lock: &'a BorrowLock<T>,
+    }
+
+    impl<'a, T: ?Sized> Future for BorrowWriteFuture<'a, T> {
+        type Output = Result<BorrowWriteGuard<'a, T>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if let Some(guard) = self.lock.try_begin_write() {
+                return Poll::Ready(Ok(guard));
+            }
+            self.lock.wakers.register(cx.waker());
+            match self.lock.try_begin_write() {
+                Some(guard) => Poll::Ready(Ok(guard)),
+                None => Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,406 @@
+use alloc::{rc::Rc, sync::Arc};
+use core::marker::PhantomData;
+
+use crate::{policy::RwPolicy, rwlock::PolicyRwLock};
+
+/// Starts a [`LocketBuilder`] for the locket type `H`, also reachable as
+/// [`Locket::builder`](crate::Locket::builder).
+pub fn builder<H>() -> LocketBuilder<H> {
+    LocketBuilder::new()
+}
+
+/// An option of a [`LocketBuilder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum BuildOption {
+    Name,
+    #[cfg(feature = "named")]
+    Labels,
+    Policy,
+    MaxReaders,
+    Traced,
+}
+
+impl core::fmt::Display for BuildOption {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            BuildOption::Name => write!(f, "name"),
+            #[cfg(feature = "named")]
+            BuildOption::Labels => write!(f, "labels"),
+            BuildOption::Policy => write!(f, "policy"),
+            BuildOption::MaxReaders => write!(f, "max_readers"),
+            BuildOption::Traced => write!(f, "traced"),
+        }
+    }
+}
+
+/// Returned by [`LocketBuilder::build`] when an option was set which no layer
+/// of the built type applies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildError {
+    pub option: BuildOption,
+}
+
+impl core::fmt::Display for BuildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "no layer of the locket applies the `{}` option",
+            self.option
+        )
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Options collected by a [`LocketBuilder`]. Each layer of the built locket
+/// takes the ones which apply to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BuildOptions {
+    name: Option<&'static str>,
+    #[cfg(feature = "named")]
+    labels: crate::named::Labels,
+    policy: Option<RwPolicy>,
+    max_readers: Option<usize>,
+    traced: bool,
+}
+
+impl BuildOptions {
+    pub const fn new() -> BuildOptions {
+        BuildOptions {
+            name: None,
+            #[cfg(feature = "named")]
+            labels: &[],
+            policy: None,
+            max_readers: None,
+            traced: false,
+        }
+    }
+
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// The name, or else the type name of `L`.
+    pub fn name_or_type<L: ?Sized>(&self) -> &'static str {
+        self.name.unwrap_or(core::any::type_name::<L>())
+    }
+
+    #[cfg(feature = "named")]
+    pub fn labels(&self) -> crate::named::Labels {
+        self.labels
+    }
+
+    pub fn policy(&self) -> RwPolicy {
+        self.policy.unwrap_or(RwPolicy::WritePreferring)
+    }
+
+    pub fn max_readers(&self) -> Option<usize> {
+        self.max_readers
+    }
+
+    pub fn traced(&self) -> bool {
+        self.traced
+    }
+
+    /// Whether `option` was set.
+    pub fn is_set(&self, option: BuildOption) -> bool {
+        match option {
+            BuildOption::Name => self.name.is_some(),
+            #[cfg(feature = "named")]
+            BuildOption::Labels => !self.labels.is_empty(),
+            BuildOption::Policy => self.policy.is_some(),
+            BuildOption::MaxReaders => self.max_readers.is_some(),
+            BuildOption::Traced => self.traced,
+        }
+    }
+
+    fn options() -> impl Iterator<Item = BuildOption> {
+        [
+            BuildOption::Name,
+            #[cfg(feature = "named")]
+            BuildOption::Labels,
+            BuildOption::Policy,
+            BuildOption::MaxReaders,
+            BuildOption::Traced,
+        ]
+        .into_iter()
+    }
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        BuildOptions::new()
+    }
+}
+
+/// A backend, wrapper or handle which [`LocketBuilder`] can construct.
+pub trait Buildable<T>: Sized {
+    /// Whether this layer, or one it wraps, applies `option`.
+    fn applies(option: BuildOption) -> bool {
+        let _ = option;
+        false
+    }
+
+    fn build(inner: T, options: &BuildOptions) -> Self;
+}
+
+/// Builds the locket type `H`, a backend nested in wrappers and usually a
+/// handle, with options set in one place instead of passed to each layer:
+///
+/// ```ignore
+/// let users: Arc<Traced<Named<PolicyRwLock<Users>>>> = Locket::builder()
+///     .name("users")
+///     .fair(true)
+///     .max_readers(64)
+///     .traced(true)
+///     .build(users)?;
+/// ```
+///
+/// The name goes to [`Named`](crate::Named), [`Traced`](crate::Traced) and
+/// [`Hooked`](crate::Hooked), the policy and reader limit to [`PolicyRwLock`].
+/// Building fails with a [`BuildError`] if an option was set which no layer of
+/// `H` applies, rather than dropping it; `.traced(true)` only asks for a
+/// [`Traced`](crate::Traced) layer to be there.
+pub struct LocketBuilder<H> {
+    options: BuildOptions,
+    _handle: PhantomData<fn() -> H>,
+}
+
+impl<H> LocketBuilder<H> {
+    pub const fn new() -> LocketBuilder<H> {
+        LocketBuilder {
+            options: BuildOptions::new(),
+            _handle: PhantomData,
+        }
+    }
+
+    pub const fn name(mut self, name: &'static str) -> LocketBuilder<H> {
+        self.options.name = Some(name);
+        self
+    }
+
+    #[cfg(feature = "named")]
+    pub const fn labels(mut self, labels: crate::named::Labels) -> LocketBuilder<H> {
+        self.options.labels = labels;
+        self
+    }
+
+    /// Shorthand for [`RwPolicy::PhaseFair`], or the default
+    /// [`RwPolicy::WritePreferring`].
+    pub const fn fair(self, fair: bool) -> LocketBuilder<H> {
+        self.policy(match fair {
+            true => RwPolicy::PhaseFair,
+            false => RwPolicy::WritePreferring,
+        })
+    }
+
+    pub const fn policy(mut self, policy: RwPolicy) -> LocketBuilder<H> {
+        self.options.policy = Some(policy);
+        self
+    }
+
+    /// Caps the number of read guards held at once; further readers wait.
+    pub const fn max_readers(mut self, max_readers: usize) -> LocketBuilder<H> {
+        self.options.max_readers = Some(max_readers);
+        self
+    }
+
+    /// Requires the built locket to trace its acquisitions.
+    pub const fn traced(mut self, traced: bool) -> LocketBuilder<H> {
+        self.options.traced = traced;
+        self
+    }
+
+    pub fn options(&self) -> &BuildOptions {
+        &self.options
+    }
+
+    pub fn build<T>(self, value: T) -> Result<H, BuildError>
+    where
+        H: Buildable<T>,
+    {
+        match BuildOptions::options()
+            .find(|&option| self.options.is_set(option) && !H::applies(option))
+        {
+            Some(option) => Err(BuildError { option }),
+            None => Ok(H::build(value, &self.options)),
+        }
+    }
+}
+
+impl<H> Default for LocketBuilder<H> {
+    fn default() -> Self {
+        LocketBuilder::new()
+    }
+}
+
+impl<H> Clone for LocketBuilder<H> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<H> Copy for LocketBuilder<H> {}
+
+impl<H> core::fmt::Debug for LocketBuilder<H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LocketBuilder")
+            .field("options", &self.options)
+            .finish()
+    }
+}
+
+impl<T> Buildable<T> for PolicyRwLock<T> {
+    fn applies(option: BuildOption) -> bool {
+        matches!(option, BuildOption::Policy | BuildOption::MaxReaders)
+    }
+
+    fn build(inner: T, options: &BuildOptions) -> Self {
+        let lock = PolicyRwLock::with_policy(inner, options.policy());
+        match options.max_readers {
+            Some(max_readers) => lock.with_max_readers(max_readers),
+            None => lock,
+        }
+    }
+}
+
+impl<L, T> Buildable<T> for Arc<L>
+where
+    L: Buildable<T>,
+{
+    fn applies(option: BuildOption) -> bool {
+        L::applies(option)
+    }
+
+    fn build(inner: T, options: &BuildOptions) -> Self {
+        Arc::new(L::build(inner, options))
+    }
+}
+
+impl<L, T> Buildable<T> for Rc<L>
+where
+    L: Buildable<T>,
+{
+    fn applies(option: BuildOption) -> bool {
+        L::applies(option)
+    }
+
+    fn build(inner: T, options: &BuildOptions) -> Self {
+        Rc::new(L::build(inner, options))
+    }
+}
+
+#[cfg(feature = "named")]
+impl<L, T> Buildable<T> for crate::named::Named<L>
+where
+    L: Buildable<T>,
+{
+    fn applies(option: BuildOption) -> bool {
+        matches!(option, BuildOption::Name | BuildOption::Labels) || L::applies(option)
+    }
+
+    fn build(inner: T, options: &BuildOptions) -> Self {
+        crate::named::Named::with_labels(
+            L::build(inner, options),
+            options.name_or_type::<L>(),
+            options.labels,
+        )
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<L, T> Buildable<T> for crate::traced::Traced<L>
+where
+    L: Buildable<T>,
+{
+    fn applies(option: BuildOption) -> bool {
+        matches!(option, BuildOption::Name | BuildOption::Traced) || L::applies(option)
+    }
+
+    fn build(inner: T, options: &BuildOptions) -> Self {
+        crate::traced::Traced::with_name(L::build(inner, options), options.name_or_type::<L>())
+    }
+}
+
+#[cfg(feature = "hooks")]
+impl<L, T> Buildable<T> for crate::hooked::Hooked<L>
+where
+    L: Buildable<T>,
+{
+    fn applies(option: BuildOption) -> bool {
+        option == BuildOption::Name || L::applies(option)
+    }
+
+    fn build(inner: T, options: &BuildOptions) -> Self {
+        crate::hooked::Hooked::with_name(L::build(inner, options), options.name_or_type::<T>())
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl<L, T> Buildable<T> for crate::metrics::Metrics<L>
+where
+    L: Buildable<T>,
+{
+    fn applies(option: BuildOption) -> bool {
+        L::applies(option)
+    }
+
+    fn build(inner: T, options: &BuildOptions) -> Self {
+        crate::metrics::Metrics::wrap(L::build(inner, options))
+    }
+}
+
+#[cfg(feature = "watchdog")]
+impl<L, T> Buildable<T> for crate::watchdog::Watchdog<L>
+where
+    L: Buildable<T>,
+{
+    fn applies(option: BuildOption) -> bool {
+        L::applies(option)
+    }
+
+    fn build(inner: T, options: &BuildOptions) -> Self {
+        crate::watchdog::Watchdog::with_threshold(
+            L::build(inner, options),
+            crate::watchdog::Watchdog::<L>::DEFAULT_THRESHOLD,
+        )
+    }
+}
+
+// Backends without options of their own.
+macro_rules! buildable {
+    ($($ty:ty;)*) => {
+        $(
+            impl<T> Buildable<T> for $ty {
+                fn build(inner: T, _options: &BuildOptions) -> Self {
+                    <$ty>::new(inner)
+                }
+            }
+        )*
+    };
+}
+
+buildable! {
+    std::sync::Mutex<T>;
+    std::sync::RwLock<T>;
+}
+
+#[cfg(feature = "parking_lot")]
+buildable! {
+    parking_lot::Mutex<T>;
+    parking_lot::FairMutex<T>;
+    parking_lot::RwLock<T>;
+}
+
+#[cfg(feature = "spin")]
+buildable! {
+    spin::Mutex<T>;
+    spin::RwLock<T>;
+}
+
+#[cfg(feature = "tokio")]
+buildable! {
+    tokio::sync::Mutex<T>;
+    tokio::sync::RwLock<T>;
+}
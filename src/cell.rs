@@ -0,0 +1,223 @@
+use core::{
+    cell::Cell,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    error::Result,
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    stats::LockStats,
+    try_lock::TryLockApi,
+};
+
+/// A single-threaded locket for `Copy` values on top of `Cell`, with no borrow
+/// flag. Read guards hold a copy of the value; write guards edit a copy and
+/// store it back when dropped. Overlapping write guards do not conflict, the
+/// one dropped last wins.
+pub struct CellLock<T> {
+    value: Cell<T>,
+}
+
+impl<T> CellLock<T> {
+    pub const fn new(inner: T) -> CellLock<T> {
+        CellLock {
+            value: Cell::new(inner),
+        }
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: Copy> CellLock<T> {
+    pub fn get(&self) -> T {
+        self.value.get()
+    }
+
+    pub fn set(&self, value: T) {
+        self.value.set(value);
+    }
+}
+
+impl<T: Copy + Default> Default for CellLock<T> {
+    fn default() -> Self {
+        CellLock::new(T::default())
+    }
+}
+
+impl<T> LockApi<T> for CellLock<T>
+where
+    T: Copy,
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = CellReadGuard<'a, T>;
+
+    type WriteGuard<'a> = CellWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        Ok(CellReadGuard {
+            value: self.value.get(),
+            _lock: core::marker::PhantomData,
+        })
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        Ok(CellWriteGuard {
+            value: self.value.get(),
+            lock: self,
+        })
+    }
+
+    fn new(inner: T) -> Self {
+        CellLock::new(inner)
+    }
+}
+
+impl<T> TryLockApi<T> for CellLock<T>
+where
+    T: Copy,
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        LockApi::read(self)
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        LockApi::write(self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> crate::timed::TimedLockApi<T> for CellLock<T>
+where
+    T: Copy,
+    for<'a> T: 'a,
+{
+}
+
+impl<T> IntoInner<T> for CellLock<T> {
+    fn into_inner(self) -> Result<T> {
+        Ok(CellLock::into_inner(self))
+    }
+}
+
+impl<T> PoisonApi for CellLock<T> {}
+
+// Guards hold copies, so the cell is never locked.
+impl<T> LockStats for CellLock<T> {
+    fn is_locked(&self) -> bool {
+        false
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        false
+    }
+}
+
+impl<T> core::fmt::Debug for CellLock<T>
+where
+    T: Copy + core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CellLock")
+            .field("data", &self.value.get())
+            .finish()
+    }
+}
+
+/// Holds a copy of the value taken when the guard was created.
+pub struct CellReadGuard<'a, T> {
+    value: T,
+    _lock: core::marker::PhantomData<&'a CellLock<T>>,
+}
+
+impl<T> Deref for CellReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for CellReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+/// Holds a copy of the value which is stored back when the guard is dropped.
+pub struct CellWriteGuard<'a, T: Copy> {
+    value: T,
+    lock: &'a CellLock<T>,
+}
+
+impl<T: Copy> Drop for CellWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.value.set(self.value);
+    }
+}
+
+impl<T: Copy> Deref for CellWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T: Copy> DerefMut for CellWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<'a, T: Copy> LockApiReadGuard<'a, T> for CellWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T: Copy> LockApiWriteGuard<'a, T> for CellWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{CellLock, CellReadGuard, CellWriteGuard};
+    use crate::{async_locking::AsyncLockApi, error::Result, locking::LockApi};
+
+    impl<T> AsyncLockApi<T> for CellLock<T>
+    where
+        T: Copy,
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = CellReadGuard<'a, T>;
+
+        type WriteGuard<'a> = CellWriteGuard<'a, T>;
+
+        type ReadFuture<'a> = core::future::Ready<Result<Self::ReadGuard<'a>>>;
+
+        type WriteFuture<'a> = core::future::Ready<Result<Self::WriteGuard<'a>>>;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            core::future::ready(LockApi::read(self))
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            core::future::ready(LockApi::write(self))
+        }
+
+        fn new(inner: T) -> Self {
+            CellLock::new(inner)
+        }
+    }
+}
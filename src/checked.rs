@@ -0,0 +1,215 @@
+#[cfg(debug_assertions)]
+use core::cell::Cell;
+use core::{
+    cell::UnsafeCell,
+    ops::{Deref, DerefMut},
+};
+
+#[cfg(debug_assertions)]
+use crate::error::LockError;
+use crate::{
+    error::Result,
+    inner::IntoInner,
+    locking::{LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+};
+
+/// A cell for values whose exclusive access is guaranteed by other means. In
+/// debug builds it tracks borrows like a `RefCell` and panics when a guard
+/// conflicts with another; in release builds it is a bare `UnsafeCell` and
+/// checks nothing.
+///
+/// Creating the cell is safe; taking a guard is not, since release builds
+/// rely on the caller's guarantee. For the same reason it does not implement
+/// [`LockApi`](crate::LockApi), whose guards are safe to take.
+pub struct DebugCheckedCell<T> {
+    value: UnsafeCell<T>,
+    // Readers count up, a writer sets -1.
+    #[cfg(debug_assertions)]
+    borrow: Cell<isize>,
+}
+
+impl<T> DebugCheckedCell<T> {
+    pub const fn new(inner: T) -> DebugCheckedCell<T> {
+        DebugCheckedCell {
+            value: UnsafeCell::new(inner),
+            #[cfg(debug_assertions)]
+            borrow: Cell::new(0),
+        }
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+
+    /// Shared access to the value. Panics in debug builds while a write guard
+    /// exists.
+    ///
+    /// # Safety
+    ///
+    /// No write guard of the cell may exist while the returned guard does.
+    pub unsafe fn assume_shared(&self) -> DebugCheckedReadGuard<'_, T> {
+        match self.try_begin_read() {
+            Some(guard) => guard,
+            None => panic!("DebugCheckedCell read while written"),
+        }
+    }
+
+    /// Exclusive access to the value. Panics in debug builds while any other
+    /// guard exists.
+    ///
+    /// # Safety
+    ///
+    /// No other guard of the cell may exist while the returned guard does.
+    pub unsafe fn assume_exclusive(&self) -> DebugCheckedWriteGuard<'_, T> {
+        match self.try_begin_write() {
+            Some(guard) => guard,
+            None => panic!("DebugCheckedCell written while borrowed"),
+        }
+    }
+
+    /// Like [`assume_shared`](DebugCheckedCell::assume_shared), but fails with
+    /// [`LockError::WouldBlock`] on conflict in debug builds.
+    ///
+    /// # Safety
+    ///
+    /// As for `assume_shared`.
+    ///
+    /// [`LockError::WouldBlock`]: crate::LockError::WouldBlock
+    pub unsafe fn try_assume_shared(&self) -> Result<DebugCheckedReadGuard<'_, T>> {
+        #[cfg(debug_assertions)]
+        return self.try_begin_read().ok_or(LockError::WouldBlock);
+        #[cfg(not(debug_assertions))]
+        return Ok(DebugCheckedReadGuard { cell: self });
+    }
+
+    /// Like [`assume_exclusive`](DebugCheckedCell::assume_exclusive), but
+    /// fails with [`LockError::WouldBlock`] on conflict in debug builds.
+    ///
+    /// # Safety
+    ///
+    /// As for `assume_exclusive`.
+    ///
+    /// [`LockError::WouldBlock`]: crate::LockError::WouldBlock
+    pub unsafe fn try_assume_exclusive(&self) -> Result<DebugCheckedWriteGuard<'_, T>> {
+        #[cfg(debug_assertions)]
+        return self.try_begin_write().ok_or(LockError::WouldBlock);
+        #[cfg(not(debug_assertions))]
+        return Ok(DebugCheckedWriteGuard { cell: self });
+    }
+
+    fn try_begin_read(&self) -> Option<DebugCheckedReadGuard<'_, T>> {
+        #[cfg(debug_assertions)]
+        {
+            let borrow = self.borrow.get();
+            if borrow < 0 {
+                return None;
+            }
+            self.borrow.set(borrow + 1);
+        }
+        Some(DebugCheckedReadGuard { cell: self })
+    }
+
+    fn try_begin_write(&self) -> Option<DebugCheckedWriteGuard<'_, T>> {
+        #[cfg(debug_assertions)]
+        {
+            if self.borrow.get() != 0 {
+                return None;
+            }
+            self.borrow.set(-1);
+        }
+        Some(DebugCheckedWriteGuard { cell: self })
+    }
+}
+
+impl<T: Default> Default for DebugCheckedCell<T> {
+    fn default() -> Self {
+        DebugCheckedCell::new(T::default())
+    }
+}
+
+impl<T> IntoInner<T> for DebugCheckedCell<T> {
+    fn into_inner(self) -> Result<T> {
+        Ok(DebugCheckedCell::into_inner(self))
+    }
+}
+
+impl<T> PoisonApi for DebugCheckedCell<T> {}
+
+// The value is not printed: reading it would need the caller's guarantee.
+impl<T> core::fmt::Debug for DebugCheckedCell<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DebugCheckedCell").finish_non_exhaustive()
+    }
+}
+
+pub struct DebugCheckedReadGuard<'a, T> {
+    cell: &'a DebugCheckedCell<T>,
+}
+
+impl<T> Drop for DebugCheckedReadGuard<'_, T> {
+    fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        self.cell.borrow.set(self.cell.borrow.get() - 1);
+    }
+}
+
+impl<T> Deref for DebugCheckedReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: no writer exists, checked in debug builds and promised to
+        // `assume_shared` otherwise.
+        unsafe { &*self.cell.value.get() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for DebugCheckedReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+pub struct DebugCheckedWriteGuard<'a, T> {
+    cell: &'a DebugCheckedCell<T>,
+}
+
+impl<T> Drop for DebugCheckedWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        #[cfg(debug_assertions)]
+        self.cell.borrow.set(0);
+    }
+}
+
+impl<T> Deref for DebugCheckedWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: this is the only guard, checked in debug builds and promised
+        // to `assume_exclusive` otherwise.
+        unsafe { &*self.cell.value.get() }
+    }
+}
+
+impl<T> DerefMut for DebugCheckedWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: as above.
+        unsafe { &mut *self.cell.value.get() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for DebugCheckedWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for DebugCheckedWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self
+    }
+}
@@ -0,0 +1,27 @@
+use std::time::Instant;
+
+/// A source of the current time for timeouts and deadlines. Tests replace the
+/// system clock with [`MockClock`](crate::testing::MockClock) through
+/// [`testing::set_thread_clock`](crate::testing::set_thread_clock).
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+// The time as seen by timeouts, deadlines and durations measured by wrappers.
+// Only the `testing` feature lets a thread swap the clock.
+pub(crate) fn now() -> Instant {
+    #[cfg(feature = "testing")]
+    if let Some(now) = crate::testing::thread_clock_now() {
+        return now;
+    }
+    SystemClock.now()
+}
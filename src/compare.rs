@@ -0,0 +1,127 @@
+use core::{
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    ops::Deref,
+    ptr,
+};
+
+use crate::{
+    locking::{LockApi, LockApiReadGuard},
+    multi::acquire2,
+};
+
+/// Compares and hashes a locket handle by the lock it points to.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ById<L>(pub L);
+
+impl<L> Deref for ById<L> {
+    type Target = L;
+
+    fn deref(&self) -> &L {
+        &self.0
+    }
+}
+
+impl<L> PartialEq for ById<L>
+where
+    L: Deref,
+{
+    fn eq(&self, other: &Self) -> bool {
+        ptr::eq(&*self.0, &*other.0)
+    }
+}
+
+impl<L> Eq for ById<L> where L: Deref {}
+
+impl<L> Hash for ById<L>
+where
+    L: Deref,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ptr::hash(&*self.0, state)
+    }
+}
+
+/// Compares and hashes a locket handle by the value behind the lock, read
+/// under a read lock. Panics if the lock cannot be read.
+pub struct ByValue<L, T> {
+    pub locket: L,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<L, T> ByValue<L, T> {
+    pub fn new(locket: L) -> ByValue<L, T> {
+        ByValue {
+            locket,
+            _value: PhantomData,
+        }
+    }
+
+    pub fn into_inner(self) -> L {
+        self.locket
+    }
+}
+
+impl<L, T> Deref for ByValue<L, T> {
+    type Target = L;
+
+    fn deref(&self) -> &L {
+        &self.locket
+    }
+}
+
+impl<L, T> Clone for ByValue<L, T>
+where
+    L: Clone,
+{
+    fn clone(&self) -> Self {
+        ByValue::new(self.locket.clone())
+    }
+}
+
+impl<L, T> PartialEq for ByValue<L, T>
+where
+    L: Deref,
+    L::Target: LockApi<T>,
+    T: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        let (lhs, rhs) = (&*self.locket, &*other.locket);
+        let (id_lhs, id_rhs) = (lhs.lock_id(), rhs.lock_id());
+        // Reading the same lock twice would deadlock a mutex.
+        if id_lhs == id_rhs {
+            return true;
+        }
+        let (lhs, rhs) = acquire2(
+            (lhs, id_lhs),
+            (rhs, id_rhs),
+            |lock| lock.read(),
+            |lock| lock.read(),
+        )
+        .expect("failed to read locket");
+        lhs.get() == rhs.get()
+    }
+}
+
+impl<L, T> Eq for ByValue<L, T>
+where
+    L: Deref,
+    L::Target: LockApi<T>,
+    T: Eq,
+{
+}
+
+impl<L, T> Hash for ByValue<L, T>
+where
+    L: Deref,
+    L::Target: LockApi<T>,
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.locket
+            .read()
+            .expect("failed to read locket")
+            .get()
+            .hash(state)
+    }
+}
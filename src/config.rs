@@ -0,0 +1,39 @@
+//! Crate-wide settings.
+
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+
+const UNSET: u64 = u64::MAX;
+
+static MAX_WAIT: AtomicU64 = AtomicU64::new(UNSET);
+
+/// Sets a last-resort bound on how long an acquisition may wait before it
+/// fails with [`LockError::Timeout`](crate::LockError::Timeout).
+///
+/// It is honored by acquisitions which are able to give up and opt in:
+/// [`Timed`] lockets never wait longer than it, [`retry`](crate::retry)
+/// loops without an attempt budget stop after it, and [`Bounded`] applies it
+/// to every blocking acquisition of the lock it wraps, e.g. underneath an
+/// instrumented wrapper as in `Traced<Bounded<L>>`. Other acquisitions are
+/// not affected, and a plain backend blocking in `read` or `write` cannot be
+/// interrupted.
+///
+/// [`Timed`]: crate::Timed
+/// [`Bounded`]: crate::Bounded
+pub fn set_max_wait(max: Duration) {
+    let nanos = u64::try_from(max.as_nanos()).unwrap_or(UNSET - 1);
+    MAX_WAIT.store(nanos.min(UNSET - 1), Ordering::Relaxed);
+}
+
+pub fn clear_max_wait() {
+    MAX_WAIT.store(UNSET, Ordering::Relaxed);
+}
+
+pub fn max_wait() -> Option<Duration> {
+    match MAX_WAIT.load(Ordering::Relaxed) {
+        UNSET => None,
+        nanos => Some(Duration::from_nanos(nanos)),
+    }
+}
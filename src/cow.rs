@@ -0,0 +1,233 @@
+use alloc::sync::Arc;
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock, TryLockError};
+
+use crate::{
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    timed::TimedLockApi,
+    try_lock::TryLockApi,
+};
+
+/// Copy-on-write. Reads hand out `Arc` snapshots and only lock while cloning
+/// the pointer. A write guard clones the value on its first mutation and
+/// swaps the copy in when dropped, so readers are only held up by the swap.
+/// Writers take turns on a separate mutex, and a writer which panics
+/// discards its copy.
+pub struct CowLocket<T> {
+    value: RwLock<Arc<T>>,
+    writer: Mutex<()>,
+}
+
+impl<T> CowLocket<T> {
+    pub const fn new(inner: Arc<T>) -> CowLocket<T> {
+        CowLocket {
+            value: RwLock::new(inner),
+            writer: Mutex::new(()),
+        }
+    }
+
+    pub fn load(&self) -> Result<Arc<T>> {
+        let value = self.value.read().map_err(|_| LockError::Poisoned)?;
+        Ok(value.clone())
+    }
+
+    fn snapshot(&self) -> Result<CowReadGuard<'_, T>> {
+        Ok(CowReadGuard {
+            value: self.load()?,
+            _lock: PhantomData,
+        })
+    }
+
+    // Panicking writers never publish, so a poisoned writer mutex guards
+    // nothing inconsistent.
+    fn begin_write<'a>(&'a self, writer: MutexGuard<'a, ()>) -> Result<CowWriteGuard<'a, T>> {
+        Ok(CowWriteGuard {
+            value: self.load()?,
+            lock: self,
+            _writer: writer,
+        })
+    }
+}
+
+impl<T> From<T> for CowLocket<T> {
+    fn from(value: T) -> Self {
+        CowLocket::new(Arc::new(value))
+    }
+}
+
+impl<T: Default> Default for CowLocket<T> {
+    fn default() -> Self {
+        CowLocket::from(T::default())
+    }
+}
+
+impl<T> LockApi<T> for CowLocket<T>
+where
+    T: Clone,
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = CowReadGuard<'a, T>;
+
+    type WriteGuard<'a> = CowWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.snapshot()
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        let writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        self.begin_write(writer)
+    }
+
+    fn new(inner: T) -> Self {
+        CowLocket::from(inner)
+    }
+}
+
+impl<T> TryLockApi<T> for CowLocket<T>
+where
+    T: Clone,
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        let value = self.value.try_read().map_err(|err| match err {
+            TryLockError::Poisoned(_) => LockError::Poisoned,
+            TryLockError::WouldBlock => LockError::WouldBlock,
+        })?;
+        Ok(CowReadGuard {
+            value: value.clone(),
+            _lock: PhantomData,
+        })
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        let writer = match self.writer.try_lock() {
+            Ok(writer) => writer,
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            Err(TryLockError::WouldBlock) => return Err(LockError::WouldBlock),
+        };
+        self.begin_write(writer)
+    }
+}
+
+impl<T> TimedLockApi<T> for CowLocket<T>
+where
+    T: Clone,
+    for<'a> T: 'a,
+{
+}
+
+impl<T> IntoInner<T> for CowLocket<T>
+where
+    T: Clone,
+{
+    fn into_inner(self) -> Result<T> {
+        let value = self.value.into_inner().map_err(|_| LockError::Poisoned)?;
+        Ok(Arc::unwrap_or_clone(value))
+    }
+}
+
+impl<T> PoisonApi for CowLocket<T> {
+    fn is_poisoned(&self) -> bool {
+        self.value.is_poisoned()
+    }
+
+    fn clear_poison(&self) {
+        self.value.clear_poison()
+    }
+}
+
+impl<T> core::fmt::Debug for CowLocket<T>
+where
+    T: Clone + core::fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CowLocket")
+            .field("data", &crate::peek::peek(self))
+            .finish()
+    }
+}
+
+pub struct CowReadGuard<'a, T> {
+    value: Arc<T>,
+    _lock: PhantomData<&'a CowLocket<T>>,
+}
+
+impl<T> CowReadGuard<'_, T> {
+    pub fn snapshot(&self) -> Arc<T> {
+        self.value.clone()
+    }
+}
+
+impl<T> Deref for CowReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for CowReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+/// Holds the writer's copy of the value, published when the guard is dropped.
+pub struct CowWriteGuard<'a, T> {
+    value: Arc<T>,
+    lock: &'a CowLocket<T>,
+    _writer: MutexGuard<'a, ()>,
+}
+
+impl<T> Drop for CowWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            return;
+        }
+        let mut value = self
+            .lock
+            .value
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        *value = self.value.clone();
+    }
+}
+
+impl<T> Deref for CowWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for CowWriteGuard<'_, T>
+where
+    T: Clone,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        Arc::make_mut(&mut self.value)
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for CowWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for CowWriteGuard<'a, T>
+where
+    T: Clone,
+{
+    fn get_mut(&mut self) -> &mut T {
+        Arc::make_mut(&mut self.value)
+    }
+}
@@ -0,0 +1,95 @@
+use alloc::{format, string::String, vec::Vec};
+use std::{
+    sync::{Mutex, PoisonError},
+    thread::{self, ThreadId},
+};
+
+/// A cycle of threads waiting on each other, as found by parking_lot.
+#[derive(Debug, Clone)]
+pub struct DeadlockReport {
+    pub threads: Vec<DeadlockedThread>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DeadlockedThread {
+    pub thread_id: ThreadId,
+    pub backtrace: String,
+    /// The lock this thread is blocked on, if it was acquired through
+    /// [`LockApi`](crate::LockApi).
+    pub waiting_for: Option<WaitedLock>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaitedLock {
+    /// The [`lock_id`](crate::LockApi::lock_id) of the lock.
+    pub id: usize,
+    /// The name of the [`Named`](crate::Named) locket wrapping it, if any.
+    pub name: Option<&'static str>,
+}
+
+struct Waiting {
+    thread_id: ThreadId,
+    lock: WaitedLock,
+}
+
+// Blocking acquisitions in progress, so a report can say what each thread of
+// a cycle waits for. parking_lot only knows the threads.
+static WAITING: Mutex<Vec<Waiting>> = Mutex::new(Vec::new());
+
+/// Records that the current thread is about to block on `id`, until the
+/// returned value is dropped.
+pub(crate) fn waiting(id: usize, name: Option<&'static str>) -> WaitEntry {
+    let thread_id = thread::current().id();
+    WAITING
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(Waiting {
+            thread_id,
+            lock: WaitedLock { id, name },
+        });
+    WaitEntry { thread_id, id }
+}
+
+pub(crate) struct WaitEntry {
+    thread_id: ThreadId,
+    id: usize,
+}
+
+impl Drop for WaitEntry {
+    fn drop(&mut self) {
+        let mut waiting = WAITING.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(idx) = waiting
+            .iter()
+            .rposition(|entry| entry.thread_id == self.thread_id && entry.lock.id == self.id)
+        {
+            waiting.remove(idx);
+        }
+    }
+}
+
+// The innermost wait of the thread, named by any layer which knows a name.
+fn waiting_for(thread_id: ThreadId) -> Option<WaitedLock> {
+    let waiting = WAITING.lock().unwrap_or_else(PoisonError::into_inner);
+    let mut entries = waiting.iter().filter(|entry| entry.thread_id == thread_id);
+    let id = entries.clone().next_back()?.lock.id;
+    let name = entries.find_map(|entry| entry.lock.name.filter(|_| entry.lock.id == id));
+    Some(WaitedLock { id, name })
+}
+
+/// Runs parking_lot's deadlock detector and returns one report per cycle found.
+/// Only locks backed by parking_lot take part in detection.
+pub fn check_deadlocks() -> Vec<DeadlockReport> {
+    parking_lot::deadlock::check_deadlock()
+        .into_iter()
+        .map(|cycle| DeadlockReport {
+            threads: cycle
+                .iter()
+                .map(|thread| DeadlockedThread {
+                    thread_id: thread.thread_id(),
+                    backtrace: format!("{:?}", thread.backtrace()),
+                    waiting_for: waiting_for(thread.thread_id()),
+                })
+                .collect(),
+        })
+        .collect()
+}
@@ -0,0 +1,409 @@
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    hash::{BuildHasher, Hash},
+    ops::{Deref, DerefMut},
+};
+use std::{
+    collections::{hash_map::RandomState, HashMap},
+    sync::{mpsc, Mutex, PoisonError},
+};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+};
+
+/// A structural change made to a tracked collection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Change<K> {
+    Inserted(K),
+    Removed(K),
+    Updated(K),
+}
+
+impl<K> Change<K> {
+    pub fn key(&self) -> &K {
+        match self {
+            Change::Inserted(key) | Change::Removed(key) | Change::Updated(key) => key,
+        }
+    }
+}
+
+/// A collection recording the changes made to it, for [`Diffed`].
+pub trait Tracked {
+    type Key: Clone;
+
+    /// The changes made since the last call, in the order they were made.
+    fn take_changes(&mut self) -> Vec<Change<Self::Key>>;
+}
+
+/// A `HashMap` recording inserted, removed and updated keys. Reads go through
+/// `Deref`; every mutation goes through the methods here, so none is missed.
+#[derive(Debug, Clone)]
+pub struct TrackedMap<K, V, S = RandomState> {
+    map: HashMap<K, V, S>,
+    changes: Vec<Change<K>>,
+}
+
+impl<K, V> TrackedMap<K, V> {
+    pub fn new() -> TrackedMap<K, V> {
+        TrackedMap::from(HashMap::new())
+    }
+}
+
+impl<K, V, S> TrackedMap<K, V, S> {
+    pub fn into_inner(self) -> HashMap<K, V, S> {
+        self.map
+    }
+}
+
+impl<K, V, S> TrackedMap<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    S: BuildHasher,
+{
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        let old = self.map.insert(key.clone(), value);
+        self.changes.push(match old {
+            Some(_) => Change::Updated(key),
+            None => Change::Inserted(key),
+        });
+        old
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let old = self.map.remove(key)?;
+        self.changes.push(Change::Removed(key.clone()));
+        Some(old)
+    }
+
+    /// Records the key as updated whether or not the value is changed.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let value = self.map.get_mut(key)?;
+        self.changes.push(Change::Updated(key.clone()));
+        Some(value)
+    }
+
+    pub fn retain(&mut self, mut f: impl FnMut(&K, &mut V) -> bool) {
+        let changes = &mut self.changes;
+        self.map.retain(|key, value| {
+            let keep = f(key, value);
+            if !keep {
+                changes.push(Change::Removed(key.clone()));
+            }
+            keep
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.retain(|_, _| false);
+    }
+}
+
+impl<K, V, S> Tracked for TrackedMap<K, V, S>
+where
+    K: Clone,
+{
+    type Key = K;
+
+    fn take_changes(&mut self) -> Vec<Change<K>> {
+        core::mem::take(&mut self.changes)
+    }
+}
+
+impl<K, V, S> Deref for TrackedMap<K, V, S> {
+    type Target = HashMap<K, V, S>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.map
+    }
+}
+
+impl<K, V, S> From<HashMap<K, V, S>> for TrackedMap<K, V, S> {
+    fn from(map: HashMap<K, V, S>) -> Self {
+        TrackedMap {
+            map,
+            changes: Vec::new(),
+        }
+    }
+}
+
+impl<K, V, S: Default> Default for TrackedMap<K, V, S> {
+    fn default() -> Self {
+        TrackedMap::from(HashMap::default())
+    }
+}
+
+impl<K, V, S> Extend<(K, V)> for TrackedMap<K, V, S>
+where
+    K: Eq + Hash + Clone,
+    S: BuildHasher,
+{
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+/// A `Vec` recording changes by index. An insert or removal shifts the
+/// elements after it, which is not reported for each of them.
+#[derive(Debug, Clone)]
+pub struct TrackedVec<T> {
+    vec: Vec<T>,
+    changes: Vec<Change<usize>>,
+}
+
+impl<T> TrackedVec<T> {
+    pub fn new() -> TrackedVec<T> {
+        TrackedVec::from(Vec::new())
+    }
+
+    pub fn into_inner(self) -> Vec<T> {
+        self.vec
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.changes.push(Change::Inserted(self.vec.len()));
+        self.vec.push(value);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        let value = self.vec.pop()?;
+        self.changes.push(Change::Removed(self.vec.len()));
+        Some(value)
+    }
+
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.vec.insert(index, value);
+        self.changes.push(Change::Inserted(index));
+    }
+
+    pub fn remove(&mut self, index: usize) -> T {
+        let value = self.vec.remove(index);
+        self.changes.push(Change::Removed(index));
+        value
+    }
+
+    /// Records the index as updated whether or not the element is changed.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        let value = self.vec.get_mut(index)?;
+        self.changes.push(Change::Updated(index));
+        Some(value)
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        while self.vec.len() > len {
+            self.pop();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+}
+
+impl<T> Tracked for TrackedVec<T> {
+    type Key = usize;
+
+    fn take_changes(&mut self) -> Vec<Change<usize>> {
+        core::mem::take(&mut self.changes)
+    }
+}
+
+impl<T> Deref for TrackedVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.vec
+    }
+}
+
+impl<T> From<Vec<T>> for TrackedVec<T> {
+    fn from(vec: Vec<T>) -> Self {
+        TrackedVec {
+            vec,
+            changes: Vec::new(),
+        }
+    }
+}
+
+impl<T> Default for TrackedVec<T> {
+    fn default() -> Self {
+        TrackedVec::new()
+    }
+}
+
+impl<T> Extend<T> for TrackedVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+/// One write's changes, shared by every subscriber.
+pub type Changes<K> = Arc<[Change<K>]>;
+
+type ChangeObserver<K> = Box<dyn Fn(&Changes<K>) + Send + Sync>;
+
+/// Delivers the changes each write made to a [`Tracked`] collection, instead
+/// of the whole collection. Writes which changed nothing are not delivered.
+///
+/// Changes are delivered before the write lock is released, so every observer
+/// and subscriber sees them in the order the writes were made. Observers run
+/// under the lock and must not lock this locket themselves.
+pub struct Diffed<L, C>
+where
+    C: Tracked,
+{
+    inner: L,
+    observers: Vec<ChangeObserver<C::Key>>,
+    subscribers: Mutex<Vec<mpsc::Sender<Changes<C::Key>>>>,
+}
+
+impl<L, C> Diffed<L, C>
+where
+    C: Tracked,
+{
+    pub fn wrap(inner: L) -> Diffed<L, C> {
+        Diffed {
+            inner,
+            observers: Vec::new(),
+            subscribers: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn on_change<F>(mut self, observer: F) -> Diffed<L, C>
+    where
+        F: Fn(&Changes<C::Key>) + Send + Sync + 'static,
+    {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Receives the changes of every write made after this call.
+    pub fn subscribe(&self) -> mpsc::Receiver<Changes<C::Key>> {
+        let (sender, receiver) = mpsc::channel();
+        self.subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(sender);
+        receiver
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    fn deliver(&self, changes: Vec<Change<C::Key>>) {
+        if changes.is_empty() {
+            return;
+        }
+        let changes = Changes::from(changes);
+        for observer in &self.observers {
+            observer(&changes);
+        }
+        self.subscribers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|sender| sender.send(changes.clone()).is_ok());
+    }
+}
+
+impl<L, C> LockApi<C> for Diffed<L, C>
+where
+    L: LockApi<C>,
+    C: Tracked,
+{
+    type ReadGuard<'a>
+        = L::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = DiffedGuard<'a, L, C, L::WriteGuard<'a>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.inner.read()
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        Ok(DiffedGuard {
+            guard: self.inner.write()?,
+            diffed: self,
+        })
+    }
+
+    fn new(inner: C) -> Self {
+        Diffed::wrap(L::new(inner))
+    }
+}
+
+pub struct DiffedGuard<'a, L, C, G>
+where
+    G: LockApiWriteGuard<'a, C>,
+    C: Tracked,
+{
+    guard: G,
+    diffed: &'a Diffed<L, C>,
+}
+
+impl<'a, L, C, G> Drop for DiffedGuard<'a, L, C, G>
+where
+    G: LockApiWriteGuard<'a, C>,
+    C: Tracked,
+{
+    fn drop(&mut self) {
+        let changes = self.guard.get_mut().take_changes();
+        self.diffed.deliver(changes);
+    }
+}
+
+impl<'a, L, C, G> Deref for DiffedGuard<'a, L, C, G>
+where
+    G: LockApiWriteGuard<'a, C> + Deref,
+    C: Tracked,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<'a, L, C, G> DerefMut for DiffedGuard<'a, L, C, G>
+where
+    G: LockApiWriteGuard<'a, C> + DerefMut,
+    C: Tracked,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, L, C, G> LockApiReadGuard<'a, C> for DiffedGuard<'a, L, C, G>
+where
+    G: LockApiWriteGuard<'a, C>,
+    C: Tracked,
+{
+    fn get(&self) -> &C {
+        self.guard.get()
+    }
+}
+
+impl<'a, L, C, G> LockApiWriteGuard<'a, C> for DiffedGuard<'a, L, C, G>
+where
+    G: LockApiWriteGuard<'a, C>,
+    C: Tracked,
+{
+    fn get_mut(&mut self) -> &mut C {
+        self.guard.get_mut()
+    }
+}
@@ -0,0 +1,76 @@
+use alloc::string::String;
+use core::{future::Future, time::Duration};
+use std::time::Instant;
+
+use crate::error::Result;
+
+/// A held distributed lock. It stays valid until [`Lease::expires_at`] unless
+/// it is refreshed, even if the holder crashes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lease {
+    key: String,
+    owner: String,
+    fencing_token: u64,
+    expires_at: Instant,
+}
+
+impl Lease {
+    pub fn new(key: String, owner: String, fencing_token: u64, expires_at: Instant) -> Lease {
+        Lease {
+            key,
+            owner,
+            fencing_token,
+            expires_at,
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The value identifying this holder to the lock service.
+    pub fn owner(&self) -> &str {
+        &self.owner
+    }
+
+    /// Grows with every acquisition of the key. Resources guarded by the lock
+    /// should reject writes carrying a lower token than one already seen, so a
+    /// holder whose lease expired while it was paused cannot clobber them.
+    pub fn fencing_token(&self) -> u64 {
+        self.fencing_token
+    }
+
+    pub fn expires_at(&self) -> Instant {
+        self.expires_at
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
+
+    pub fn set_expires_at(&mut self, expires_at: Instant) {
+        self.expires_at = expires_at;
+    }
+}
+
+/// A lock shared between processes or nodes through an external service.
+/// Locks are named by key and held for a time to live (TTL), so one is not
+/// lost for good when its holder dies.
+pub trait AsyncDistributedLock {
+    /// Waits until the lock is acquired.
+    fn acquire(&self, key: &str, ttl: Duration) -> impl Future<Output = Result<Lease>>;
+
+    /// Returns `None` if somebody else holds the lock.
+    fn try_acquire(&self, key: &str, ttl: Duration) -> impl Future<Output = Result<Option<Lease>>>;
+
+    /// Extends the lease to `ttl` from now. Fails with [`LockError::Expired`]
+    /// if it was lost in the meantime.
+    ///
+    /// [`LockError::Expired`]: crate::LockError::Expired
+    fn refresh(&self, lease: &mut Lease, ttl: Duration) -> impl Future<Output = Result<()>>;
+
+    /// Fails with [`LockError::Expired`] if the lease was already lost.
+    ///
+    /// [`LockError::Expired`]: crate::LockError::Expired
+    fn release(&self, lease: Lease) -> impl Future<Output = Result<()>>;
+}
@@ -0,0 +1,333 @@
+use core::{
+    cell::UnsafeCell,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use crate::{
+    backoff::Backoff,
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    stats::LockStats,
+    try_lock::TryLockApi,
+};
+
+/// A left-right style lock keeping two copies of the value. Readers never wait
+/// for the writer: they use the active buffer while the single writer modifies
+/// the other one, which becomes active when the write guard is dropped.
+pub struct DoubleBuffered<T> {
+    buffers: [UnsafeCell<T>; 2],
+    readers: [AtomicUsize; 2],
+    active: AtomicUsize,
+    writer: AtomicBool,
+    backoff: Backoff,
+    #[cfg(all(feature = "async", feature = "alloc"))]
+    wakers: crate::wakers::WakerList,
+}
+
+unsafe impl<T: Send> Send for DoubleBuffered<T> {}
+unsafe impl<T: Send + Sync> Sync for DoubleBuffered<T> {}
+
+impl<T> DoubleBuffered<T>
+where
+    T: Clone,
+{
+    pub fn new(inner: T) -> DoubleBuffered<T> {
+        DoubleBuffered::with_backoff(inner, Backoff::Spin)
+    }
+
+    pub fn with_backoff(inner: T, backoff: Backoff) -> DoubleBuffered<T> {
+        DoubleBuffered {
+            buffers: [UnsafeCell::new(inner.clone()), UnsafeCell::new(inner)],
+            readers: [AtomicUsize::new(0), AtomicUsize::new(0)],
+            active: AtomicUsize::new(0),
+            writer: AtomicBool::new(false),
+            backoff,
+            #[cfg(all(feature = "async", feature = "alloc"))]
+            wakers: crate::wakers::WakerList::new(),
+        }
+    }
+}
+
+impl<T> DoubleBuffered<T> {
+    pub fn backoff(&self) -> Backoff {
+        self.backoff
+    }
+
+    pub fn into_inner(self) -> T {
+        let [first, second] = self.buffers;
+        match self.active.into_inner() {
+            0 => first.into_inner(),
+            _ => second.into_inner(),
+        }
+    }
+
+    fn begin_read(&self) -> DoubleReadGuard<'_, T> {
+        loop {
+            let idx = self.active.load(Ordering::SeqCst);
+            self.readers[idx].fetch_add(1, Ordering::SeqCst);
+            // A flip between the load and the increment means the writer may
+            // already own this buffer again; back off and use the new one.
+            if self.active.load(Ordering::SeqCst) == idx {
+                return DoubleReadGuard { lock: self, idx };
+            }
+            self.readers[idx].fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    fn try_begin_write(&self) -> Option<DoubleWriteGuard<'_, T>>
+    where
+        T: Clone,
+    {
+        self.writer
+            .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+
+        let active = self.active.load(Ordering::SeqCst);
+        let idx = 1 - active;
+        // Wait for readers which entered the inactive buffer before the last flip.
+        let mut snooze = self.backoff.start();
+        while self.readers[idx].load(Ordering::SeqCst) != 0 {
+            snooze.snooze();
+        }
+        // SAFETY: we are the only writer and no reader uses the inactive buffer.
+        unsafe { (*self.buffers[idx].get()).clone_from(&*self.buffers[active].get()) };
+        Some(DoubleWriteGuard { lock: self, idx })
+    }
+}
+
+impl<T: Clone + Default> Default for DoubleBuffered<T> {
+    fn default() -> Self {
+        DoubleBuffered::new(T::default())
+    }
+}
+
+impl<T> LockApi<T> for DoubleBuffered<T>
+where
+    T: Clone,
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = DoubleReadGuard<'a, T>;
+
+    type WriteGuard<'a> = DoubleWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        Ok(self.begin_read())
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        let mut snooze = self.backoff.start();
+        loop {
+            if let Some(guard) = self.try_begin_write() {
+                return Ok(guard);
+            }
+            snooze.snooze();
+        }
+    }
+
+    fn new(inner: T) -> Self {
+        DoubleBuffered::new(inner)
+    }
+}
+
+impl<T> IntoInner<T> for DoubleBuffered<T> {
+    fn into_inner(self) -> Result<T> {
+        Ok(DoubleBuffered::into_inner(self))
+    }
+}
+
+impl<T> TryLockApi<T> for DoubleBuffered<T>
+where
+    T: Clone,
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        Ok(self.begin_read())
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.try_begin_write().ok_or(LockError::WouldBlock)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> crate::timed::TimedLockApi<T> for DoubleBuffered<T>
+where
+    T: Clone,
+    for<'a> T: 'a,
+{
+}
+
+impl<T> LockStats for DoubleBuffered<T> {
+    fn is_locked(&self) -> bool {
+        self.writer.load(Ordering::Relaxed) || self.reader_count() != Some(0)
+    }
+
+    /// Readers never wait for the writer, so this only tells whether a write
+    /// is in progress.
+    fn is_locked_exclusive(&self) -> bool {
+        self.writer.load(Ordering::Relaxed)
+    }
+
+    fn reader_count(&self) -> Option<usize> {
+        Some(
+            self.readers
+                .iter()
+                .map(|readers| readers.load(Ordering::Relaxed))
+                .sum(),
+        )
+    }
+}
+
+impl<T> core::fmt::Debug for DoubleBuffered<T>
+where
+    T: Clone + core::fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DoubleBuffered")
+            .field("data", &crate::peek::peek(self))
+            .finish()
+    }
+}
+
+impl<T> PoisonApi for DoubleBuffered<T> {}
+
+pub struct DoubleReadGuard<'a, T> {
+    lock: &'a DoubleBuffered<T>,
+    idx: usize,
+}
+
+impl<T> Drop for DoubleReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.readers[self.idx].fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl<T> Deref for DoubleReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the writer never touches a buffer with registered readers.
+        unsafe { &*self.lock.buffers[self.idx].get() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for DoubleReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        self.deref()
+    }
+}
+
+pub struct DoubleWriteGuard<'a, T> {
+    lock: &'a DoubleBuffered<T>,
+    idx: usize,
+}
+
+impl<T> Drop for DoubleWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        // Only publish complete writes; the buffer is resynchronized by the next writer.
+        #[cfg(feature = "std")]
+        let publish = !std::thread::panicking();
+        #[cfg(not(feature = "std"))]
+        let publish = true;
+        if publish {
+            self.lock.active.store(self.idx, Ordering::SeqCst);
+        }
+        self.lock.writer.store(false, Ordering::Release);
+        #[cfg(all(feature = "async", feature = "alloc"))]
+        self.lock.wakers.wake_all();
+    }
+}
+
+impl<T> Deref for DoubleWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the inactive buffer belongs to the writer.
+        unsafe { &*self.lock.buffers[self.idx].get() }
+    }
+}
+
+impl<T> DerefMut for DoubleWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the inactive buffer belongs to the writer.
+        unsafe { &mut *self.lock.buffers[self.idx].get() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for DoubleWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for DoubleWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+#[cfg(all(feature = "async", feature = "alloc"))]
+pub use self::async_impl::DoubleWriteFuture;
+
+#[cfg(all(feature = "async", feature = "alloc"))]
+mod async_impl {
+    use super::{DoubleBuffered, DoubleReadGuard, DoubleWriteGuard};
+    use crate::{async_locking::AsyncLockApi, error::Result};
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    impl<T> AsyncLockApi<T> for DoubleBuffered<T>
+    where
+        T: Clone,
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = DoubleReadGuard<'a, T>;
+
+        type WriteGuard<'a> = DoubleWriteGuard<'a, T>;
+
+        type ReadFuture<'a> = core::future::Ready<Result<Self::ReadGuard<'a>>>;
+
+        type WriteFuture<'a> = DoubleWriteFuture<'a, T>;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            core::future::ready(Ok(self.begin_read()))
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            DoubleWriteFuture { lock: self }
+        }
+
+        fn new(inner: T) -> Self {
+            DoubleBuffered::new(inner)
+        }
+    }
+
+    /// Retries acquiring the writer slot whenever a write guard is dropped.
+    pub struct DoubleWriteFuture<'a, T> {
+        lock: &'a DoubleBuffered<T>,
+    }
+
+    impl<'a, T> Future for DoubleWriteFuture<'a, T>
+    where
+        T: Clone,
+    {
+        type Output = Result<DoubleWriteGuard<'a, T>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if let Some(guard) = self.lock.try_begin_write() {
+                return Poll::Ready(Ok(guard));
+            }
+            self.lock.wakers.register(cx.waker());
+            match self.lock.try_begin_write() {
+                Some(guard) => Poll::Ready(Ok(guard)),
+                None => Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,242 @@
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::atomic::Ordering,
+};
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
+
+use crossbeam_epoch::{self as epoch, Atomic, Guard, Owned, Shared};
+
+use crate::{
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    timed::TimedLockApi,
+    try_lock::TryLockApi,
+};
+
+/// A read-mostly lock built on `crossbeam-epoch`. A read pins the current
+/// epoch and borrows the current version in place, with no reference count
+/// or lock word to touch. Writes clone the value, modify the copy and install
+/// it when the guard is dropped; the old version is freed once no pinned
+/// reader can see it any more. Writers are serialized so no update is lost.
+pub struct EpochLocket<T> {
+    value: Atomic<T>,
+    writer: Mutex<()>,
+}
+
+impl<T> EpochLocket<T> {
+    pub fn new(inner: T) -> EpochLocket<T> {
+        EpochLocket {
+            value: Atomic::new(inner),
+            writer: Mutex::new(()),
+        }
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        // SAFETY: `&mut self` rules out readers, and the pointer is only null
+        // after `into_inner`.
+        unsafe {
+            self.value
+                .load(Ordering::Relaxed, epoch::unprotected())
+                .deref_mut()
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        // SAFETY: owning `self` rules out readers. The null left behind is
+        // skipped on drop.
+        unsafe {
+            let value = self
+                .value
+                .swap(Shared::null(), Ordering::Relaxed, epoch::unprotected());
+            *value.into_owned().into_box()
+        }
+    }
+
+    fn begin_read(&self) -> EpochReadGuard<'_, T> {
+        let guard = epoch::pin();
+        let value = self.value.load(Ordering::Acquire, &guard).as_raw();
+        EpochReadGuard {
+            value,
+            _guard: guard,
+            _lock: PhantomData,
+        }
+    }
+
+    fn begin_write<'a>(&'a self, writer: MutexGuard<'a, ()>) -> EpochWriteGuard<'a, T>
+    where
+        T: Clone + Send,
+    {
+        let value = T::clone(&self.begin_read());
+        EpochWriteGuard {
+            value: Some(value),
+            lock: self,
+            _writer: writer,
+        }
+    }
+}
+
+impl<T> Drop for EpochLocket<T> {
+    fn drop(&mut self) {
+        // SAFETY: `&mut self` rules out readers; retired versions are owned
+        // by the collector.
+        unsafe {
+            let value = self.value.load(Ordering::Relaxed, epoch::unprotected());
+            if !value.is_null() {
+                drop(value.into_owned());
+            }
+        }
+    }
+}
+
+impl<T: Default> Default for EpochLocket<T> {
+    fn default() -> Self {
+        EpochLocket::new(T::default())
+    }
+}
+
+impl<T> LockApi<T> for EpochLocket<T>
+where
+    T: Clone + Send,
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = EpochReadGuard<'a, T>;
+
+    type WriteGuard<'a> = EpochWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        Ok(self.begin_read())
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        let writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(self.begin_write(writer))
+    }
+
+    fn new(inner: T) -> Self {
+        EpochLocket::new(inner)
+    }
+}
+
+impl<T> IntoInner<T> for EpochLocket<T> {
+    fn into_inner(self) -> Result<T> {
+        Ok(EpochLocket::into_inner(self))
+    }
+}
+
+impl<T> TryLockApi<T> for EpochLocket<T>
+where
+    T: Clone + Send,
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        Ok(self.begin_read())
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        let writer = match self.writer.try_lock() {
+            Ok(writer) => writer,
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            Err(TryLockError::WouldBlock) => return Err(LockError::WouldBlock),
+        };
+        Ok(self.begin_write(writer))
+    }
+}
+
+impl<T> TimedLockApi<T> for EpochLocket<T>
+where
+    T: Clone + Send,
+    for<'a> T: 'a,
+{
+}
+
+impl<T> core::fmt::Debug for EpochLocket<T>
+where
+    T: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("EpochLocket")
+            .field("data", &&*self.begin_read())
+            .finish()
+    }
+}
+
+impl<T> PoisonApi for EpochLocket<T> {}
+
+/// Keeps the current epoch pinned, and with it the version it points to.
+pub struct EpochReadGuard<'a, T> {
+    value: *const T,
+    _guard: Guard,
+    _lock: PhantomData<&'a EpochLocket<T>>,
+}
+
+impl<T> Deref for EpochReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: versions are only freed once no pinned guard can see them.
+        unsafe { &*self.value }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for EpochReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+/// Retired versions are dropped by whichever thread collects them, hence
+/// `T: Send`.
+pub struct EpochWriteGuard<'a, T: Send> {
+    value: Option<T>,
+    lock: &'a EpochLocket<T>,
+    _writer: MutexGuard<'a, ()>,
+}
+
+impl<T: Send> Drop for EpochWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        // A writer which panicked may have left its copy half modified; keep
+        // the previously installed version instead.
+        if std::thread::panicking() {
+            return;
+        }
+        if let Some(value) = self.value.take() {
+            let guard = epoch::pin();
+            let old = self
+                .lock
+                .value
+                .swap(Owned::new(value), Ordering::AcqRel, &guard);
+            // SAFETY: `old` is unreachable from now on; readers which still
+            // see it are pinned, which delays its destruction.
+            unsafe { guard.defer_destroy(old) };
+        }
+    }
+}
+
+impl<T: Send> Deref for EpochWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_ref().unwrap()
+    }
+}
+
+impl<T: Send> DerefMut for EpochWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value.as_mut().unwrap()
+    }
+}
+
+impl<'a, T: Send> LockApiReadGuard<'a, T> for EpochWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T: Send> LockApiWriteGuard<'a, T> for EpochWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
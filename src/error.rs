@@ -1,13 +1,54 @@
 pub type Result<T> = core::result::Result<T, LockError>;
 
+/// Why a lock operation failed. Until 0.2 this was a unit struct; match on
+/// the variants, with a wildcard arm since more may be added.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct LockError;
+#[non_exhaustive]
+pub enum LockError {
+    /// A previous holder panicked while holding the lock.
+    Poisoned,
+    /// The lock is held in a conflicting mode and the backend cannot wait for it.
+    WouldBlock,
+    /// Other strong handles to the lock still exist.
+    Shared,
+    /// Every strong handle to the lock has been dropped.
+    Dropped,
+    /// The lock could not be acquired before the deadline.
+    Timeout,
+    /// A lease ran out or was taken over by another holder.
+    Expired,
+    /// Not enough nodes of a distributed lock service could be reached.
+    Unavailable,
+    /// The lock does not allow writing.
+    ReadOnly,
+    /// The operating system failed to lock or unlock a file.
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
+}
 
 impl core::fmt::Display for LockError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "lock failed")
+        match self {
+            LockError::Poisoned => write!(f, "lock poisoned"),
+            LockError::WouldBlock => write!(f, "lock would block"),
+            LockError::Shared => write!(f, "lock is still shared"),
+            LockError::Dropped => write!(f, "lock was dropped"),
+            LockError::Timeout => write!(f, "lock acquisition timed out"),
+            LockError::Expired => write!(f, "lock lease expired"),
+            LockError::Unavailable => write!(f, "lock service unavailable"),
+            LockError::ReadOnly => write!(f, "lock is read-only"),
+            #[cfg(feature = "std")]
+            LockError::Io(kind) => write!(f, "file lock failed: {kind}"),
+        }
     }
 }
 
 #[cfg(feature = "std")]
 impl std::error::Error for LockError {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for LockError {
+    fn from(err: std::io::Error) -> Self {
+        LockError::Io(err.kind())
+    }
+}
@@ -0,0 +1,273 @@
+use core::{
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::{
+    fs::{File, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError,
+    },
+};
+
+use fs4::FileExt;
+
+use crate::{
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    timed::TimedLockApi,
+    try_lock::TryLockApi,
+};
+
+fn try_lock_error<G>(err: TryLockError<G>) -> LockError {
+    match err {
+        TryLockError::Poisoned(_) => LockError::Poisoned,
+        TryLockError::WouldBlock => LockError::WouldBlock,
+    }
+}
+
+fn try_file_lock_error(err: fs4::TryLockError) -> LockError {
+    match err {
+        fs4::TryLockError::Error(err) => err.into(),
+        fs4::TryLockError::WouldBlock => LockError::WouldBlock,
+    }
+}
+
+// Removes the lock file created by `LockApi::new`.
+struct TempPath(PathBuf);
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// A lock which also holds an advisory OS lock (`flock` on Unix,
+/// `LockFileEx` on Windows) on a file, so it excludes other processes using
+/// the same path as well as other threads. Only the lock is shared between
+/// processes, the value lives in this one.
+///
+/// Every process must go through a `FileLock` (or another advisory lock) on
+/// the path; the OS does not stop plain reads and writes of the file.
+pub struct FileLock<T> {
+    file: File,
+    path: PathBuf,
+    // In-process readers sharing the file's shared lock.
+    readers: Mutex<usize>,
+    value: RwLock<T>,
+    _temp: Option<TempPath>,
+}
+
+impl<T> FileLock<T> {
+    /// Guards `path`, creating the file if it does not exist.
+    pub fn open(path: impl AsRef<Path>, inner: T) -> io::Result<FileLock<T>> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+        Ok(FileLock {
+            file,
+            path,
+            readers: Mutex::new(0),
+            value: RwLock::new(inner),
+            _temp: None,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn into_inner(self) -> Result<T> {
+        self.value.into_inner().map_err(|_| LockError::Poisoned)
+    }
+
+    fn readers(&self) -> MutexGuard<'_, usize> {
+        self.readers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn lock_shared<'a>(&'a self, value: RwLockReadGuard<'a, T>) -> Result<FileReadGuard<'a, T>> {
+        let mut readers = self.readers();
+        if *readers == 0 {
+            FileExt::lock_shared(&self.file)?;
+        }
+        *readers += 1;
+        Ok(FileReadGuard { value, lock: self })
+    }
+
+    fn try_lock_shared<'a>(
+        &'a self,
+        value: RwLockReadGuard<'a, T>,
+    ) -> Result<FileReadGuard<'a, T>> {
+        let mut readers = self.readers();
+        if *readers == 0 {
+            FileExt::try_lock_shared(&self.file).map_err(try_file_lock_error)?;
+        }
+        *readers += 1;
+        Ok(FileReadGuard { value, lock: self })
+    }
+
+    fn unlock_shared(&self) {
+        let mut readers = self.readers();
+        *readers -= 1;
+        if *readers == 0 {
+            let _ = FileExt::unlock(&self.file);
+        }
+    }
+}
+
+impl<T> LockApi<T> for FileLock<T>
+where
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = FileReadGuard<'a, T>;
+
+    type WriteGuard<'a> = FileWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        let value = self.value.read().map_err(|_| LockError::Poisoned)?;
+        self.lock_shared(value)
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        let value = self.value.write().map_err(|_| LockError::Poisoned)?;
+        FileExt::lock(&self.file)?;
+        Ok(FileWriteGuard { value, lock: self })
+    }
+
+    /// Locks a fresh file in the temporary directory, which is removed again
+    /// when the lock is dropped. Nothing else knows that path, so this only
+    /// excludes threads; use [`FileLock::open`] to share a lock between
+    /// processes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file cannot be created.
+    fn new(inner: T) -> Self {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let path = std::env::temp_dir().join(format!(
+            "locket-{}-{}.lock",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        let mut lock = FileLock::open(&path, inner).expect("failed to create lock file");
+        lock._temp = Some(TempPath(path));
+        lock
+    }
+}
+
+impl<T> TryLockApi<T> for FileLock<T>
+where
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        let value = self.value.try_read().map_err(try_lock_error)?;
+        self.try_lock_shared(value)
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        let value = self.value.try_write().map_err(try_lock_error)?;
+        FileExt::try_lock(&self.file).map_err(try_file_lock_error)?;
+        Ok(FileWriteGuard { value, lock: self })
+    }
+}
+
+impl<T> TimedLockApi<T> for FileLock<T> where for<'a> T: 'a {}
+
+impl<T> IntoInner<T> for FileLock<T> {
+    fn into_inner(self) -> Result<T> {
+        FileLock::into_inner(self)
+    }
+}
+
+impl<T> PoisonApi for FileLock<T> {
+    fn is_poisoned(&self) -> bool {
+        self.value.is_poisoned()
+    }
+
+    fn clear_poison(&self) {
+        self.value.clear_poison()
+    }
+}
+
+impl<T> core::fmt::Debug for FileLock<T>
+where
+    T: core::fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FileLock")
+            .field("path", &self.path)
+            .field("data", &crate::peek::peek(self))
+            .finish()
+    }
+}
+
+pub struct FileReadGuard<'a, T> {
+    value: RwLockReadGuard<'a, T>,
+    lock: &'a FileLock<T>,
+}
+
+impl<T> Drop for FileReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.unlock_shared();
+    }
+}
+
+impl<T> Deref for FileReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for FileReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+pub struct FileWriteGuard<'a, T> {
+    value: RwLockWriteGuard<'a, T>,
+    lock: &'a FileLock<T>,
+}
+
+impl<T> Drop for FileWriteGuard<'_, T> {
+    // Runs before `value` is released, so no other thread sees the file
+    // unlocked while it holds the value.
+    fn drop(&mut self) {
+        let _ = FileExt::unlock(&self.lock.file);
+    }
+}
+
+impl<T> Deref for FileWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> DerefMut for FileWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for FileWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for FileWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+}
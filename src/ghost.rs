@@ -0,0 +1,134 @@
+use core::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    error::Result,
+    inner::IntoInner,
+    locking::{LockApiReadGuard, LockApiWriteGuard},
+};
+
+type Brand<'brand> = PhantomData<fn(&'brand ()) -> &'brand ()>;
+
+/// The key to every [`GhostCell`] of the same brand. Reading a cell borrows
+/// the token shared, writing borrows it mutably, so exclusivity is checked at
+/// compile time and locking costs nothing.
+///
+/// `LockApi` is not implemented: its accessors take only `&self`, leaving no
+/// place for the token. The guards implement the guard traits, so code generic
+/// over them still works.
+pub struct GhostToken<'brand> {
+    _brand: Brand<'brand>,
+}
+
+impl GhostToken<'_> {
+    /// Runs `f` with a token of a fresh, unnameable brand.
+    pub fn scope<R>(f: impl for<'brand> FnOnce(GhostToken<'brand>) -> R) -> R {
+        f(GhostToken {
+            _brand: PhantomData,
+        })
+    }
+}
+
+pub struct GhostCell<'brand, T: ?Sized> {
+    _brand: Brand<'brand>,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for GhostCell<'_, T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for GhostCell<'_, T> {}
+
+impl<'brand, T> GhostCell<'brand, T> {
+    pub const fn new(inner: T) -> GhostCell<'brand, T> {
+        GhostCell {
+            _brand: PhantomData,
+            data: UnsafeCell::new(inner),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<'brand, T: ?Sized> GhostCell<'brand, T> {
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    pub fn read<'a>(&'a self, _token: &'a GhostToken<'brand>) -> GhostReadGuard<'a, T> {
+        // SAFETY: writers need the token mutably, which `_token` prevents.
+        GhostReadGuard {
+            data: unsafe { &*self.data.get() },
+        }
+    }
+
+    pub fn write<'a>(&'a self, _token: &'a mut GhostToken<'brand>) -> GhostWriteGuard<'a, T> {
+        // SAFETY: the token is borrowed exclusively for the guard's lifetime.
+        GhostWriteGuard {
+            data: unsafe { &mut *self.data.get() },
+        }
+    }
+}
+
+impl<T: Default> Default for GhostCell<'_, T> {
+    fn default() -> Self {
+        GhostCell::new(T::default())
+    }
+}
+
+impl<T> IntoInner<T> for GhostCell<'_, T> {
+    fn into_inner(self) -> Result<T> {
+        Ok(GhostCell::into_inner(self))
+    }
+}
+
+pub struct GhostReadGuard<'a, T: ?Sized> {
+    data: &'a T,
+}
+
+impl<T: ?Sized> Deref for GhostReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.data
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for GhostReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        self.data
+    }
+}
+
+pub struct GhostWriteGuard<'a, T: ?Sized> {
+    data: &'a mut T,
+}
+
+impl<T: ?Sized> Deref for GhostWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.data
+    }
+}
+
+impl<T: ?Sized> DerefMut for GhostWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.data
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for GhostWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        self.data
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for GhostWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self.data
+    }
+}
@@ -0,0 +1,181 @@
+use alloc::sync::Arc;
+use core::ops::{Deref, DerefMut};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    try_lock::TryLockApi,
+};
+
+/// A guard bundled with the `Arc` it was taken from. It borrows nothing, so it
+/// is `'static` and can be stored in a struct or moved into a task, with any
+/// backend. See [`ArcReadGuard`] and [`ArcWriteGuard`] for the usual types.
+pub struct GuardedArc<L, G> {
+    // Declared first so it is dropped before the lock it points into.
+    guard: G,
+    lock: Arc<L>,
+}
+
+/// A read guard owning its lock.
+pub type ArcReadGuard<L, T> = GuardedArc<L, <L as LockApi<T>>::ReadGuard<'static>>;
+
+/// A write guard owning its lock.
+pub type ArcWriteGuard<L, T> = GuardedArc<L, <L as LockApi<T>>::WriteGuard<'static>>;
+
+/// An async read guard owning its lock.
+#[cfg(feature = "async")]
+pub type ArcReadGuardAsync<L, T> =
+    GuardedArc<L, <L as crate::async_locking::AsyncLockApi<T>>::ReadGuard<'static>>;
+
+/// An async write guard owning its lock.
+#[cfg(feature = "async")]
+pub type ArcWriteGuardAsync<L, T> =
+    GuardedArc<L, <L as crate::async_locking::AsyncLockApi<T>>::WriteGuard<'static>>;
+
+impl<L> GuardedArc<L, ()>
+where
+    L: 'static,
+{
+    pub fn read<T>(lock: Arc<L>) -> Result<ArcReadGuard<L, T>>
+    where
+        L: LockApi<T>,
+    {
+        Self::acquire(lock, |lock| lock.read())
+    }
+
+    pub fn write<T>(lock: Arc<L>) -> Result<ArcWriteGuard<L, T>>
+    where
+        L: LockApi<T>,
+    {
+        Self::acquire(lock, |lock| lock.write())
+    }
+
+    pub fn try_read<T>(lock: Arc<L>) -> Result<ArcReadGuard<L, T>>
+    where
+        L: TryLockApi<T>,
+    {
+        Self::acquire(lock, |lock| lock.try_read())
+    }
+
+    pub fn try_write<T>(lock: Arc<L>) -> Result<ArcWriteGuard<L, T>>
+    where
+        L: TryLockApi<T>,
+    {
+        Self::acquire(lock, |lock| lock.try_write())
+    }
+
+    fn acquire<G>(
+        lock: Arc<L>,
+        acquire: impl FnOnce(&'static L) -> Result<G>,
+    ) -> Result<GuardedArc<L, G>> {
+        // SAFETY: the value behind an `Arc` does not move, and the returned
+        // `GuardedArc` keeps this `Arc` alive until after the guard has been
+        // dropped. The guard is only reachable through its target, so the
+        // `'static` borrow never escapes.
+        let target: &'static L = unsafe { &*Arc::as_ptr(&lock) };
+        let guard = acquire(target)?;
+        Ok(GuardedArc { guard, lock })
+    }
+}
+
+impl<L, G> GuardedArc<L, G> {
+    // `guard` must have been taken from `lock`, see `GuardedArc::acquire`.
+    #[cfg(feature = "async")]
+    pub(crate) fn from_parts(guard: G, lock: Arc<L>) -> GuardedArc<L, G> {
+        GuardedArc { guard, lock }
+    }
+
+    /// The lock this guard holds.
+    pub fn lock(&self) -> &Arc<L> {
+        &self.lock
+    }
+
+    /// Releases the guard, giving back the `Arc`.
+    pub fn unlock(self) -> Arc<L> {
+        let GuardedArc { guard, lock } = self;
+        drop(guard);
+        lock
+    }
+}
+
+impl<L, G> Deref for GuardedArc<L, G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<L, G> DerefMut for GuardedArc<L, G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, L, G, T> LockApiReadGuard<'a, T> for GuardedArc<L, G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.get()
+    }
+}
+
+impl<'a, L, G, T> LockApiWriteGuard<'a, T> for GuardedArc<L, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.get_mut()
+    }
+}
+
+impl<L, G> core::fmt::Debug for GuardedArc<L, G>
+where
+    G: Deref,
+    G::Target: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GuardedArc")
+            .field("data", &&*self.guard)
+            .finish()
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{ArcReadGuardAsync, ArcWriteGuardAsync, GuardedArc};
+    use crate::{async_locking::AsyncLockApi, error::Result};
+    use alloc::sync::Arc;
+
+    impl<L> GuardedArc<L, ()>
+    where
+        L: 'static,
+    {
+        pub async fn read_async<T>(lock: Arc<L>) -> Result<ArcReadGuardAsync<L, T>>
+        where
+            L: AsyncLockApi<T>,
+        {
+            // SAFETY: as in `GuardedArc::acquire`.
+            let target: &'static L = unsafe { &*Arc::as_ptr(&lock) };
+            let guard = target.read().await?;
+            Ok(GuardedArc { guard, lock })
+        }
+
+        pub async fn write_async<T>(lock: Arc<L>) -> Result<ArcWriteGuardAsync<L, T>>
+        where
+            L: AsyncLockApi<T>,
+        {
+            // SAFETY: as in `GuardedArc::acquire`.
+            let target: &'static L = unsafe { &*Arc::as_ptr(&lock) };
+            let guard = target.write().await?;
+            Ok(GuardedArc { guard, lock })
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use core::{marker::PhantomData, ops::Deref};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard},
+};
+
+/// A handle which can only read the shared value. Obtained with
+/// [`Locket::reader`](crate::Locket::reader).
+pub struct ReadLocket<T, L> {
+    inner: L,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T, L> ReadLocket<T, L> {
+    pub fn new(inner: L) -> ReadLocket<T, L> {
+        ReadLocket {
+            inner,
+            _value: PhantomData,
+        }
+    }
+}
+
+impl<T, L> ReadLocket<T, L>
+where
+    L: LockApi<T>,
+{
+    pub fn read(&self) -> Result<L::ReadGuard<'_>> {
+        self.inner.read()
+    }
+
+    pub fn snapshot(&self) -> Result<T>
+    where
+        T: Clone,
+    {
+        Ok(self.inner.read()?.get().clone())
+    }
+}
+
+impl<T, L> Clone for ReadLocket<T, L>
+where
+    L: Clone,
+{
+    fn clone(&self) -> Self {
+        ReadLocket::new(self.inner.clone())
+    }
+}
+
+/// The single handle allowed to write. It is not `Clone`; readers are handed out
+/// with [`reader`](WriteLocket::reader). Obtained with
+/// [`Locket::into_writer`](crate::Locket::into_writer), which only succeeds
+/// for the only handle, strong or weak, so no other writable handle exists.
+pub struct WriteLocket<T, L> {
+    inner: L,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T, L> WriteLocket<T, L> {
+    pub(crate) fn new(inner: L) -> WriteLocket<T, L> {
+        WriteLocket {
+            inner,
+            _value: PhantomData,
+        }
+    }
+
+    pub fn reader(&self) -> ReadLocket<T, L>
+    where
+        L: Clone,
+    {
+        ReadLocket::new(self.inner.clone())
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    /// Joins the halves returned by [`Locket::split`](crate::Locket::split)
+    /// back into the original locket. Fails if `reader` belongs to another lock.
+    pub fn reunite(self, reader: ReadLocket<T, L>) -> core::result::Result<L, ReuniteError<T, L>>
+    where
+        L: Deref,
+    {
+        if core::ptr::eq(&*self.inner, &*reader.inner) {
+            drop(reader);
+            Ok(self.inner)
+        } else {
+            Err(ReuniteError {
+                writer: self,
+                reader,
+            })
+        }
+    }
+}
+
+/// The writing and reading halves returned by
+/// [`Locket::split`](crate::Locket::split).
+pub type Halves<T, L> = (WriteLocket<T, L>, ReadLocket<T, L>);
+
+/// Returned by [`WriteLocket::reunite`] when the halves do not share a lock.
+pub struct ReuniteError<T, L> {
+    pub writer: WriteLocket<T, L>,
+    pub reader: ReadLocket<T, L>,
+}
+
+impl<T, L> core::fmt::Debug for ReuniteError<T, L> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReuniteError").finish_non_exhaustive()
+    }
+}
+
+impl<T, L> core::fmt::Display for ReuniteError<T, L> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "tried to reunite halves of different lockets")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, L> std::error::Error for ReuniteError<T, L> {}
+
+impl<T, L> WriteLocket<T, L>
+where
+    L: LockApi<T>,
+{
+    pub fn read(&self) -> Result<L::ReadGuard<'_>> {
+        self.inner.read()
+    }
+
+    pub fn write(&self) -> Result<L::WriteGuard<'_>> {
+        self.inner.write()
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{ReadLocket, WriteLocket};
+    use crate::{async_locking::AsyncLockApi, error::Result, locking::LockApiReadGuard};
+
+    impl<T, L> ReadLocket<T, L>
+    where
+        L: AsyncLockApi<T>,
+    {
+        pub fn read_async(&self) -> L::ReadFuture<'_> {
+            self.inner.read()
+        }
+
+        pub async fn snapshot_async(&self) -> Result<T>
+        where
+            T: Clone,
+        {
+            Ok(self.inner.read().await?.get().clone())
+        }
+    }
+
+    impl<T, L> WriteLocket<T, L>
+    where
+        L: AsyncLockApi<T>,
+    {
+        pub fn read_async(&self) -> L::ReadFuture<'_> {
+            self.inner.read()
+        }
+
+        pub fn write_async(&self) -> L::WriteFuture<'_> {
+            self.inner.write()
+        }
+    }
+}
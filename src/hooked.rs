@@ -0,0 +1,178 @@
+use alloc::boxed::Box;
+use core::{
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+use std::time::Instant;
+
+use crate::{
+    error::Result,
+    locking::{AccessMode, LockApi, LockApiReadGuard, LockApiWriteGuard},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HookContext {
+    pub name: &'static str,
+    pub mode: AccessMode,
+    pub wait: Duration,
+    /// How long the guard was held; `None` when acquiring.
+    pub held: Option<Duration>,
+}
+
+type Hook = Box<dyn Fn(&HookContext) + Send + Sync>;
+
+/// Invokes user callbacks whenever a guard of the inner lock is created or dropped.
+pub struct Hooked<L> {
+    inner: L,
+    name: &'static str,
+    on_acquire: Option<Hook>,
+    on_release: Option<Hook>,
+}
+
+impl<L> Hooked<L> {
+    pub fn with_name(inner: L, name: &'static str) -> Hooked<L> {
+        Hooked {
+            inner,
+            name,
+            on_acquire: None,
+            on_release: None,
+        }
+    }
+
+    pub fn on_acquire<F>(mut self, hook: F) -> Hooked<L>
+    where
+        F: Fn(&HookContext) + Send + Sync + 'static,
+    {
+        self.on_acquire = Some(Box::new(hook));
+        self
+    }
+
+    pub fn on_release<F>(mut self, hook: F) -> Hooked<L>
+    where
+        F: Fn(&HookContext) + Send + Sync + 'static,
+    {
+        self.on_release = Some(Box::new(hook));
+        self
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    fn acquire<'a, G>(
+        &'a self,
+        mode: AccessMode,
+        lock: impl FnOnce(&'a L) -> Result<G>,
+    ) -> Result<HookedGuard<'a, L, G>> {
+        let start = crate::clock::now();
+        let guard = lock(&self.inner)?;
+        let acquired = crate::clock::now();
+        let context = HookContext {
+            name: self.name,
+            mode,
+            wait: acquired - start,
+            held: None,
+        };
+        if let Some(hook) = &self.on_acquire {
+            hook(&context);
+        }
+        Ok(HookedGuard {
+            guard: Some(guard),
+            hooked: self,
+            context,
+            acquired,
+        })
+    }
+}
+
+impl<L, T> LockApi<T> for Hooked<L>
+where
+    L: LockApi<T>,
+{
+    type ReadGuard<'a>
+        = HookedGuard<'a, L, L::ReadGuard<'a>>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = HookedGuard<'a, L, L::WriteGuard<'a>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.acquire(AccessMode::Read, |lock| lock.read())
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.acquire(AccessMode::Write, |lock| lock.write())
+    }
+
+    fn new(inner: T) -> Self {
+        Hooked::with_name(L::new(inner), core::any::type_name::<T>())
+    }
+}
+
+pub struct HookedGuard<'a, L, G> {
+    guard: Option<G>,
+    hooked: &'a Hooked<L>,
+    context: HookContext,
+    acquired: Instant,
+}
+
+impl<L, G> Drop for HookedGuard<'_, L, G> {
+    fn drop(&mut self) {
+        drop(self.guard.take());
+        if let Some(hook) = &self.hooked.on_release {
+            hook(&HookContext {
+                held: Some(crate::clock::now().saturating_duration_since(self.acquired)),
+                ..self.context
+            });
+        }
+    }
+}
+
+impl<L, G> Deref for HookedGuard<'_, L, G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard.as_ref().unwrap()
+    }
+}
+
+impl<L, G> DerefMut for HookedGuard<'_, L, G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard.as_mut().unwrap()
+    }
+}
+
+impl<'a, T, L, G> LockApiReadGuard<'a, T> for HookedGuard<'a, L, G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.as_ref().unwrap().get()
+    }
+}
+
+impl<'a, T, L, G> LockApiWriteGuard<'a, T> for HookedGuard<'a, L, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.as_mut().unwrap().get_mut()
+    }
+}
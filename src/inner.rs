@@ -0,0 +1,214 @@
+use core::cell::RefCell;
+
+use crate::error::Result;
+
+/// Consumes a lock and returns the protected value. Fails only for backends
+/// which track poisoning.
+pub trait IntoInner<T> {
+    fn into_inner(self) -> Result<T>;
+}
+
+impl<T> IntoInner<T> for RefCell<T> {
+    fn into_inner(self) -> Result<T> {
+        Ok(RefCell::into_inner(self))
+    }
+}
+
+#[cfg(feature = "atomic_refcell")]
+impl<T> IntoInner<T> for atomic_refcell::AtomicRefCell<T> {
+    fn into_inner(self) -> Result<T> {
+        Ok(atomic_refcell::AtomicRefCell::into_inner(self))
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+mod parking_lot_impl {
+    use super::IntoInner;
+    use crate::error::Result;
+    use parking_lot::{FairMutex, Mutex, RwLock};
+
+    impl<T> IntoInner<T> for Mutex<T> {
+        fn into_inner(self) -> Result<T> {
+            Ok(Mutex::into_inner(self))
+        }
+    }
+
+    impl<T> IntoInner<T> for FairMutex<T> {
+        fn into_inner(self) -> Result<T> {
+            Ok(FairMutex::into_inner(self))
+        }
+    }
+
+    impl<T> IntoInner<T> for RwLock<T> {
+        fn into_inner(self) -> Result<T> {
+            Ok(RwLock::into_inner(self))
+        }
+    }
+}
+
+#[cfg(feature = "spin")]
+mod spin_impl {
+    use super::IntoInner;
+    use crate::error::Result;
+    use spin::{mutex::Mutex, rwlock::RwLock};
+
+    impl<T, R> IntoInner<T> for Mutex<T, R> {
+        fn into_inner(self) -> Result<T> {
+            Ok(Mutex::into_inner(self))
+        }
+    }
+
+    impl<T, R> IntoInner<T> for RwLock<T, R> {
+        fn into_inner(self) -> Result<T> {
+            Ok(RwLock::into_inner(self))
+        }
+    }
+}
+
+#[cfg(feature = "std-lock")]
+mod std_impl {
+    use super::IntoInner;
+    use crate::{
+        error::{LockError, Result},
+        poison::{StdMutex, StdRwLock},
+    };
+    use std::sync::{Mutex, RwLock};
+
+    impl<T> IntoInner<T> for Mutex<T> {
+        fn into_inner(self) -> Result<T> {
+            Mutex::into_inner(self).map_err(|_| LockError::Poisoned)
+        }
+    }
+
+    impl<T> IntoInner<T> for RwLock<T> {
+        fn into_inner(self) -> Result<T> {
+            RwLock::into_inner(self).map_err(|_| LockError::Poisoned)
+        }
+    }
+
+    impl<T> IntoInner<T> for StdMutex<T> {
+        fn into_inner(self) -> Result<T> {
+            StdMutex::into_inner(self)
+        }
+    }
+
+    impl<T> IntoInner<T> for StdRwLock<T> {
+        fn into_inner(self) -> Result<T> {
+            StdRwLock::into_inner(self)
+        }
+    }
+}
+
+#[cfg(feature = "async-lock")]
+mod async_lock_impl {
+    use super::IntoInner;
+    use crate::error::Result;
+    use async_lock::{Mutex, RwLock};
+
+    impl<T> IntoInner<T> for Mutex<T> {
+        fn into_inner(self) -> Result<T> {
+            Ok(Mutex::into_inner(self))
+        }
+    }
+
+    impl<T> IntoInner<T> for RwLock<T> {
+        fn into_inner(self) -> Result<T> {
+            Ok(RwLock::into_inner(self))
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_impl {
+    use super::IntoInner;
+    use crate::error::Result;
+    use tokio::sync::{Mutex, RwLock};
+
+    impl<T> IntoInner<T> for Mutex<T> {
+        fn into_inner(self) -> Result<T> {
+            Ok(Mutex::into_inner(self))
+        }
+    }
+
+    impl<T> IntoInner<T> for RwLock<T> {
+        fn into_inner(self) -> Result<T> {
+            Ok(RwLock::into_inner(self))
+        }
+    }
+}
+
+#[cfg(all(feature = "async-std", not(feature = "async-lock")))]
+mod async_std_impl {
+    use super::IntoInner;
+    use crate::error::Result;
+    use async_std::sync::{Mutex, RwLock};
+
+    impl<T> IntoInner<T> for Mutex<T> {
+        fn into_inner(self) -> Result<T> {
+            Ok(Mutex::into_inner(self))
+        }
+    }
+
+    impl<T> IntoInner<T> for RwLock<T> {
+        fn into_inner(self) -> Result<T> {
+            Ok(RwLock::into_inner(self))
+        }
+    }
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_feature = "atomics"))]
+mod wasm_impl {
+    use super::IntoInner;
+    use crate::error::{LockError, Result};
+    use wasm_sync::{Mutex, RwLock};
+
+    impl<T> IntoInner<T> for Mutex<T> {
+        fn into_inner(self) -> Result<T> {
+            Mutex::into_inner(self).map_err(|_| LockError::Poisoned)
+        }
+    }
+
+    impl<T> IntoInner<T> for RwLock<T> {
+        fn into_inner(self) -> Result<T> {
+            RwLock::into_inner(self).map_err(|_| LockError::Poisoned)
+        }
+    }
+}
+
+#[cfg(all(loom, feature = "std-lock"))]
+mod loom_impl {
+    use super::IntoInner;
+    use crate::error::{LockError, Result};
+    use loom::sync::{Mutex, RwLock};
+
+    impl<T> IntoInner<T> for Mutex<T> {
+        fn into_inner(self) -> Result<T> {
+            Mutex::into_inner(self).map_err(|_| LockError::Poisoned)
+        }
+    }
+
+    impl<T> IntoInner<T> for RwLock<T> {
+        fn into_inner(self) -> Result<T> {
+            RwLock::into_inner(self).map_err(|_| LockError::Poisoned)
+        }
+    }
+}
+
+#[cfg(feature = "shuttle")]
+mod shuttle_impl {
+    use super::IntoInner;
+    use crate::error::{LockError, Result};
+    use shuttle::sync::{Mutex, RwLock};
+
+    impl<T> IntoInner<T> for Mutex<T> {
+        fn into_inner(self) -> Result<T> {
+            Mutex::into_inner(self).map_err(|_| LockError::Poisoned)
+        }
+    }
+
+    impl<T> IntoInner<T> for RwLock<T> {
+        fn into_inner(self) -> Result<T> {
+            RwLock::into_inner(self).map_err(|_| LockError::Poisoned)
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use alloc::sync::Arc;
+use core::{
+    cell::UnsafeCell,
+    ops::{Deref, DerefMut},
+};
+use std::sync::{Condvar, Mutex, MutexGuard, PoisonError};
+
+use crate::{
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    stats::LockStats,
+    try_lock::TryLockApi,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Mode {
+    IntentShared,
+    IntentExclusive,
+    Shared,
+    Exclusive,
+}
+
+impl Mode {
+    // The modes which may be held alongside this one.
+    fn compatible(self, held: &[usize; 4]) -> bool {
+        let [is, ix, s, x] = *held;
+        match self {
+            Mode::IntentShared => x == 0,
+            Mode::IntentExclusive => s == 0 && x == 0,
+            Mode::Shared => ix == 0 && x == 0,
+            Mode::Exclusive => is == 0 && ix == 0 && s == 0 && x == 0,
+        }
+    }
+}
+
+// The lock state of one node, shared with its children.
+struct Node {
+    held: Mutex<[usize; 4]>,
+    released: Condvar,
+    parent: Option<Arc<Node>>,
+}
+
+impl Node {
+    fn new(parent: Option<Arc<Node>>) -> Arc<Node> {
+        Arc::new(Node {
+            held: Mutex::new([0; 4]),
+            released: Condvar::new(),
+            parent,
+        })
+    }
+
+    fn held(&self) -> MutexGuard<'_, [usize; 4]> {
+        self.held.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn acquire(&self, mode: Mode) {
+        let mut held = self.held();
+        while !mode.compatible(&held) {
+            held = self
+                .released
+                .wait(held)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        held[mode as usize] += 1;
+    }
+
+    fn try_acquire(&self, mode: Mode) -> bool {
+        let mut held = self.held();
+        if !mode.compatible(&held) {
+            return false;
+        }
+        held[mode as usize] += 1;
+        true
+    }
+
+    fn release(&self, mode: Mode) {
+        self.held()[mode as usize] -= 1;
+        self.released.notify_all();
+    }
+
+    // Ancestors are always locked root first, which keeps acquisitions on
+    // different paths from deadlocking.
+    fn acquire_path(&self, intent: Mode, mode: Mode) {
+        if let Some(parent) = &self.parent {
+            parent.acquire_path(intent, intent);
+        }
+        self.acquire(mode);
+    }
+
+    fn try_acquire_path(&self, intent: Mode, mode: Mode) -> bool {
+        if let Some(parent) = &self.parent {
+            if !parent.try_acquire_path(intent, intent) {
+                return false;
+            }
+        }
+        if self.try_acquire(mode) {
+            return true;
+        }
+        if let Some(parent) = &self.parent {
+            parent.release_path(intent, intent);
+        }
+        false
+    }
+
+    fn release_path(&self, intent: Mode, mode: Mode) {
+        self.release(mode);
+        if let Some(parent) = &self.parent {
+            parent.release_path(intent, intent);
+        }
+    }
+}
+
+/// One node of a tree of locks using multiple granularity locking. Reading a
+/// node takes intent-shared locks on its ancestors, writing it intent-exclusive
+/// ones. Writers of disjoint subtrees therefore run concurrently, while a
+/// guard on a node also covers its whole subtree: writing the root waits for
+/// every other guard in the tree and keeps new ones out.
+///
+/// Nodes may hold values of different types. A thread holding a guard must
+/// not lock an ancestor of that node in a conflicting mode, which deadlocks.
+pub struct IntentLocket<T> {
+    node: Arc<Node>,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for IntentLocket<T> {}
+unsafe impl<T: Send + Sync> Sync for IntentLocket<T> {}
+
+impl<T> IntentLocket<T> {
+    /// A new root.
+    pub fn new(inner: T) -> IntentLocket<T> {
+        IntentLocket {
+            node: Node::new(None),
+            value: UnsafeCell::new(inner),
+        }
+    }
+
+    /// A new child of this node. It refers to the lock state of its
+    /// ancestors, not their values, so it may outlive them.
+    pub fn child<U>(&self, inner: U) -> IntentLocket<U> {
+        IntentLocket {
+            node: Node::new(Some(self.node.clone())),
+            value: UnsafeCell::new(inner),
+        }
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.node.parent.is_none()
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: Default> Default for IntentLocket<T> {
+    fn default() -> Self {
+        IntentLocket::new(T::default())
+    }
+}
+
+impl<T> LockApi<T> for IntentLocket<T>
+where
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = IntentReadGuard<'a, T>;
+
+    type WriteGuard<'a> = IntentWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.node.acquire_path(Mode::IntentShared, Mode::Shared);
+        Ok(IntentReadGuard { lock: self })
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.node
+            .acquire_path(Mode::IntentExclusive, Mode::Exclusive);
+        Ok(IntentWriteGuard { lock: self })
+    }
+
+    fn new(inner: T) -> Self {
+        IntentLocket::new(inner)
+    }
+}
+
+impl<T> TryLockApi<T> for IntentLocket<T>
+where
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        if !self.node.try_acquire_path(Mode::IntentShared, Mode::Shared) {
+            return Err(LockError::WouldBlock);
+        }
+        Ok(IntentReadGuard { lock: self })
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        if !self
+            .node
+            .try_acquire_path(Mode::IntentExclusive, Mode::Exclusive)
+        {
+            return Err(LockError::WouldBlock);
+        }
+        Ok(IntentWriteGuard { lock: self })
+    }
+}
+
+impl<T> IntoInner<T> for IntentLocket<T> {
+    fn into_inner(self) -> Result<T> {
+        Ok(IntentLocket::into_inner(self))
+    }
+}
+
+impl<T> PoisonApi for IntentLocket<T> {}
+
+// Intent locks taken for descendants do not count, only guards on this node.
+impl<T> LockStats for IntentLocket<T> {
+    fn is_locked(&self) -> bool {
+        let held = self.node.held();
+        held[Mode::Shared as usize] > 0 || held[Mode::Exclusive as usize] > 0
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        self.node.held()[Mode::Exclusive as usize] > 0
+    }
+
+    fn reader_count(&self) -> Option<usize> {
+        Some(self.node.held()[Mode::Shared as usize])
+    }
+}
+
+impl<T> core::fmt::Debug for IntentLocket<T>
+where
+    T: core::fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("IntentLocket")
+            .field("data", &crate::peek::peek(self))
+            .finish()
+    }
+}
+
+pub struct IntentReadGuard<'a, T> {
+    lock: &'a IntentLocket<T>,
+}
+
+impl<T> Drop for IntentReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock
+            .node
+            .release_path(Mode::IntentShared, Mode::Shared);
+    }
+}
+
+impl<T> Deref for IntentReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: a shared lock on the node excludes its writers.
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for IntentReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+pub struct IntentWriteGuard<'a, T> {
+    lock: &'a IntentLocket<T>,
+}
+
+impl<T> Drop for IntentWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock
+            .node
+            .release_path(Mode::IntentExclusive, Mode::Exclusive);
+    }
+}
+
+impl<T> Deref for IntentWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the exclusive lock on the node excludes everybody else.
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T> DerefMut for IntentWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: as above.
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for IntentWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for IntentWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self
+    }
+}
@@ -0,0 +1,254 @@
+use alloc::sync::Arc;
+use core::{
+    hash::Hash,
+    ops::{Deref, DerefMut},
+};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+};
+
+/// A lock per key. Locks are created on first use and keep their value,
+/// unless [`pruning`](KeyedLocket::pruning) is enabled.
+pub struct KeyedLocket<K, V, L> {
+    locks: Mutex<HashMap<K, Arc<L>>>,
+    init: fn(&K) -> V,
+    prune: bool,
+}
+
+impl<K, V, L> KeyedLocket<K, V, L>
+where
+    K: Hash + Eq + Clone,
+    V: Default,
+{
+    pub fn new() -> KeyedLocket<K, V, L> {
+        KeyedLocket::with_init(|_| V::default())
+    }
+}
+
+impl<K, V, L> Default for KeyedLocket<K, V, L>
+where
+    K: Hash + Eq + Clone,
+    V: Default,
+{
+    fn default() -> Self {
+        KeyedLocket::new()
+    }
+}
+
+impl<K, V, L> KeyedLocket<K, V, L>
+where
+    K: Hash + Eq + Clone,
+{
+    /// Each new per-key lock starts out with `init(key)`.
+    pub fn with_init(init: fn(&K) -> V) -> KeyedLocket<K, V, L> {
+        KeyedLocket {
+            locks: Mutex::new(HashMap::new()),
+            init,
+            prune: false,
+        }
+    }
+
+    /// Removes a key's lock, and with it the value, once the last guard (or
+    /// pending acquisition) for the key is dropped. Suits values which only
+    /// matter while locked, like `()`, so the map does not grow with every
+    /// key ever used.
+    pub fn pruning(mut self, prune: bool) -> KeyedLocket<K, V, L> {
+        self.prune = prune;
+        self
+    }
+
+    /// Number of keys with a lock; with pruning, those locked or being locked.
+    pub fn len(&self) -> usize {
+        self.locks().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.locks().is_empty()
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.locks().contains_key(key)
+    }
+
+    fn locks(&self) -> MutexGuard<'_, HashMap<K, Arc<L>>> {
+        self.locks.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn entry(&self, key: K, new: impl FnOnce(V) -> L) -> Entry<'_, K, V, L> {
+        let lock = self
+            .locks()
+            .entry(key.clone())
+            .or_insert_with_key(|key| Arc::new(new((self.init)(key))))
+            .clone();
+        Entry {
+            owner: self,
+            key,
+            lock: Some(lock),
+        }
+    }
+}
+
+impl<K, V, L> KeyedLocket<K, V, L>
+where
+    K: Hash + Eq + Clone,
+    L: LockApi<V>,
+{
+    pub fn read(&self, key: K) -> Result<KeyedGuard<'_, K, V, L, L::ReadGuard<'_>>> {
+        let entry = self.entry(key, L::new);
+        // SAFETY: the guard is stored next to the `Arc` keeping the lock alive
+        // and is dropped before it.
+        let lock: &L = unsafe { &*Arc::as_ptr(entry.lock()) };
+        let guard = lock.read()?;
+        Ok(KeyedGuard { guard, entry })
+    }
+
+    pub fn write(&self, key: K) -> Result<KeyedGuard<'_, K, V, L, L::WriteGuard<'_>>> {
+        let entry = self.entry(key, L::new);
+        // SAFETY: see `read`.
+        let lock: &L = unsafe { &*Arc::as_ptr(entry.lock()) };
+        let guard = lock.write()?;
+        Ok(KeyedGuard { guard, entry })
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{KeyedGuard, KeyedLocket};
+    use crate::{async_locking::AsyncLockApi, error::Result};
+    use alloc::sync::Arc;
+    use core::hash::Hash;
+
+    impl<K, V, L> KeyedLocket<K, V, L>
+    where
+        K: Hash + Eq + Clone,
+        L: AsyncLockApi<V>,
+    {
+        pub async fn read_async(
+            &self,
+            key: K,
+        ) -> Result<KeyedGuard<'_, K, V, L, L::ReadGuard<'_>>> {
+            let entry = self.entry(key, L::new);
+            // SAFETY: see `KeyedLocket::read`.
+            let lock: &L = unsafe { &*Arc::as_ptr(entry.lock()) };
+            let guard = lock.read().await?;
+            Ok(KeyedGuard { guard, entry })
+        }
+
+        pub async fn write_async(
+            &self,
+            key: K,
+        ) -> Result<KeyedGuard<'_, K, V, L, L::WriteGuard<'_>>> {
+            let entry = self.entry(key, L::new);
+            // SAFETY: see `KeyedLocket::read`.
+            let lock: &L = unsafe { &*Arc::as_ptr(entry.lock()) };
+            let guard = lock.write().await?;
+            Ok(KeyedGuard { guard, entry })
+        }
+    }
+}
+
+struct Entry<'a, K, V, L>
+where
+    K: Hash + Eq,
+{
+    owner: &'a KeyedLocket<K, V, L>,
+    key: K,
+    lock: Option<Arc<L>>,
+}
+
+impl<K, V, L> Entry<'_, K, V, L>
+where
+    K: Hash + Eq,
+{
+    fn lock(&self) -> &Arc<L> {
+        self.lock.as_ref().unwrap()
+    }
+}
+
+impl<K, V, L> Drop for Entry<'_, K, V, L>
+where
+    K: Hash + Eq,
+{
+    fn drop(&mut self) {
+        if !self.owner.prune {
+            return;
+        }
+        // References are only cloned and dropped with the map locked, so the
+        // strong count can be trusted while we hold it.
+        let mut locks = self
+            .owner
+            .locks
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        drop(self.lock.take());
+        if HashMap::get(&locks, &self.key).is_some_and(|lock| Arc::strong_count(lock) == 1) {
+            locks.remove(&self.key);
+        }
+    }
+}
+
+pub struct KeyedGuard<'a, K, V, L, G>
+where
+    K: Hash + Eq,
+{
+    // Declared first so it is dropped before the entry releases the lock.
+    guard: G,
+    entry: Entry<'a, K, V, L>,
+}
+
+impl<K, V, L, G> KeyedGuard<'_, K, V, L, G>
+where
+    K: Hash + Eq,
+{
+    pub fn key(&self) -> &K {
+        &self.entry.key
+    }
+}
+
+impl<K, V, L, G> Deref for KeyedGuard<'_, K, V, L, G>
+where
+    K: Hash + Eq,
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<K, V, L, G> DerefMut for KeyedGuard<'_, K, V, L, G>
+where
+    K: Hash + Eq,
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, T, K, V, L, G> LockApiReadGuard<'a, T> for KeyedGuard<'a, K, V, L, G>
+where
+    K: Hash + Eq,
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.get()
+    }
+}
+
+impl<'a, T, K, V, L, G> LockApiWriteGuard<'a, T> for KeyedGuard<'a, K, V, L, G>
+where
+    K: Hash + Eq,
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.get_mut()
+    }
+}
@@ -0,0 +1,122 @@
+use core::{marker::PhantomData, ops::Deref};
+
+use crate::{error::Result, locking::LockApi, once::OnceApi};
+
+#[cfg(feature = "std")]
+type DefaultOnce<T> = std::sync::OnceLock<T>;
+
+#[cfg(all(not(feature = "std"), feature = "spin"))]
+type DefaultOnce<T> = spin::Once<T>;
+
+#[cfg(all(not(feature = "std"), not(feature = "spin")))]
+type DefaultOnce<T> = core::cell::OnceCell<T>;
+
+pub struct LazyLocket<T, L, F = fn() -> T> {
+    cell: DefaultOnce<L>,
+    init: F,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T, L, F> LazyLocket<T, L, F> {
+    pub const fn new(init: F) -> LazyLocket<T, L, F> {
+        LazyLocket {
+            cell: DefaultOnce::new(),
+            init,
+            _value: PhantomData,
+        }
+    }
+
+    pub fn get(&self) -> Option<&L> {
+        OnceApi::get(&self.cell)
+    }
+}
+
+impl<T, L, F> LazyLocket<T, L, F>
+where
+    L: LockApi<T>,
+    F: Fn() -> T,
+{
+    pub fn force(&self) -> &L {
+        self.cell.get_or_init(|| L::new((self.init)()))
+    }
+
+    pub fn read(&self) -> Result<L::ReadGuard<'_>> {
+        self.force().read()
+    }
+
+    pub fn write(&self) -> Result<L::WriteGuard<'_>> {
+        self.force().write()
+    }
+}
+
+impl<T, L, F> Deref for LazyLocket<T, L, F>
+where
+    L: LockApi<T>,
+    F: Fn() -> T,
+{
+    type Target = L;
+
+    fn deref(&self) -> &Self::Target {
+        self.force()
+    }
+}
+
+#[cfg(feature = "async")]
+pub use self::async_impl::LazyAsyncLocket;
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::DefaultOnce;
+    use crate::{async_locking::AsyncLockApi, once::OnceApi};
+    use core::{marker::PhantomData, ops::Deref};
+
+    pub struct LazyAsyncLocket<T, L, F = fn() -> T> {
+        cell: DefaultOnce<L>,
+        init: F,
+        _value: PhantomData<fn() -> T>,
+    }
+
+    impl<T, L, F> LazyAsyncLocket<T, L, F> {
+        pub const fn new(init: F) -> LazyAsyncLocket<T, L, F> {
+            LazyAsyncLocket {
+                cell: DefaultOnce::new(),
+                init,
+                _value: PhantomData,
+            }
+        }
+
+        pub fn get(&self) -> Option<&L> {
+            OnceApi::get(&self.cell)
+        }
+    }
+
+    impl<T, L, F> LazyAsyncLocket<T, L, F>
+    where
+        L: AsyncLockApi<T>,
+        F: Fn() -> T,
+    {
+        pub fn force(&self) -> &L {
+            self.cell.get_or_init(|| L::new((self.init)()))
+        }
+
+        pub fn read(&self) -> L::ReadFuture<'_> {
+            self.force().read()
+        }
+
+        pub fn write(&self) -> L::WriteFuture<'_> {
+            self.force().write()
+        }
+    }
+
+    impl<T, L, F> Deref for LazyAsyncLocket<T, L, F>
+    where
+        L: AsyncLockApi<T>,
+        F: Fn() -> T,
+    {
+        type Target = L;
+
+        fn deref(&self) -> &Self::Target {
+            self.force()
+        }
+    }
+}
@@ -0,0 +1,110 @@
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, rc::Rc, sync::Arc};
+
+use crate::{error::Result, locking::LockApi, try_lock::TryLockApi};
+
+/// Gives up ownership of a handle, keeping the value alive for the rest of the
+/// program. The reference it returns needs no reference counting to copy.
+pub trait Leak {
+    type Inner: ?Sized;
+
+    fn leak(this: Self) -> &'static Self::Inner;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Leak for Arc<T>
+where
+    T: ?Sized + 'static,
+{
+    type Inner = T;
+
+    fn leak(this: Self) -> &'static T {
+        // SAFETY: the strong count taken by `this` is never given back, so the
+        // value is never freed.
+        unsafe { &*Arc::into_raw(this) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Leak for Rc<T>
+where
+    T: ?Sized + 'static,
+{
+    type Inner = T;
+
+    fn leak(this: Self) -> &'static T {
+        // SAFETY: as for `Arc`.
+        unsafe { &*Rc::into_raw(this) }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Leak for Box<T>
+where
+    T: ?Sized + 'static,
+{
+    type Inner = T;
+
+    fn leak(this: Self) -> &'static T {
+        Box::leak(this)
+    }
+}
+
+#[cfg(feature = "portable-atomic")]
+impl<T> Leak for portable_atomic_util::Arc<T>
+where
+    T: 'static,
+{
+    type Inner = T;
+
+    fn leak(this: Self) -> &'static T {
+        // SAFETY: as for `Arc`.
+        unsafe { &*portable_atomic_util::Arc::into_raw(this) }
+    }
+}
+
+/// Guards which are `'static` because the lock is, as for `static` items and
+/// lockets given up with [`Locket::leak`](crate::Locket::leak).
+pub trait StaticLockApi<T, L>
+where
+    L: LockApi<T> + 'static,
+{
+    fn read_static(self) -> Result<L::ReadGuard<'static>>;
+
+    fn write_static(self) -> Result<L::WriteGuard<'static>>;
+
+    fn try_read_static(self) -> Result<L::ReadGuard<'static>>
+    where
+        L: TryLockApi<T>;
+
+    fn try_write_static(self) -> Result<L::WriteGuard<'static>>
+    where
+        L: TryLockApi<T>;
+}
+
+impl<T, L> StaticLockApi<T, L> for &'static L
+where
+    L: LockApi<T> + 'static,
+{
+    fn read_static(self) -> Result<L::ReadGuard<'static>> {
+        self.read()
+    }
+
+    fn write_static(self) -> Result<L::WriteGuard<'static>> {
+        self.write()
+    }
+
+    fn try_read_static(self) -> Result<L::ReadGuard<'static>>
+    where
+        L: TryLockApi<T>,
+    {
+        self.try_read()
+    }
+
+    fn try_write_static(self) -> Result<L::WriteGuard<'static>>
+    where
+        L: TryLockApi<T>,
+    {
+        self.try_write()
+    }
+}
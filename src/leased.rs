@@ -0,0 +1,268 @@
+use core::time::Duration;
+use std::{
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    time::Instant,
+};
+
+use crate::{
+    error::{LockError, Result},
+    poison::PoisonApi,
+    stats::LockStats,
+};
+
+#[derive(Clone, Copy)]
+struct Holder {
+    token: u64,
+    // `None` for a lease too long to represent, which never runs out.
+    expires_at: Option<Instant>,
+}
+
+#[derive(Default)]
+struct LeaseState {
+    holder: Option<Holder>,
+    next_token: u64,
+    revocations: u64,
+}
+
+impl LeaseState {
+    // The live lease, forgetting one which ran out.
+    fn holder(&mut self, now: Instant) -> Option<Holder> {
+        match self.holder {
+            Some(Holder {
+                expires_at: Some(expires_at),
+                ..
+            }) if expires_at <= now => {
+                self.holder = None;
+                self.revocations += 1;
+                None
+            }
+            holder => holder,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Wait {
+    Never,
+    Forever,
+    Until(Instant),
+}
+
+/// An exclusive locket whose write guards hold a lease. A holder which keeps
+/// its guard past the lease is revoked: waiters proceed, and every later
+/// access through the old guard fails with [`LockError::Expired`].
+///
+/// Guards hand out the value only for the duration of a closure, so a revoked
+/// holder cannot keep a reference to it. An access which is running when the
+/// lease runs out is not interrupted; the next holder waits for it to finish.
+pub struct LeasedLocket<T> {
+    lease: Duration,
+    state: Mutex<LeaseState>,
+    released: Condvar,
+    data: Mutex<T>,
+}
+
+impl<T> LeasedLocket<T> {
+    pub fn new(inner: T, lease: Duration) -> LeasedLocket<T> {
+        LeasedLocket {
+            lease,
+            state: Mutex::new(LeaseState::default()),
+            released: Condvar::new(),
+            data: Mutex::new(inner),
+        }
+    }
+
+    pub fn lease(&self) -> Duration {
+        self.lease
+    }
+
+    /// How many leases were revoked because their holder overran them.
+    pub fn revocations(&self) -> u64 {
+        self.state().revocations
+    }
+
+    pub fn into_inner(self) -> Result<T> {
+        self.data.into_inner().map_err(|_| LockError::Poisoned)
+    }
+
+    pub fn get_mut(&mut self) -> Result<&mut T> {
+        self.data.get_mut().map_err(|_| LockError::Poisoned)
+    }
+
+    /// Takes a lease, waiting for the current one to be released or to run
+    /// out.
+    pub fn write(&self) -> Result<LeasedGuard<'_, T>> {
+        self.acquire(Wait::Forever)
+    }
+
+    pub fn try_write(&self) -> Result<LeasedGuard<'_, T>> {
+        self.acquire(Wait::Never)
+    }
+
+    pub fn write_timeout(&self, timeout: Duration) -> Result<LeasedGuard<'_, T>> {
+        match crate::clock::now().checked_add(timeout) {
+            Some(deadline) => self.acquire(Wait::Until(deadline)),
+            None => self.acquire(Wait::Forever),
+        }
+    }
+
+    /// Reads the value once no lease is held.
+    pub fn read<R>(&self, f: impl FnOnce(&T) -> R) -> Result<R> {
+        loop {
+            drop(self.wait_free(Wait::Forever)?);
+            // Check again holding the value, as `access` does, since a lease
+            // may have been taken in between.
+            let data = self.data()?;
+            if self.state().holder(crate::clock::now()).is_none() {
+                return Ok(f(&*data));
+            }
+        }
+    }
+
+    // When a lease taken or renewed now runs out, or `None` if it never does.
+    fn expiry(&self, now: Instant) -> Option<Instant> {
+        now.checked_add(self.lease)
+    }
+
+    // The state is only touched in short sections which cannot panic.
+    fn state(&self) -> MutexGuard<'_, LeaseState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn data(&self) -> Result<MutexGuard<'_, T>> {
+        self.data.lock().map_err(|_| LockError::Poisoned)
+    }
+
+    // Waits until no live lease is held, returning the state still locked. A
+    // lease without an expiry is waited for until it is released.
+    fn wait_free(&self, wait: Wait) -> Result<MutexGuard<'_, LeaseState>> {
+        let mut state = self.state();
+        loop {
+            let now = crate::clock::now();
+            let Some(holder) = state.holder(now) else {
+                return Ok(state);
+            };
+            let until = match wait {
+                Wait::Never => return Err(LockError::WouldBlock),
+                Wait::Forever => holder.expires_at,
+                Wait::Until(deadline) if deadline <= now => return Err(LockError::Timeout),
+                Wait::Until(deadline) => Some(match holder.expires_at {
+                    Some(expires_at) => deadline.min(expires_at),
+                    None => deadline,
+                }),
+            };
+            state = match until {
+                Some(until) => {
+                    self.released
+                        .wait_timeout(state, until.saturating_duration_since(now))
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .released
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+
+    fn acquire(&self, wait: Wait) -> Result<LeasedGuard<'_, T>> {
+        let mut state = self.wait_free(wait)?;
+        let token = state.next_token;
+        state.next_token += 1;
+        state.holder = Some(Holder {
+            token,
+            expires_at: self.expiry(crate::clock::now()),
+        });
+        Ok(LeasedGuard { lock: self, token })
+    }
+
+    // Checks the lease of `token`, holding the value locked so the lease
+    // cannot move on between the check and the access.
+    fn access(&self, token: u64) -> Result<MutexGuard<'_, T>> {
+        let data = self.data()?;
+        match self.state().holder(crate::clock::now()) {
+            Some(holder) if holder.token == token => Ok(data),
+            _ => Err(LockError::Expired),
+        }
+    }
+}
+
+impl<T> PoisonApi for LeasedLocket<T> {}
+
+impl<T> LockStats for LeasedLocket<T> {
+    fn is_locked(&self) -> bool {
+        self.state().holder(crate::clock::now()).is_some()
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        self.is_locked()
+    }
+}
+
+impl<T> core::fmt::Debug for LeasedLocket<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LeasedLocket")
+            .field("lease", &self.lease)
+            .field("leased", &self.is_locked())
+            .finish_non_exhaustive()
+    }
+}
+
+/// A lease on a [`LeasedLocket`], released when dropped.
+pub struct LeasedGuard<'a, T> {
+    lock: &'a LeasedLocket<T>,
+    token: u64,
+}
+
+impl<T> LeasedGuard<'_, T> {
+    /// Runs `f` with the value, or fails with [`LockError::Expired`] once the
+    /// lease was revoked.
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R> {
+        Ok(f(&mut *self.lock.access(self.token)?))
+    }
+
+    /// When the lease runs out, or `None` once it was revoked or if it never
+    /// runs out.
+    pub fn expires_at(&self) -> Option<Instant> {
+        self.holder().and_then(|holder| holder.expires_at)
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.holder().is_none()
+    }
+
+    fn holder(&self) -> Option<Holder> {
+        let mut state = self.lock.state();
+        state
+            .holder(crate::clock::now())
+            .filter(|holder| holder.token == self.token)
+    }
+
+    /// Extends the lease to a full lease duration from now. Fails with
+    /// [`LockError::Expired`] once it was revoked.
+    pub fn renew(&self) -> Result<()> {
+        let now = crate::clock::now();
+        let mut state = self.lock.state();
+        match state.holder(now) {
+            Some(holder) if holder.token == self.token => {
+                state.holder = Some(Holder {
+                    token: self.token,
+                    expires_at: self.lock.expiry(now),
+                });
+                Ok(())
+            }
+            _ => Err(LockError::Expired),
+        }
+    }
+}
+
+impl<T> Drop for LeasedGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.lock.state();
+        if matches!(state.holder(crate::clock::now()), Some(holder) if holder.token == self.token) {
+            state.holder = None;
+            self.lock.released.notify_all();
+        }
+    }
+}
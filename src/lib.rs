@@ -1,26 +1,336 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "nightly", feature(reentrant_lock))]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(any(feature = "alloc", feature = "spin"))]
+mod alias;
+#[cfg(feature = "async")]
+mod async_event;
 #[cfg(feature = "async")]
 mod async_lock;
 #[cfg(feature = "async")]
 mod async_locking;
+#[cfg(feature = "async")]
+mod async_monitor;
+#[cfg(feature = "async")]
+mod async_once;
+
+#[cfg(feature = "async")]
+mod async_timed;
+mod backoff;
+#[cfg(feature = "std")]
+mod batched;
+#[cfg(feature = "async")]
+mod blocking;
+#[cfg(target_has_atomic = "ptr")]
+mod borrow;
+#[cfg(feature = "std")]
+mod builder;
+mod cell;
+mod checked;
+#[cfg(feature = "std")]
+mod clock;
+mod compare;
+#[cfg(feature = "max-wait")]
+pub mod config;
+#[cfg(feature = "std")]
+mod cow;
+#[cfg(feature = "deadlock_detection")]
+pub mod deadlock;
 
+#[cfg(target_has_atomic = "ptr")]
+mod atomic;
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "distributed")]
+mod distributed;
+#[cfg(target_has_atomic = "ptr")]
+mod double;
+#[cfg(feature = "epoch")]
+mod epoch;
 mod error;
+#[cfg(feature = "file-lock")]
+mod file;
+mod ghost;
+#[cfg(feature = "alloc")]
+mod guarded;
+mod handle;
+#[cfg(feature = "hooks")]
+mod hooked;
+mod inner;
+#[cfg(feature = "std")]
+mod intent;
+#[cfg(feature = "std")]
+mod keyed;
+mod lazy;
+mod leak;
+#[cfg(feature = "std")]
+mod leased;
 mod lock;
+#[cfg(all(feature = "lock_api", target_has_atomic = "ptr"))]
+mod lock_api_compat;
+mod lockable;
 mod locking;
+mod macros;
+mod mapped;
+#[cfg(feature = "metrics")]
+mod metrics;
+#[cfg(feature = "std")]
+mod monitor;
+mod multi;
+#[cfg(feature = "std")]
+mod mvcc;
+#[cfg(feature = "named")]
+mod named;
+#[cfg(feature = "async")]
+mod notify;
+#[cfg(feature = "alloc")]
+mod observed;
+mod once;
+#[cfg(feature = "lock-order")]
+mod order;
+mod peek;
+#[cfg(feature = "alloc")]
+mod pinned;
+mod poison;
+mod policy;
+#[cfg(all(feature = "async", feature = "alloc"))]
+mod poll;
+pub mod prelude;
+#[cfg(feature = "std")]
+mod queued;
+#[cfg(target_has_atomic = "ptr")]
+mod raw;
+mod readonly;
+#[cfg(feature = "recursion-check")]
+mod recursion;
+#[cfg(feature = "redis")]
+mod redlock;
+mod reentrant;
+#[cfg(feature = "registry")]
+pub mod registry;
+mod retry;
+#[cfg(feature = "std")]
+mod rwlock;
+#[cfg(target_has_atomic = "ptr")]
+mod seqlock;
+#[cfg(feature = "serde")]
+pub mod serde;
+mod sharded;
+#[cfg(feature = "shared-memory")]
+mod shm;
+pub mod stats;
+#[cfg(feature = "arc-swap")]
+mod swap;
+#[cfg(feature = "testing")]
+pub mod testing;
+#[cfg(feature = "std-lock")]
+mod thread_local;
+#[cfg(feature = "std")]
+mod timed;
+#[cfg(feature = "tracing")]
+mod traced;
+mod transaction;
+mod try_lock;
 mod types;
+#[cfg(target_has_atomic = "64")]
+mod versioned;
+#[cfg(feature = "wait-graph")]
+pub mod wait_graph;
+#[cfg(all(feature = "async", feature = "alloc", target_has_atomic = "ptr"))]
+mod wakers;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(all(feature = "event-listener", target_has_atomic = "64"))]
+mod watch;
+#[cfg(all(feature = "tokio", feature = "std"))]
+mod watch_backed;
+#[cfg(feature = "watchdog")]
+mod watchdog;
+mod zip;
 
-pub use self::{error::*, lock::Locket, locking::*, types::*};
+pub use self::{
+    backoff::*, cell::*, checked::*, compare::*, error::*, ghost::*, handle::*, inner::*, lazy::*,
+    leak::*, lock::Locket, locking::*, mapped::*, multi::*, once::*, peek::*, poison::*, policy::*,
+    readonly::*, reentrant::*, retry::*, sharded::*, stats::*, transaction::*, try_lock::*,
+    types::*, zip::*,
+};
 
+#[cfg(any(feature = "alloc", feature = "spin"))]
+pub use self::alias::*;
+#[cfg(feature = "async")]
+pub use self::async_event::*;
 #[cfg(feature = "async")]
 pub use self::async_lock::*;
 #[cfg(feature = "async")]
+pub use self::async_monitor::*;
+#[cfg(feature = "async")]
+pub use self::async_once::*;
+#[cfg(feature = "async")]
 pub use async_locking::*;
 
+#[cfg(feature = "std")]
+pub use self::keyed::*;
+#[cfg(feature = "std")]
+pub use self::leased::*;
+#[cfg(all(feature = "lock_api", target_has_atomic = "ptr"))]
+pub use self::lock_api_compat::*;
+#[cfg(feature = "lock-order")]
+pub use self::order::*;
+#[cfg(target_has_atomic = "64")]
+pub use self::versioned::*;
+#[cfg(target_has_atomic = "ptr")]
+pub use self::{atomic::*, borrow::*, double::*, raw::*, seqlock::*};
+
+#[cfg(feature = "async")]
+pub use self::async_timed::*;
+#[cfg(feature = "std")]
+pub use self::batched::*;
+#[cfg(feature = "async")]
+pub use self::blocking::*;
+#[cfg(feature = "std")]
+pub use self::builder::*;
+#[cfg(feature = "std")]
+pub use self::clock::{Clock, SystemClock};
+#[cfg(feature = "std")]
+pub use self::cow::*;
+#[cfg(feature = "std")]
+pub use self::diff::*;
+#[cfg(feature = "distributed")]
+pub use self::distributed::*;
+#[cfg(feature = "epoch")]
+pub use self::epoch::*;
+#[cfg(feature = "file-lock")]
+pub use self::file::*;
+#[cfg(feature = "alloc")]
+pub use self::guarded::*;
+#[cfg(feature = "hooks")]
+pub use self::hooked::*;
+#[cfg(feature = "std")]
+pub use self::intent::*;
+#[cfg(feature = "alloc")]
+pub use self::lock::FreezeError;
+
+#[cfg(feature = "metrics")]
+pub use self::metrics::*;
+
+#[cfg(feature = "std")]
+pub use self::monitor::*;
+#[cfg(feature = "std")]
+pub use self::mvcc::*;
+#[cfg(feature = "named")]
+pub use self::named::*;
+#[cfg(feature = "async")]
+pub use self::notify::*;
+#[cfg(feature = "alloc")]
+pub use self::observed::*;
+#[cfg(feature = "alloc")]
+pub use self::pinned::*;
+#[cfg(all(feature = "async", feature = "alloc"))]
+pub use self::poll::*;
+#[cfg(feature = "std")]
+pub use self::queued::*;
+#[cfg(feature = "recursion-check")]
+pub use self::recursion::*;
+#[cfg(feature = "redis")]
+pub use self::redlock::*;
+#[cfg(feature = "std")]
+pub use self::rwlock::*;
+#[cfg(feature = "shared-memory")]
+pub use self::shm::*;
+#[cfg(feature = "arc-swap")]
+pub use self::swap::*;
+#[cfg(feature = "std-lock")]
+pub use self::thread_local::*;
+#[cfg(feature = "std")]
+pub use self::timed::*;
+#[cfg(feature = "tracing")]
+pub use self::traced::*;
+#[cfg(feature = "wait-graph")]
+pub use self::wait_graph::{DeadlockChecked, DeadlockGuard};
+#[cfg(all(feature = "event-listener", target_has_atomic = "64"))]
+pub use self::watch::*;
+#[cfg(all(feature = "tokio", feature = "std"))]
+pub use self::watch_backed::*;
+#[cfg(feature = "watchdog")]
+pub use self::watchdog::*;
+
+#[cfg(feature = "derive")]
+pub use locket_derive::Locket;
+
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "alloc")]
+    pub use alloc::sync::Arc;
+
+    // Exactly when `alias` is compiled and one of its `DefaultLock`
+    // variants applies.
+    #[cfg(all(
+        any(feature = "alloc", feature = "spin"),
+        any(
+            all(feature = "wasm", target_arch = "wasm32"),
+            feature = "parking_lot",
+            feature = "std-lock",
+            feature = "spin"
+        )
+    ))]
+    pub type StaticLock<T> = crate::alias::DefaultLock<T>;
+
+    // The lock of `#[derive(Locket)]` without a `lock` attribute. Fields are
+    // shared through an `Arc`, so it has to be `Sync`; `BorrowLock` stands in
+    // when no blocking backend is enabled.
+    #[cfg(all(
+        feature = "derive",
+        any(
+            all(feature = "wasm", target_arch = "wasm32"),
+            feature = "parking_lot",
+            feature = "std-lock",
+            feature = "spin"
+        )
+    ))]
+    pub type DeriveLock<T> = crate::alias::DefaultLock<T>;
+
+    #[cfg(all(
+        feature = "derive",
+        target_has_atomic = "ptr",
+        not(any(
+            all(feature = "wasm", target_arch = "wasm32"),
+            feature = "parking_lot",
+            feature = "std-lock",
+            feature = "spin"
+        ))
+    ))]
+    pub type DeriveLock<T> = crate::borrow::BorrowLock<T>;
+}
+
 #[cfg(feature = "parking_lot")]
 pub use parking_lot;
 
 #[cfg(feature = "spin")]
 pub use spin;
+
+#[cfg(feature = "lock_api")]
+pub use lock_api;
+
+#[cfg(feature = "once_cell")]
+pub use once_cell;
+
+#[cfg(feature = "atomic_refcell")]
+pub use atomic_refcell;
+
+#[cfg(feature = "event-listener")]
+pub use event_listener;
+
+#[cfg(feature = "arc-swap")]
+pub use arc_swap;
+
+#[cfg(feature = "bytemuck")]
+pub use bytemuck;
+
+#[cfg(feature = "wasm")]
+pub use wasm_sync;
+
+#[cfg(feature = "portable-atomic")]
+pub use portable_atomic_util;
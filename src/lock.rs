@@ -1,11 +1,179 @@
-use alloc::{rc::Rc, sync::Arc};
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, rc::Rc, sync::Arc};
+#[cfg(feature = "alloc")]
+use core::pin::Pin;
 
-use crate::{Downgrade, LockApi};
+#[cfg(feature = "alloc")]
+use crate::{
+    error::{LockError, Result},
+    inner::IntoInner,
+    types::TryUnwrap,
+    FairLock,
+};
+use crate::{
+    handle::{Halves, ReadLocket, WriteLocket},
+    leak::Leak,
+    mapped::MappedLocket,
+    Downgrade, LockApi,
+};
 
-pub trait Locket<T>: LockApi<T> + Downgrade + Clone {}
+pub trait Locket<T>: LockApi<T> + Downgrade + Clone {
+    /// Builds the value with `init`, for values which are expensive to create.
+    fn new_with(init: impl FnOnce() -> T) -> Self {
+        Self::new(init())
+    }
+
+    /// Builds the value with `init`, passing its error on.
+    fn try_new<E>(
+        init: impl FnOnce() -> core::result::Result<T, E>,
+    ) -> core::result::Result<Self, E> {
+        init().map(Self::new)
+    }
+
+    /// Narrows this locket to the part of `T` selected by `read` and `write`.
+    /// The returned handle shares the same lock.
+    fn map<U>(&self, read: fn(&T) -> &U, write: fn(&mut T) -> &mut U) -> MappedLocket<Self, T, U>
+    where
+        U: ?Sized,
+    {
+        MappedLocket::new(self.clone(), read, write)
+    }
+
+    fn reader(&self) -> ReadLocket<T, Self> {
+        ReadLocket::new(self.clone())
+    }
+
+    /// Turns this handle into the single writer, or gives it back if other
+    /// handles exist, since they could still write. That includes weak
+    /// handles, which could be upgraded.
+    fn into_writer(self) -> core::result::Result<WriteLocket<T, Self>, Self> {
+        match (self.strong_count(), self.weak_count()) {
+            (1, 0) => Ok(WriteLocket::new(self)),
+            _ => Err(self),
+        }
+    }
+
+    /// Splits the locket into its writing and reading halves, or gives it
+    /// back if other strong or weak handles exist. Use [`WriteLocket::reunite`] to
+    /// join them again.
+    fn split(self) -> core::result::Result<Halves<T, Self>, Self> {
+        let writer = self.into_writer()?;
+        let reader = writer.reader();
+        Ok((writer, reader))
+    }
+
+    /// Takes the lock out of its `Arc` or `Rc`, or gives the handle back if
+    /// other strong handles exist.
+    #[cfg(feature = "alloc")]
+    fn try_unwrap(self) -> core::result::Result<Self::Inner, Self>
+    where
+        Self: TryUnwrap,
+    {
+        TryUnwrap::try_unwrap(self)
+    }
+
+    /// Moves the value out of the lock, for example during shutdown. Fails
+    /// with [`LockError::Shared`] if other handles exist.
+    #[cfg(feature = "alloc")]
+    fn try_into_inner(self) -> Result<T>
+    where
+        Self: TryUnwrap,
+        Self::Inner: IntoInner<T>,
+    {
+        let inner = TryUnwrap::try_unwrap(self).map_err(|_| LockError::Shared)?;
+        inner.into_inner()
+    }
+
+    /// Gives up this handle for a `&'static` reference to the lock, for
+    /// lockets which live until the program exits. Copying the reference costs
+    /// nothing, and guards taken through
+    /// [`StaticLockApi`](crate::StaticLockApi) are `'static`. The lock is
+    /// never dropped.
+    fn leak(self) -> &'static Self::Inner
+    where
+        Self: Leak,
+    {
+        Leak::leak(self)
+    }
+
+    /// Starts a [`LocketBuilder`] for this locket type, which is usually
+    /// inferred from the binding the result is assigned to.
+    ///
+    /// [`LocketBuilder`]: crate::LocketBuilder
+    #[cfg(feature = "std")]
+    fn builder() -> crate::builder::LocketBuilder<Self> {
+        crate::builder::LocketBuilder::new()
+    }
+
+    /// Creates a locket whose value is pinned, see [`PinnedLocket`].
+    ///
+    /// [`PinnedLocket`]: crate::PinnedLocket
+    #[cfg(feature = "alloc")]
+    fn pin(inner: T) -> crate::pinned::PinnedLocket<Self>
+    where
+        Self: crate::pinned::StableHandle,
+    {
+        crate::pinned::PinnedLocket::new(inner)
+    }
+
+    /// Moves the value out of the lock into a plain `Arc` which can be read
+    /// without locking. Gives the handle back if other handles exist, like
+    /// `Arc::try_unwrap`.
+    #[cfg(feature = "alloc")]
+    fn freeze(self) -> core::result::Result<Arc<T>, FreezeError<Self>>
+    where
+        Self: TryUnwrap,
+        Self::Inner: IntoInner<T>,
+    {
+        let inner = TryUnwrap::try_unwrap(self).map_err(FreezeError::Shared)?;
+        let value = inner.into_inner().map_err(FreezeError::Lock)?;
+        Ok(Arc::new(value))
+    }
+
+    /// The inverse of [`freeze`](Locket::freeze). Gives `frozen` back if
+    /// other references to it exist.
+    #[cfg(feature = "alloc")]
+    fn thaw(frozen: Arc<T>) -> core::result::Result<Self, Arc<T>> {
+        Arc::try_unwrap(frozen).map(Self::new)
+    }
+}
+
+/// Returned by [`Locket::freeze`].
+#[cfg(feature = "alloc")]
+pub enum FreezeError<L> {
+    /// Other strong handles exist; the handle is given back.
+    Shared(L),
+    /// The value could not be taken out of the lock, e.g. because it is
+    /// poisoned.
+    Lock(LockError),
+}
+
+#[cfg(feature = "alloc")]
+impl<L> core::fmt::Debug for FreezeError<L> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FreezeError::Shared(_) => f.debug_tuple("Shared").finish_non_exhaustive(),
+            FreezeError::Lock(err) => f.debug_tuple("Lock").field(err).finish(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L> core::fmt::Display for FreezeError<L> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FreezeError::Shared(_) => write!(f, "{}", LockError::Shared),
+            FreezeError::Lock(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<L> std::error::Error for FreezeError<L> {}
 
 impl<T, L> Locket<T> for L where L: LockApi<T> + Downgrade + Clone {}
 
+#[cfg(feature = "alloc")]
 impl<L, T> LockApi<T> for Arc<L>
 where
     L: LockApi<T>,
@@ -23,11 +191,16 @@ where
         (**self).write()
     }
 
+    fn lock_id(&self) -> usize {
+        (**self).lock_id()
+    }
+
     fn new(inner: T) -> Self {
         Arc::new(L::new(inner))
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<L, T> LockApi<T> for Rc<L>
 where
     L: LockApi<T>,
@@ -45,7 +218,139 @@ where
         (**self).write()
     }
 
+    fn lock_id(&self) -> usize {
+        (**self).lock_id()
+    }
+
     fn new(inner: T) -> Self {
         Rc::new(L::new(inner))
     }
 }
+
+#[cfg(feature = "alloc")]
+impl<L, T> LockApi<T> for Box<L>
+where
+    L: LockApi<T>,
+    for<'a> L: 'a,
+{
+    type ReadGuard<'a> = L::ReadGuard<'a>;
+
+    type WriteGuard<'a> = L::WriteGuard<'a>;
+
+    fn read(&self) -> crate::error::Result<Self::ReadGuard<'_>> {
+        (**self).read()
+    }
+
+    fn write(&self) -> crate::error::Result<Self::WriteGuard<'_>> {
+        (**self).write()
+    }
+
+    fn lock_id(&self) -> usize {
+        (**self).lock_id()
+    }
+
+    fn new(inner: T) -> Self {
+        Box::new(L::new(inner))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L, T> LockApi<T> for Pin<Arc<L>>
+where
+    L: LockApi<T>,
+    for<'a> L: 'a,
+{
+    type ReadGuard<'a> = L::ReadGuard<'a>;
+
+    type WriteGuard<'a> = L::WriteGuard<'a>;
+
+    fn read(&self) -> crate::error::Result<Self::ReadGuard<'_>> {
+        (**self).read()
+    }
+
+    fn write(&self) -> crate::error::Result<Self::WriteGuard<'_>> {
+        (**self).write()
+    }
+
+    fn lock_id(&self) -> usize {
+        (**self).lock_id()
+    }
+
+    fn new(inner: T) -> Self {
+        Arc::pin(L::new(inner))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L> FairLock for Arc<L> where L: FairLock {}
+
+#[cfg(feature = "alloc")]
+impl<L> FairLock for Rc<L> where L: FairLock {}
+
+#[cfg(loom)]
+mod loom_impl {
+    use crate::{FairLock, LockApi};
+    use loom::sync::Arc;
+
+    impl<L, T> LockApi<T> for Arc<L>
+    where
+        L: LockApi<T>,
+        for<'a> L: 'a,
+    {
+        type ReadGuard<'a> = L::ReadGuard<'a>;
+
+        type WriteGuard<'a> = L::WriteGuard<'a>;
+
+        fn read(&self) -> crate::error::Result<Self::ReadGuard<'_>> {
+            (**self).read()
+        }
+
+        fn write(&self) -> crate::error::Result<Self::WriteGuard<'_>> {
+            (**self).write()
+        }
+
+        fn lock_id(&self) -> usize {
+            (**self).lock_id()
+        }
+
+        fn new(inner: T) -> Self {
+            Arc::new(L::new(inner))
+        }
+    }
+
+    impl<L> FairLock for Arc<L> where L: FairLock {}
+}
+
+#[cfg(feature = "portable-atomic")]
+mod portable_atomic_impl {
+    use crate::{FairLock, LockApi};
+    use portable_atomic_util::Arc;
+
+    impl<L, T> LockApi<T> for Arc<L>
+    where
+        L: LockApi<T>,
+        for<'a> L: 'a,
+    {
+        type ReadGuard<'a> = L::ReadGuard<'a>;
+
+        type WriteGuard<'a> = L::WriteGuard<'a>;
+
+        fn read(&self) -> crate::error::Result<Self::ReadGuard<'_>> {
+            (**self).read()
+        }
+
+        fn write(&self) -> crate::error::Result<Self::WriteGuard<'_>> {
+            (**self).write()
+        }
+
+        fn lock_id(&self) -> usize {
+            (**self).lock_id()
+        }
+
+        fn new(inner: T) -> Self {
+            Arc::new(L::new(inner))
+        }
+    }
+
+    impl<L> FairLock for Arc<L> where L: FairLock {}
+}
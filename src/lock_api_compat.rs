@@ -0,0 +1,92 @@
+use crate::raw::RawLockApi;
+
+/// Exposes a [`RawLockApi`] backend as a `lock_api` raw lock, so it can be
+/// plugged into crates generic over `lock_api::RawMutex` or
+/// `lock_api::RawRwLock`. As a mutex it only takes the exclusive lock.
+///
+/// ```ignore
+/// let lock: CompatRwLock<RawSpinRwLock, _> = CompatRwLock::new(vec![1]);
+/// lock.write().push(2);
+/// ```
+#[derive(Debug, Default)]
+#[repr(transparent)]
+pub struct LockApiCompat<R>(R);
+
+impl<R: RawLockApi> LockApiCompat<R> {
+    pub const fn new(raw: R) -> LockApiCompat<R> {
+        LockApiCompat(raw)
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> R {
+        self.0
+    }
+}
+
+pub type CompatMutex<R, T> = lock_api::Mutex<LockApiCompat<R>, T>;
+
+pub type CompatRwLock<R, T> = lock_api::RwLock<LockApiCompat<R>, T>;
+
+// Backends are not required to allow unlocking on another thread.
+unsafe impl<R: RawLockApi> lock_api::RawMutex for LockApiCompat<R> {
+    const INIT: Self = LockApiCompat(R::INIT);
+
+    type GuardMarker = lock_api::GuardNoSend;
+
+    fn lock(&self) {
+        self.0.lock()
+    }
+
+    fn try_lock(&self) -> bool {
+        self.0.try_lock()
+    }
+
+    unsafe fn unlock(&self) {
+        self.0.unlock()
+    }
+
+    fn is_locked(&self) -> bool {
+        self.0.is_locked()
+    }
+}
+
+unsafe impl<R: RawLockApi> lock_api::RawRwLock for LockApiCompat<R> {
+    const INIT: Self = LockApiCompat(R::INIT);
+
+    type GuardMarker = lock_api::GuardNoSend;
+
+    fn lock_shared(&self) {
+        self.0.lock_shared()
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        self.0.try_lock_shared()
+    }
+
+    unsafe fn unlock_shared(&self) {
+        self.0.unlock_shared()
+    }
+
+    fn lock_exclusive(&self) {
+        self.0.lock()
+    }
+
+    fn try_lock_exclusive(&self) -> bool {
+        self.0.try_lock()
+    }
+
+    unsafe fn unlock_exclusive(&self) {
+        self.0.unlock()
+    }
+
+    fn is_locked(&self) -> bool {
+        self.0.is_locked()
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        self.0.is_locked_exclusive()
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{
+    locking::LockApi,
+    try_lock::TryLockApi,
+    types::{Lockable, TryLockable},
+};
+
+// `Lockable` for the lock backends, locking for writing. A blanket impl over
+// `LockApi<T>` is not possible since `T` would be unconstrained. Backends which
+// cannot wait (RefCell, AtomicRefCell) or are poisoned panic instead.
+macro_rules! lockable {
+    ($($ty:ty $(: $($bound:path),+)?;)*) => {
+        $(
+            impl<T> Lockable for $ty
+            where
+                $(T: $($bound +)+,)?
+                for<'a> T: 'a,
+            {
+                type Guard<'a>
+                    = <$ty as LockApi<T>>::WriteGuard<'a>
+                where
+                    Self: 'a;
+
+                fn lock(&self) -> Self::Guard<'_> {
+                    LockApi::write(self).unwrap_or_else(|err| panic!("failed to lock: {err}"))
+                }
+            }
+
+            impl<T> TryLockable for $ty
+            where
+                $(T: $($bound +)+,)?
+                for<'a> T: 'a,
+            {
+                fn try_lock(&self) -> Option<Self::Guard<'_>> {
+                    TryLockApi::try_write(self).ok()
+                }
+            }
+        )*
+    };
+}
+
+lockable! {
+    core::cell::RefCell<T>;
+    crate::cell::CellLock<T>: Copy;
+}
+
+#[cfg(target_has_atomic = "ptr")]
+lockable! {
+    crate::double::DoubleBuffered<T>: Clone;
+    crate::seqlock::SeqLock<T>: Copy;
+    crate::atomic::AtomicLock<T>: crate::atomic::AtomicValue;
+    crate::borrow::BorrowLock<T>;
+}
+
+#[cfg(feature = "atomic_refcell")]
+lockable! {
+    atomic_refcell::AtomicRefCell<T>;
+}
+
+#[cfg(feature = "parking_lot")]
+lockable! {
+    parking_lot::Mutex<T>;
+    parking_lot::FairMutex<T>;
+    parking_lot::RwLock<T>;
+}
+
+#[cfg(feature = "spin")]
+lockable! {
+    spin::Mutex<T>;
+    spin::RwLock<T>;
+}
+
+#[cfg(feature = "std-lock")]
+lockable! {
+    std::sync::Mutex<T>;
+    std::sync::RwLock<T>;
+}
+
+#[cfg(feature = "std")]
+lockable! {
+    crate::cow::CowLocket<T>: Clone;
+    crate::intent::IntentLocket<T>;
+    crate::mvcc::MvccLocket<T>: Clone;
+    crate::rwlock::PolicyRwLock<T>;
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_feature = "atomics"))]
+lockable! {
+    wasm_sync::Mutex<T>;
+    wasm_sync::RwLock<T>;
+}
+
+#[cfg(feature = "epoch")]
+lockable! {
+    crate::epoch::EpochLocket<T>: Clone, Send;
+}
+
+#[cfg(feature = "arc-swap")]
+lockable! {
+    crate::swap::SwapLock<T>: Clone;
+}
+
+#[cfg(feature = "file-lock")]
+lockable! {
+    crate::file::FileLock<T>;
+}
+
+#[cfg(feature = "shared-memory")]
+lockable! {
+    crate::shm::ShmLock<T>: bytemuck::Pod;
+}
@@ -12,6 +12,31 @@ pub trait LockApiWriteGuard<'a, T>: LockApiReadGuard<'a, T> {
     fn get_mut(&mut self) -> &mut T;
 }
 
+/// Fairness controls for guards of backends which support them (parking_lot).
+pub trait LockApiFairGuard<'a, T>: LockApiReadGuard<'a, T> {
+    /// Releases the lock and hands it directly to a waiting thread, if any.
+    fn unlock_fair(self);
+
+    /// Temporarily yields the lock to a waiting thread, then reacquires it.
+    fn bump(&mut self);
+}
+
+/// A lock which hands out guards to a `T`.
+///
+/// Handles owning their lock (`Arc`, `Rc`, `Box`, `Pin<Arc<_>>`) forward to
+/// it, but `&L` deliberately does not implement this trait: `new` would have
+/// to leak the lock to return a reference to it. Generic code taking a lock by
+/// reference should bound `L: LockApi<T>` and take `&L` instead.
+///
+/// ```compile_fail
+/// use locket::LockApi;
+///
+/// fn write<L: LockApi<u32>>(lock: L) {
+///     drop(lock.write());
+/// }
+///
+/// write(&core::cell::RefCell::new(0));
+/// ```
 pub trait LockApi<T> {
     type ReadGuard<'a>: LockApiReadGuard<'a, T>
     where
@@ -25,8 +50,34 @@ pub trait LockApi<T> {
     fn write(&self) -> Result<Self::WriteGuard<'_>>;
 
     fn new(inner: T) -> Self;
+
+    /// Identifies the lock behind this handle, for acquiring several lockets
+    /// in a global order. Handles such as `Arc` forward to the lock they
+    /// point to, so every clone of a locket has the same id.
+    fn lock_id(&self) -> usize {
+        self as *const Self as *const () as usize
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessMode {
+    Read,
+    Write,
+}
+
+impl AccessMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AccessMode::Read => "read",
+            AccessMode::Write => "write",
+        }
+    }
 }
 
+/// Marker for locks which hand themselves to waiters in a fair (FIFO) order,
+/// so generic code can require fairness with a `L: LockApi<T> + FairLock` bound.
+pub trait FairLock {}
+
 impl<'a, T> LockApiReadGuard<'a, T> for Ref<'a, T> {
     fn get(&self) -> &T {
         self.deref()
@@ -54,11 +105,11 @@ where
     type WriteGuard<'a> = RefMut<'a, T>;
 
     fn read(&self) -> Result<Self::ReadGuard<'_>> {
-        self.try_borrow().map_err(|_| LockError)
+        self.try_borrow().map_err(|_| LockError::WouldBlock)
     }
 
     fn write(&self) -> Result<Self::WriteGuard<'_>> {
-        self.try_borrow_mut().map_err(|_| LockError)
+        self.try_borrow_mut().map_err(|_| LockError::WouldBlock)
     }
 
     fn new(inner: T) -> Self {
@@ -66,11 +117,61 @@ where
     }
 }
 
+#[cfg(feature = "atomic_refcell")]
+mod atomic_refcell_impl {
+    use super::{LockApi, LockApiReadGuard, LockApiWriteGuard};
+    use crate::error::{LockError, Result};
+    use atomic_refcell::{AtomicRef, AtomicRefCell, AtomicRefMut};
+    use core::ops::{Deref, DerefMut};
+
+    impl<'a, T> LockApiReadGuard<'a, T> for AtomicRef<'a, T> {
+        fn get(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<'a, T> LockApiReadGuard<'a, T> for AtomicRefMut<'a, T> {
+        fn get(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<'a, T> LockApiWriteGuard<'a, T> for AtomicRefMut<'a, T> {
+        fn get_mut(&mut self) -> &mut T {
+            self.deref_mut()
+        }
+    }
+
+    // Like `RefCell`, a conflicting borrow is reported instead of waited for.
+    impl<T> LockApi<T> for AtomicRefCell<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = AtomicRef<'a, T>;
+
+        type WriteGuard<'a> = AtomicRefMut<'a, T>;
+
+        fn read(&self) -> Result<Self::ReadGuard<'_>> {
+            self.try_borrow().map_err(|_| LockError::WouldBlock)
+        }
+
+        fn write(&self) -> Result<Self::WriteGuard<'_>> {
+            self.try_borrow_mut().map_err(|_| LockError::WouldBlock)
+        }
+
+        fn new(inner: T) -> Self {
+            AtomicRefCell::new(inner)
+        }
+    }
+}
+
 #[cfg(feature = "parking_lot")]
 mod parking_lot_impl {
     // Mutex
     use super::*;
-    use parking_lot::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+    use parking_lot::{
+        FairMutex, FairMutexGuard, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard,
+    };
 
     impl<'a, T> LockApiReadGuard<'a, T> for MutexGuard<'a, T> {
         fn get(&self) -> &T {
@@ -78,6 +179,16 @@ mod parking_lot_impl {
         }
     }
 
+    impl<'a, T> LockApiFairGuard<'a, T> for MutexGuard<'a, T> {
+        fn unlock_fair(self) {
+            MutexGuard::unlock_fair(self)
+        }
+
+        fn bump(&mut self) {
+            MutexGuard::bump(self)
+        }
+    }
+
     impl<'a, T> LockApiWriteGuard<'a, T> for MutexGuard<'a, T> {
         fn get_mut(&mut self) -> &mut T {
             self.deref_mut()
@@ -93,10 +204,14 @@ mod parking_lot_impl {
         type WriteGuard<'a> = MutexGuard<'a, T>;
 
         fn read(&self) -> Result<Self::ReadGuard<'_>> {
+            #[cfg(feature = "deadlock_detection")]
+            let _waiting = crate::deadlock::waiting(self.lock_id(), None);
             Ok(self.lock())
         }
 
         fn write(&self) -> Result<Self::WriteGuard<'_>> {
+            #[cfg(feature = "deadlock_detection")]
+            let _waiting = crate::deadlock::waiting(self.lock_id(), None);
             Ok(self.lock())
         }
 
@@ -105,6 +220,57 @@ mod parking_lot_impl {
         }
     }
 
+    // FairMutex
+
+    impl<'a, T> LockApiReadGuard<'a, T> for FairMutexGuard<'a, T> {
+        fn get(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<'a, T> LockApiFairGuard<'a, T> for FairMutexGuard<'a, T> {
+        fn unlock_fair(self) {
+            FairMutexGuard::unlock_fair(self)
+        }
+
+        fn bump(&mut self) {
+            FairMutexGuard::bump(self)
+        }
+    }
+
+    impl<'a, T> LockApiWriteGuard<'a, T> for FairMutexGuard<'a, T> {
+        fn get_mut(&mut self) -> &mut T {
+            self.deref_mut()
+        }
+    }
+
+    impl<T> LockApi<T> for FairMutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = FairMutexGuard<'a, T>;
+
+        type WriteGuard<'a> = FairMutexGuard<'a, T>;
+
+        fn read(&self) -> Result<Self::ReadGuard<'_>> {
+            #[cfg(feature = "deadlock_detection")]
+            let _waiting = crate::deadlock::waiting(self.lock_id(), None);
+            Ok(self.lock())
+        }
+
+        fn write(&self) -> Result<Self::WriteGuard<'_>> {
+            #[cfg(feature = "deadlock_detection")]
+            let _waiting = crate::deadlock::waiting(self.lock_id(), None);
+            Ok(self.lock())
+        }
+
+        fn new(inner: T) -> Self {
+            FairMutex::new(inner)
+        }
+    }
+
+    impl<T> FairLock for FairMutex<T> {}
+
     // RwLock
 
     impl<'a, T> LockApiReadGuard<'a, T> for RwLockReadGuard<'a, T> {
@@ -113,12 +279,32 @@ mod parking_lot_impl {
         }
     }
 
+    impl<'a, T> LockApiFairGuard<'a, T> for RwLockReadGuard<'a, T> {
+        fn unlock_fair(self) {
+            RwLockReadGuard::unlock_fair(self)
+        }
+
+        fn bump(&mut self) {
+            RwLockReadGuard::bump(self)
+        }
+    }
+
     impl<'a, T> LockApiReadGuard<'a, T> for RwLockWriteGuard<'a, T> {
         fn get(&self) -> &T {
             self.deref()
         }
     }
 
+    impl<'a, T> LockApiFairGuard<'a, T> for RwLockWriteGuard<'a, T> {
+        fn unlock_fair(self) {
+            RwLockWriteGuard::unlock_fair(self)
+        }
+
+        fn bump(&mut self) {
+            RwLockWriteGuard::bump(self)
+        }
+    }
+
     impl<'a, T> LockApiWriteGuard<'a, T> for RwLockWriteGuard<'a, T> {
         fn get_mut(&mut self) -> &mut T {
             self.deref_mut()
@@ -134,10 +320,14 @@ mod parking_lot_impl {
         type WriteGuard<'a> = RwLockWriteGuard<'a, T>;
 
         fn read(&self) -> Result<Self::ReadGuard<'_>> {
+            #[cfg(feature = "deadlock_detection")]
+            let _waiting = crate::deadlock::waiting(self.lock_id(), None);
             Ok((*self).read())
         }
 
         fn write(&self) -> Result<Self::WriteGuard<'_>> {
+            #[cfg(feature = "deadlock_detection")]
+            let _waiting = crate::deadlock::waiting(self.lock_id(), None);
             Ok((*self).write())
         }
 
@@ -151,7 +341,13 @@ mod parking_lot_impl {
 mod spin_impl {
     // Mutex
     use super::*;
-    use spin::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+    // The crate root aliases pin the relax strategy to the default one.
+    use spin::{
+        mutex::Mutex,
+        relax::RelaxStrategy,
+        rwlock::{RwLock, RwLockWriteGuard},
+        MutexGuard, RwLockReadGuard,
+    };
 
     impl<'a, T> LockApiReadGuard<'a, T> for MutexGuard<'a, T> {
         fn get(&self) -> &T {
@@ -165,9 +361,11 @@ mod spin_impl {
         }
     }
 
-    impl<T> LockApi<T> for Mutex<T>
+    impl<T, R> LockApi<T> for Mutex<T, R>
     where
+        R: RelaxStrategy,
         for<'a> T: 'a,
+        for<'a> R: 'a,
     {
         type ReadGuard<'a> = MutexGuard<'a, T>;
 
@@ -194,25 +392,27 @@ mod spin_impl {
         }
     }
 
-    impl<'a, T> LockApiReadGuard<'a, T> for RwLockWriteGuard<'a, T> {
+    impl<'a, T, R> LockApiReadGuard<'a, T> for RwLockWriteGuard<'a, T, R> {
         fn get(&self) -> &T {
             self.deref()
         }
     }
 
-    impl<'a, T> LockApiWriteGuard<'a, T> for RwLockWriteGuard<'a, T> {
+    impl<'a, T, R> LockApiWriteGuard<'a, T> for RwLockWriteGuard<'a, T, R> {
         fn get_mut(&mut self) -> &mut T {
             self.deref_mut()
         }
     }
 
-    impl<T> LockApi<T> for RwLock<T>
+    impl<T, R> LockApi<T> for RwLock<T, R>
     where
+        R: RelaxStrategy,
         for<'a> T: 'a,
+        for<'a> R: 'a,
     {
         type ReadGuard<'a> = RwLockReadGuard<'a, T>;
 
-        type WriteGuard<'a> = RwLockWriteGuard<'a, T>;
+        type WriteGuard<'a> = RwLockWriteGuard<'a, T, R>;
 
         fn read(&self) -> Result<Self::ReadGuard<'_>> {
             Ok((*self).read())
@@ -255,11 +455,223 @@ mod std_impl {
         type WriteGuard<'a> = MutexGuard<'a, T>;
 
         fn read(&self) -> Result<Self::ReadGuard<'_>> {
-            self.lock().map_err(|_| LockError)
+            self.lock().map_err(|_| LockError::Poisoned)
+        }
+
+        fn write(&self) -> Result<Self::WriteGuard<'_>> {
+            self.lock().map_err(|_| LockError::Poisoned)
+        }
+
+        fn new(inner: T) -> Self {
+            Mutex::new(inner)
+        }
+    }
+
+    // RwLock
+
+    impl<'a, T> LockApiReadGuard<'a, T> for RwLockReadGuard<'a, T> {
+        fn get(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<'a, T> LockApiReadGuard<'a, T> for RwLockWriteGuard<'a, T> {
+        fn get(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<'a, T> LockApiWriteGuard<'a, T> for RwLockWriteGuard<'a, T> {
+        fn get_mut(&mut self) -> &mut T {
+            self.deref_mut()
+        }
+    }
+
+    impl<T> LockApi<T> for RwLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = RwLockReadGuard<'a, T>;
+
+        type WriteGuard<'a> = RwLockWriteGuard<'a, T>;
+
+        fn read(&self) -> Result<Self::ReadGuard<'_>> {
+            (*self).read().map_err(|_| LockError::Poisoned)
+        }
+
+        fn write(&self) -> Result<Self::WriteGuard<'_>> {
+            (*self).write().map_err(|_| LockError::Poisoned)
+        }
+
+        fn new(inner: T) -> Self {
+            RwLock::new(inner)
+        }
+    }
+}
+
+// On other targets these are re-exports of std's locks.
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_feature = "atomics"))]
+mod wasm_impl {
+    use super::*;
+    use std::sync::{MutexGuard, RwLockReadGuard, RwLockWriteGuard};
+    use wasm_sync::{Mutex, RwLock};
+
+    impl<T> LockApi<T> for Mutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = MutexGuard<'a, T>;
+
+        type WriteGuard<'a> = MutexGuard<'a, T>;
+
+        fn read(&self) -> Result<Self::ReadGuard<'_>> {
+            self.lock().map_err(|_| LockError::Poisoned)
+        }
+
+        fn write(&self) -> Result<Self::WriteGuard<'_>> {
+            self.lock().map_err(|_| LockError::Poisoned)
+        }
+
+        fn new(inner: T) -> Self {
+            Mutex::new(inner)
+        }
+    }
+
+    impl<T> LockApi<T> for RwLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = RwLockReadGuard<'a, T>;
+
+        type WriteGuard<'a> = RwLockWriteGuard<'a, T>;
+
+        fn read(&self) -> Result<Self::ReadGuard<'_>> {
+            (*self).read().map_err(|_| LockError::Poisoned)
+        }
+
+        fn write(&self) -> Result<Self::WriteGuard<'_>> {
+            (*self).write().map_err(|_| LockError::Poisoned)
+        }
+
+        fn new(inner: T) -> Self {
+            RwLock::new(inner)
+        }
+    }
+}
+
+#[cfg(all(loom, feature = "std-lock"))]
+mod loom_impl {
+    // Mutex
+    use super::*;
+    use loom::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+    impl<'a, T> LockApiReadGuard<'a, T> for MutexGuard<'a, T> {
+        fn get(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<'a, T> LockApiWriteGuard<'a, T> for MutexGuard<'a, T> {
+        fn get_mut(&mut self) -> &mut T {
+            self.deref_mut()
+        }
+    }
+
+    impl<T> LockApi<T> for Mutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = MutexGuard<'a, T>;
+
+        type WriteGuard<'a> = MutexGuard<'a, T>;
+
+        fn read(&self) -> Result<Self::ReadGuard<'_>> {
+            self.lock().map_err(|_| LockError::Poisoned)
+        }
+
+        fn write(&self) -> Result<Self::WriteGuard<'_>> {
+            self.lock().map_err(|_| LockError::Poisoned)
+        }
+
+        fn new(inner: T) -> Self {
+            Mutex::new(inner)
+        }
+    }
+
+    // RwLock
+
+    impl<'a, T> LockApiReadGuard<'a, T> for RwLockReadGuard<'a, T> {
+        fn get(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<'a, T> LockApiReadGuard<'a, T> for RwLockWriteGuard<'a, T> {
+        fn get(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<'a, T> LockApiWriteGuard<'a, T> for RwLockWriteGuard<'a, T> {
+        fn get_mut(&mut self) -> &mut T {
+            self.deref_mut()
+        }
+    }
+
+    impl<T> LockApi<T> for RwLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = RwLockReadGuard<'a, T>;
+
+        type WriteGuard<'a> = RwLockWriteGuard<'a, T>;
+
+        fn read(&self) -> Result<Self::ReadGuard<'_>> {
+            (*self).read().map_err(|_| LockError::Poisoned)
+        }
+
+        fn write(&self) -> Result<Self::WriteGuard<'_>> {
+            (*self).write().map_err(|_| LockError::Poisoned)
+        }
+
+        fn new(inner: T) -> Self {
+            RwLock::new(inner)
+        }
+    }
+}
+
+#[cfg(feature = "shuttle")]
+mod shuttle_impl {
+    // Mutex
+    use super::*;
+    use shuttle::sync::{Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+    impl<'a, T> LockApiReadGuard<'a, T> for MutexGuard<'a, T> {
+        fn get(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<'a, T> LockApiWriteGuard<'a, T> for MutexGuard<'a, T> {
+        fn get_mut(&mut self) -> &mut T {
+            self.deref_mut()
+        }
+    }
+
+    impl<T> LockApi<T> for Mutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = MutexGuard<'a, T>;
+
+        type WriteGuard<'a> = MutexGuard<'a, T>;
+
+        fn read(&self) -> Result<Self::ReadGuard<'_>> {
+            self.lock().map_err(|_| LockError::Poisoned)
         }
 
         fn write(&self) -> Result<Self::WriteGuard<'_>> {
-            self.lock().map_err(|_| LockError)
+            self.lock().map_err(|_| LockError::Poisoned)
         }
 
         fn new(inner: T) -> Self {
@@ -296,11 +708,11 @@ mod std_impl {
         type WriteGuard<'a> = RwLockWriteGuard<'a, T>;
 
         fn read(&self) -> Result<Self::ReadGuard<'_>> {
-            (*self).read().map_err(|_| LockError)
+            (*self).read().map_err(|_| LockError::Poisoned)
         }
 
         fn write(&self) -> Result<Self::WriteGuard<'_>> {
-            (*self).write().map_err(|_| LockError)
+            (*self).write().map_err(|_| LockError::Poisoned)
         }
 
         fn new(inner: T) -> Self {
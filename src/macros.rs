@@ -0,0 +1,22 @@
+/// Declares statics holding a [`LazyLocket`](crate::LazyLocket), initialized on
+/// first access: `static CONFIG: Config = Config::default();`.
+///
+/// The lock can be named with `static CONFIG: Config as Mutex<Config> = ...;`.
+/// Otherwise the best available backend is used: parking_lot's `RwLock`, then
+/// std's `RwLock`, then spin's `RwLock`. On wasm32 with the `wasm` feature,
+/// [`wasm::RwLock`](crate::wasm::RwLock) is used instead.
+#[macro_export]
+macro_rules! static_locket {
+    () => {};
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $ty:ty as $lock:ty = $init:expr; $($rest:tt)*) => {
+        $(#[$attr])*
+        $vis static $name: $crate::LazyLocket<$ty, $lock> = $crate::LazyLocket::new(|| $init);
+        $crate::static_locket!($($rest)*);
+    };
+    ($(#[$attr:meta])* $vis:vis static $name:ident: $ty:ty = $init:expr; $($rest:tt)*) => {
+        $crate::static_locket!(
+            $(#[$attr])* $vis static $name: $ty as $crate::__private::StaticLock<$ty> = $init;
+            $($rest)*
+        );
+    };
+}
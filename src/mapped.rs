@@ -0,0 +1,142 @@
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+};
+
+/// A handle sharing the lock of a parent locket, exposing only the part of the
+/// value selected by its projections. See [`Locket::map`](crate::Locket::map).
+pub struct MappedLocket<L, T, U: ?Sized> {
+    inner: L,
+    read: fn(&T) -> &U,
+    write: fn(&mut T) -> &mut U,
+}
+
+impl<L, T, U: ?Sized> MappedLocket<L, T, U> {
+    pub fn new(inner: L, read: fn(&T) -> &U, write: fn(&mut T) -> &mut U) -> MappedLocket<L, T, U> {
+        MappedLocket { inner, read, write }
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+}
+
+impl<L, T, U> MappedLocket<L, T, U>
+where
+    L: LockApi<T>,
+    U: ?Sized,
+{
+    pub fn read(&self) -> Result<MappedReadGuard<'_, L::ReadGuard<'_>, T, U>> {
+        Ok(MappedReadGuard {
+            guard: self.inner.read()?,
+            read: self.read,
+            _lifetime: PhantomData,
+        })
+    }
+
+    pub fn write(&self) -> Result<MappedWriteGuard<'_, L::WriteGuard<'_>, T, U>> {
+        Ok(MappedWriteGuard {
+            guard: self.inner.write()?,
+            read: self.read,
+            write: self.write,
+            _lifetime: PhantomData,
+        })
+    }
+}
+
+impl<L, T, U> Clone for MappedLocket<L, T, U>
+where
+    L: Clone,
+    U: ?Sized,
+{
+    fn clone(&self) -> Self {
+        MappedLocket {
+            inner: self.inner.clone(),
+            read: self.read,
+            write: self.write,
+        }
+    }
+}
+
+pub struct MappedReadGuard<'a, G, T, U: ?Sized> {
+    guard: G,
+    read: fn(&T) -> &U,
+    _lifetime: PhantomData<&'a ()>,
+}
+
+impl<'a, G, T, U> Deref for MappedReadGuard<'a, G, T, U>
+where
+    G: LockApiReadGuard<'a, T>,
+    U: ?Sized,
+{
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        (self.read)(self.guard.get())
+    }
+}
+
+impl<'a, G, T, U> LockApiReadGuard<'a, U> for MappedReadGuard<'a, G, T, U>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &U {
+        self.deref()
+    }
+}
+
+pub struct MappedWriteGuard<'a, G, T, U: ?Sized> {
+    guard: G,
+    read: fn(&T) -> &U,
+    write: fn(&mut T) -> &mut U,
+    _lifetime: PhantomData<&'a ()>,
+}
+
+impl<'a, G, T, U> Deref for MappedWriteGuard<'a, G, T, U>
+where
+    G: LockApiWriteGuard<'a, T>,
+    U: ?Sized,
+{
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        (self.read)(self.guard.get())
+    }
+}
+
+impl<'a, G, T, U> DerefMut for MappedWriteGuard<'a, G, T, U>
+where
+    G: LockApiWriteGuard<'a, T>,
+    U: ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut U {
+        (self.write)(self.guard.get_mut())
+    }
+}
+
+impl<'a, G, T, U> LockApiReadGuard<'a, U> for MappedWriteGuard<'a, G, T, U>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get(&self) -> &U {
+        self.deref()
+    }
+}
+
+impl<'a, G, T, U> LockApiWriteGuard<'a, U> for MappedWriteGuard<'a, G, T, U>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut U {
+        self.deref_mut()
+    }
+}
@@ -0,0 +1,471 @@
+use core::{
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering},
+    time::Duration,
+};
+use std::time::Instant;
+
+#[cfg(feature = "fairness")]
+use alloc::sync::Arc;
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    timed::TimedLockApi,
+    try_lock::TryLockApi,
+};
+
+const BUCKETS: usize = 32;
+
+// Longer than taking a free lock, so an acquisition which waited this long
+// was blocked even if the counters saw no conflict.
+const CONTENDED_WAIT: Duration = Duration::from_micros(10);
+
+#[cfg(feature = "fairness")]
+static NEXT_RECORDER: AtomicU64 = AtomicU64::new(1);
+
+#[cfg(feature = "fairness")]
+std::thread_local! {
+    // This thread's wait histogram for each recorder it used, so recording a
+    // wait does not take the recorder's lock.
+    static WAITS: core::cell::RefCell<alloc::vec::Vec<(u64, Arc<Histogram>)>> =
+        const { core::cell::RefCell::new(alloc::vec::Vec::new()) };
+}
+
+/// Histogram with power-of-two buckets over microseconds: bucket `0` counts
+/// durations below 1µs and bucket `i` durations in `[2^(i-1), 2^i)` µs.
+struct Histogram {
+    buckets: [AtomicU64; BUCKETS],
+    sum: AtomicU64,
+    max: AtomicU64,
+}
+
+impl Histogram {
+    const fn new() -> Histogram {
+        Histogram {
+            buckets: [const { AtomicU64::new(0) }; BUCKETS],
+            sum: AtomicU64::new(0),
+            max: AtomicU64::new(0),
+        }
+    }
+
+    fn record(&self, duration: Duration) {
+        let micros: u64 = duration.as_micros().try_into().unwrap_or(u64::MAX);
+        let bucket = ((u64::BITS - micros.leading_zeros()) as usize).min(BUCKETS - 1);
+        self.buckets[bucket].fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(micros, Ordering::Relaxed);
+        self.max.fetch_max(micros, Ordering::Relaxed);
+    }
+
+    fn snapshot(&self) -> HistogramSnapshot {
+        HistogramSnapshot {
+            buckets: core::array::from_fn(|i| self.buckets[i].load(Ordering::Relaxed)),
+            sum_us: self.sum.load(Ordering::Relaxed),
+            max_us: self.max.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistogramSnapshot {
+    pub buckets: [u64; BUCKETS],
+    pub sum_us: u64,
+    pub max_us: u64,
+}
+
+impl HistogramSnapshot {
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+
+    pub fn mean(&self) -> Duration {
+        match self.count() {
+            0 => Duration::ZERO,
+            count => Duration::from_micros(self.sum_us / count),
+        }
+    }
+
+    pub fn max(&self) -> Duration {
+        Duration::from_micros(self.max_us)
+    }
+
+    /// Upper bound of the bucket containing the `quantile` (0.0 - 1.0) sample.
+    pub fn quantile(&self, quantile: f64) -> Duration {
+        let target = (self.count() as f64 * quantile.clamp(0.0, 1.0)).ceil() as u64;
+        let mut seen = 0;
+        for (i, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= target && *count > 0 {
+                return Duration::from_micros(1u64 << i);
+            }
+        }
+        self.max()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetricsSnapshot {
+    pub read_acquisitions: u64,
+    pub write_acquisitions: u64,
+    /// Acquisitions which started while a conflicting guard was held or
+    /// requested, or which waited long enough to have been blocked. Only the
+    /// latter catches readers of backends whose reads are exclusive, such as
+    /// mutexes.
+    pub contended_acquisitions: u64,
+    pub wait: HistogramSnapshot,
+    pub hold: HistogramSnapshot,
+}
+
+impl MetricsSnapshot {
+    pub fn acquisitions(&self) -> u64 {
+        self.read_acquisitions + self.write_acquisitions
+    }
+
+    pub fn contention_ratio(&self) -> f64 {
+        match self.acquisitions() {
+            0 => 0.0,
+            total => self.contended_acquisitions as f64 / total as f64,
+        }
+    }
+}
+
+/// The wait times of one thread.
+#[cfg(feature = "fairness")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WaiterSnapshot {
+    pub thread_id: std::thread::ThreadId,
+    pub wait: HistogramSnapshot,
+}
+
+/// Wait times per waiting thread, for spotting starved waiters. Every thread
+/// which ever acquired the lock keeps an entry.
+#[cfg(feature = "fairness")]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FairnessSnapshot {
+    pub waiters: alloc::vec::Vec<WaiterSnapshot>,
+}
+
+#[cfg(feature = "fairness")]
+impl FairnessSnapshot {
+    /// Jain's fairness index over the mean wait of each waiter: `1.0` when all
+    /// of them waited equally long, down to `1/n` when a single one did all
+    /// the waiting.
+    pub fn index(&self) -> f64 {
+        let means = self.waiters.iter().filter_map(|waiter| {
+            let count = waiter.wait.count();
+            (count > 0).then(|| waiter.wait.sum_us as f64 / count as f64)
+        });
+        let (n, sum, squares) = means.fold((0.0, 0.0, 0.0), |(n, sum, squares), mean| {
+            (n + 1.0, sum + mean, squares + mean * mean)
+        });
+        match squares > 0.0 {
+            true => sum * sum / (n * squares),
+            false => 1.0,
+        }
+    }
+}
+
+// The counters behind `Metrics`, shared with `Named` lockets so the registry can
+// report them.
+pub(crate) struct Recorder {
+    readers: AtomicUsize,
+    writers: AtomicUsize,
+    read_acquisitions: AtomicU64,
+    write_acquisitions: AtomicU64,
+    contended: AtomicU64,
+    wait: Histogram,
+    hold: Histogram,
+    // Assigned on first use, since `new` is const. 0 while unassigned.
+    #[cfg(feature = "fairness")]
+    id: AtomicU64,
+    #[cfg(feature = "fairness")]
+    waiters: std::sync::Mutex<alloc::vec::Vec<(std::thread::ThreadId, Arc<Histogram>)>>,
+}
+
+impl Recorder {
+    pub(crate) const fn new() -> Recorder {
+        Recorder {
+            readers: AtomicUsize::new(0),
+            writers: AtomicUsize::new(0),
+            read_acquisitions: AtomicU64::new(0),
+            write_acquisitions: AtomicU64::new(0),
+            contended: AtomicU64::new(0),
+            wait: Histogram::new(),
+            hold: Histogram::new(),
+            #[cfg(feature = "fairness")]
+            id: AtomicU64::new(0),
+            #[cfg(feature = "fairness")]
+            waiters: std::sync::Mutex::new(alloc::vec::Vec::new()),
+        }
+    }
+
+    #[cfg(feature = "fairness")]
+    fn id(&self) -> u64 {
+        match self.id.load(Ordering::Relaxed) {
+            0 => {
+                let id = NEXT_RECORDER.fetch_add(1, Ordering::Relaxed);
+                match self
+                    .id
+                    .compare_exchange(0, id, Ordering::Relaxed, Ordering::Relaxed)
+                {
+                    Ok(_) => id,
+                    Err(id) => id,
+                }
+            }
+            id => id,
+        }
+    }
+
+    // Only a thread's first wait on this recorder takes the lock, to share its
+    // histogram with `fairness`.
+    #[cfg(feature = "fairness")]
+    fn record_waiter(&self, wait: Duration) {
+        let id = self.id();
+        // Waits during thread teardown, after the thread-local is gone, are
+        // not recorded per thread.
+        let _ = WAITS.try_with(|waits| {
+            let mut waits = waits.borrow_mut();
+            if let Some((_, histogram)) = waits.iter().find(|(recorder, _)| *recorder == id) {
+                histogram.record(wait);
+                return;
+            }
+            // Histograms only referenced from here belong to dropped recorders.
+            waits.retain(|(_, histogram)| Arc::strong_count(histogram) > 1);
+            let histogram = Arc::new(Histogram::new());
+            histogram.record(wait);
+            self.waiters
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .push((std::thread::current().id(), histogram.clone()));
+            waits.push((id, histogram));
+        });
+    }
+
+    #[cfg(feature = "fairness")]
+    pub(crate) fn fairness(&self) -> FairnessSnapshot {
+        let waiters = self
+            .waiters
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        FairnessSnapshot {
+            waiters: waiters
+                .iter()
+                .map(|(thread_id, histogram)| WaiterSnapshot {
+                    thread_id: *thread_id,
+                    wait: histogram.snapshot(),
+                })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            read_acquisitions: self.read_acquisitions.load(Ordering::Relaxed),
+            write_acquisitions: self.write_acquisitions.load(Ordering::Relaxed),
+            contended_acquisitions: self.contended.load(Ordering::Relaxed),
+            wait: self.wait.snapshot(),
+            hold: self.hold.snapshot(),
+        }
+    }
+
+    fn counters(&self, write: bool) -> (&AtomicUsize, &AtomicUsize) {
+        match write {
+            true => (&self.writers, &self.readers),
+            false => (&self.readers, &self.writers),
+        }
+    }
+
+    /// Records an acquisition attempt around `lock`, returning the time the
+    /// guard was acquired.
+    pub(crate) fn acquire<G>(
+        &self,
+        write: bool,
+        lock: impl FnOnce() -> Result<G>,
+    ) -> Result<(G, Instant)> {
+        let (own, other) = self.counters(write);
+        let before = own.fetch_add(1, Ordering::Relaxed);
+        let contended = other.load(Ordering::Relaxed) > 0 || (write && before > 0);
+
+        let start = crate::clock::now();
+        let guard = match lock() {
+            Ok(guard) => guard,
+            Err(err) => {
+                own.fetch_sub(1, Ordering::Relaxed);
+                return Err(err);
+            }
+        };
+        let acquired = crate::clock::now();
+        let wait = acquired - start;
+
+        self.wait.record(wait);
+        #[cfg(feature = "fairness")]
+        self.record_waiter(wait);
+        if contended || wait >= CONTENDED_WAIT {
+            self.contended.fetch_add(1, Ordering::Relaxed);
+        }
+        match write {
+            true => &self.write_acquisitions,
+            false => &self.read_acquisitions,
+        }
+        .fetch_add(1, Ordering::Relaxed);
+        Ok((guard, acquired))
+    }
+
+    pub(crate) fn release(&self, write: bool, acquired: Instant) {
+        self.hold
+            .record(crate::clock::now().saturating_duration_since(acquired));
+        self.counters(write).0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Records acquisition counts, contention and wait/hold time histograms for the
+/// inner lock.
+pub struct Metrics<L> {
+    inner: L,
+    recorder: Recorder,
+}
+
+impl<L> Metrics<L> {
+    pub const fn wrap(inner: L) -> Metrics<L> {
+        Metrics {
+            inner,
+            recorder: Recorder::new(),
+        }
+    }
+
+    pub fn snapshot(&self) -> MetricsSnapshot {
+        self.recorder.snapshot()
+    }
+
+    #[cfg(feature = "fairness")]
+    pub fn fairness(&self) -> FairnessSnapshot {
+        self.recorder.fairness()
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    fn acquire<'a, G>(
+        &'a self,
+        write: bool,
+        lock: impl FnOnce(&'a L) -> Result<G>,
+    ) -> Result<MetricsGuard<'a, L, G>> {
+        let (guard, acquired) = self.recorder.acquire(write, || lock(&self.inner))?;
+        Ok(MetricsGuard {
+            guard: Some(guard),
+            metrics: self,
+            write,
+            acquired,
+        })
+    }
+}
+
+impl<L, T> LockApi<T> for Metrics<L>
+where
+    L: LockApi<T>,
+{
+    type ReadGuard<'a>
+        = MetricsGuard<'a, L, L::ReadGuard<'a>>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = MetricsGuard<'a, L, L::WriteGuard<'a>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.acquire(false, |lock| lock.read())
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.acquire(true, |lock| lock.write())
+    }
+
+    fn new(inner: T) -> Self {
+        Metrics::wrap(L::new(inner))
+    }
+}
+
+impl<L, T> TryLockApi<T> for Metrics<L>
+where
+    L: TryLockApi<T>,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.acquire(false, |lock| lock.try_read())
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.acquire(true, |lock| lock.try_write())
+    }
+}
+
+impl<L, T> TimedLockApi<T> for Metrics<L>
+where
+    L: TimedLockApi<T>,
+{
+    fn read_until(&self, deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+        self.acquire(false, |lock| lock.read_until(deadline))
+    }
+
+    fn write_until(&self, deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+        self.acquire(true, |lock| lock.write_until(deadline))
+    }
+}
+
+pub struct MetricsGuard<'a, L, G> {
+    guard: Option<G>,
+    metrics: &'a Metrics<L>,
+    write: bool,
+    acquired: Instant,
+}
+
+impl<L, G> Drop for MetricsGuard<'_, L, G> {
+    fn drop(&mut self) {
+        drop(self.guard.take());
+        self.metrics.recorder.release(self.write, self.acquired);
+    }
+}
+
+impl<L, G> Deref for MetricsGuard<'_, L, G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard.as_ref().unwrap()
+    }
+}
+
+impl<L, G> DerefMut for MetricsGuard<'_, L, G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard.as_mut().unwrap()
+    }
+}
+
+impl<'a, T, L, G> LockApiReadGuard<'a, T> for MetricsGuard<'a, L, G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.as_ref().unwrap().get()
+    }
+}
+
+impl<'a, T, L, G> LockApiWriteGuard<'a, T> for MetricsGuard<'a, L, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.as_mut().unwrap().get_mut()
+    }
+}
@@ -0,0 +1,274 @@
+use core::marker::PhantomData;
+use std::sync::{Condvar, Mutex, PoisonError};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiWriteGuard},
+    try_lock::TryLockApi,
+};
+
+/// A blocking condition to wait on alongside any [`LockApi`] lock.
+pub trait ConditionApi {
+    /// A registration taken while the lock is still held, so a notification
+    /// sent before the waiter blocks is not lost.
+    type Listener<'a>
+    where
+        Self: 'a;
+
+    fn listen(&self) -> Self::Listener<'_>;
+
+    /// Blocks until the condition was notified after `listener` was taken.
+    fn block(listener: Self::Listener<'_>);
+
+    fn notify_one(&self);
+
+    fn notify_all(&self);
+
+    fn new() -> Self;
+}
+
+/// The default [`ConditionApi`]: a generation counter behind a std condvar.
+/// Every notification wakes the waiters registered before it, so
+/// `notify_one` may wake more than one of them.
+#[derive(Debug, Default)]
+pub struct Condition {
+    generation: Mutex<u64>,
+    changed: Condvar,
+}
+
+impl Condition {
+    pub const fn new() -> Condition {
+        Condition {
+            generation: Mutex::new(0),
+            changed: Condvar::new(),
+        }
+    }
+
+    fn bump(&self) {
+        *self
+            .generation
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) += 1;
+    }
+}
+
+pub struct ConditionListener<'a> {
+    condition: &'a Condition,
+    generation: u64,
+}
+
+impl ConditionApi for Condition {
+    type Listener<'a> = ConditionListener<'a>;
+
+    fn listen(&self) -> Self::Listener<'_> {
+        ConditionListener {
+            condition: self,
+            generation: *self
+                .generation
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner),
+        }
+    }
+
+    fn block(listener: Self::Listener<'_>) {
+        let condition = listener.condition;
+        let generation = condition
+            .generation
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        drop(
+            condition
+                .changed
+                .wait_while(generation, |generation| *generation == listener.generation)
+                .unwrap_or_else(PoisonError::into_inner),
+        );
+    }
+
+    fn notify_one(&self) {
+        self.bump();
+        self.changed.notify_one();
+    }
+
+    fn notify_all(&self) {
+        self.bump();
+        self.changed.notify_all();
+    }
+
+    fn new() -> Self {
+        Condition::new()
+    }
+}
+
+#[cfg(feature = "event-listener")]
+impl ConditionApi for event_listener::Event {
+    type Listener<'a> = event_listener::EventListener;
+
+    fn listen(&self) -> Self::Listener<'_> {
+        self.listen()
+    }
+
+    fn block(listener: Self::Listener<'_>) {
+        event_listener::Listener::wait(listener)
+    }
+
+    fn notify_one(&self) {
+        self.notify(1);
+    }
+
+    fn notify_all(&self) {
+        self.notify(usize::MAX);
+    }
+
+    fn new() -> Self {
+        event_listener::Event::new()
+    }
+}
+
+/// A lock and a condition in one, for the classic monitor pattern: wait
+/// under the lock until another thread changes the value and notifies.
+///
+/// ```ignore
+/// let queue = Monitor::<Vec<Job>, parking_lot::RwLock<_>>::new(Vec::new());
+/// queue.write_and_notify(|jobs| jobs.push(job))?;
+/// let mut jobs = queue.wait_while(queue.write()?, |jobs| jobs.is_empty())?;
+/// ```
+pub struct Monitor<T, L, C = Condition> {
+    lock: L,
+    condition: C,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T, L, C> Monitor<T, L, C>
+where
+    L: LockApi<T>,
+    C: ConditionApi,
+{
+    pub fn new(inner: T) -> Monitor<T, L, C> {
+        Monitor::from_parts(L::new(inner), C::new())
+    }
+
+    pub fn from_parts(lock: L, condition: C) -> Monitor<T, L, C> {
+        Monitor {
+            lock,
+            condition,
+            _value: PhantomData,
+        }
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.lock
+    }
+
+    pub fn condition(&self) -> &C {
+        &self.condition
+    }
+
+    pub fn into_inner(self) -> L {
+        self.lock
+    }
+
+    /// Releases `guard`, blocks until notified and reacquires the write lock.
+    /// Wakeups may be spurious; prefer [`wait_while`](Monitor::wait_while).
+    pub fn wait<'a>(&'a self, guard: L::WriteGuard<'a>) -> Result<L::WriteGuard<'a>> {
+        let listener = self.condition.listen();
+        drop(guard);
+        C::block(listener);
+        self.lock.write()
+    }
+
+    /// Waits for as long as `condition` holds, checking it under the lock
+    /// after every wakeup.
+    pub fn wait_while<'a, F>(
+        &'a self,
+        mut guard: L::WriteGuard<'a>,
+        mut condition: F,
+    ) -> Result<L::WriteGuard<'a>>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        while condition(guard.get_mut()) {
+            guard = self.wait(guard)?;
+        }
+        Ok(guard)
+    }
+
+    pub fn notify_one(&self) {
+        self.condition.notify_one()
+    }
+
+    pub fn notify_all(&self) {
+        self.condition.notify_all()
+    }
+
+    /// Updates the value and wakes every waiter once the lock is released.
+    pub fn write_and_notify<R>(&self, f: impl FnOnce(&mut T) -> R) -> Result<R> {
+        let ret = f(self.lock.write()?.get_mut());
+        self.condition.notify_all();
+        Ok(ret)
+    }
+}
+
+impl<T, L, C> Default for Monitor<T, L, C>
+where
+    T: Default,
+    L: LockApi<T>,
+    C: ConditionApi,
+{
+    fn default() -> Self {
+        Monitor::new(T::default())
+    }
+}
+
+impl<T, L, C> LockApi<T> for Monitor<T, L, C>
+where
+    L: LockApi<T>,
+    C: ConditionApi,
+{
+    type ReadGuard<'a>
+        = L::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = L::WriteGuard<'a>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.lock.read()
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.lock.write()
+    }
+
+    fn new(inner: T) -> Self {
+        Monitor::new(inner)
+    }
+}
+
+impl<T, L, C> TryLockApi<T> for Monitor<T, L, C>
+where
+    L: TryLockApi<T>,
+    C: ConditionApi,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.lock.try_read()
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.lock.try_write()
+    }
+}
+
+impl<T, L, C> core::fmt::Debug for Monitor<T, L, C>
+where
+    T: core::fmt::Debug,
+    L: TryLockApi<T>,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Monitor")
+            .field("data", &crate::peek::peek(&self.lock))
+            .finish_non_exhaustive()
+    }
+}
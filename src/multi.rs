@@ -0,0 +1,180 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::{error::Result, locking::LockApi};
+
+// Indices of `ids` in ascending order of the lock ids (see
+// `LockApi::lock_id`). Locking in this order everywhere means two callers can
+// never wait on each other.
+fn order<const N: usize>(ids: [usize; N]) -> [usize; N] {
+    let mut order = core::array::from_fn(|idx| idx);
+    order.sort_unstable_by_key(|idx| ids[*idx]);
+    assert_distinct(order.iter().map(|idx| ids[*idx]));
+    order
+}
+
+fn assert_distinct(sorted: impl Iterator<Item = usize>) {
+    let mut prev = None;
+    for id in sorted {
+        assert!(prev != Some(id), "the same locket was passed twice");
+        prev = Some(id);
+    }
+}
+
+pub(crate) fn acquire2<'a, A, B, GA, GB>(
+    (a, id_a): (&'a A, usize),
+    (b, id_b): (&'a B, usize),
+    lock_a: impl FnOnce(&'a A) -> Result<GA>,
+    lock_b: impl FnOnce(&'a B) -> Result<GB>,
+) -> Result<(GA, GB)>
+where
+    A: ?Sized,
+    B: ?Sized,
+{
+    if order([id_a, id_b])[0] == 0 {
+        let a = lock_a(a)?;
+        Ok((a, lock_b(b)?))
+    } else {
+        let b = lock_b(b)?;
+        Ok((lock_a(a)?, b))
+    }
+}
+
+/// Write locks `a` and `b` in lock id order.
+pub fn lock2<'a, A, B, TA, TB>(a: &'a A, b: &'a B) -> Result<(A::WriteGuard<'a>, B::WriteGuard<'a>)>
+where
+    A: LockApi<TA>,
+    B: LockApi<TB>,
+{
+    acquire2(
+        (a, a.lock_id()),
+        (b, b.lock_id()),
+        |a| a.write(),
+        |b| b.write(),
+    )
+}
+
+/// Write locks `a`, `b` and `c` in lock id order.
+pub fn lock3<'a, A, B, C, TA, TB, TC>(
+    a: &'a A,
+    b: &'a B,
+    c: &'a C,
+) -> Result<(A::WriteGuard<'a>, B::WriteGuard<'a>, C::WriteGuard<'a>)>
+where
+    A: LockApi<TA>,
+    B: LockApi<TB>,
+    C: LockApi<TC>,
+{
+    let (mut ga, mut gb, mut gc) = (None, None, None);
+    for idx in order([a.lock_id(), b.lock_id(), c.lock_id()]) {
+        match idx {
+            0 => ga = Some(a.write()?),
+            1 => gb = Some(b.write()?),
+            _ => gc = Some(c.write()?),
+        }
+    }
+    Ok((ga.unwrap(), gb.unwrap(), gc.unwrap()))
+}
+
+/// Write locks every locket in lock id order. The guards are returned in the
+/// order the lockets were passed.
+#[cfg(feature = "alloc")]
+pub fn lock_all<'a, L, T>(locks: &[&'a L]) -> Result<Vec<L::WriteGuard<'a>>>
+where
+    L: LockApi<T>,
+{
+    let mut order = (0..locks.len()).collect::<Vec<_>>();
+    order.sort_unstable_by_key(|idx| locks[*idx].lock_id());
+    assert_distinct(order.iter().map(|idx| locks[*idx].lock_id()));
+
+    let mut guards = (0..locks.len()).map(|_| None).collect::<Vec<_>>();
+    for idx in order {
+        guards[idx] = Some(locks[idx].write()?);
+    }
+    Ok(guards.into_iter().map(Option::unwrap).collect())
+}
+
+#[cfg(feature = "async")]
+pub use self::async_impl::*;
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::order;
+    use crate::{async_locking::AsyncLockApi, error::Result};
+    use core::future::Future;
+    #[cfg(feature = "alloc")]
+    use {super::assert_distinct, alloc::vec::Vec};
+
+    pub(crate) async fn acquire2_async<'a, A, B, GA, GB, FA, FB>(
+        (a, id_a): (&'a A, usize),
+        (b, id_b): (&'a B, usize),
+        lock_a: impl FnOnce(&'a A) -> FA,
+        lock_b: impl FnOnce(&'a B) -> FB,
+    ) -> Result<(GA, GB)>
+    where
+        FA: Future<Output = Result<GA>>,
+        FB: Future<Output = Result<GB>>,
+    {
+        if order([id_a, id_b])[0] == 0 {
+            let a = lock_a(a).await?;
+            Ok((a, lock_b(b).await?))
+        } else {
+            let b = lock_b(b).await?;
+            Ok((lock_a(a).await?, b))
+        }
+    }
+
+    pub async fn lock2_async<'a, A, B, TA, TB>(
+        a: &'a A,
+        b: &'a B,
+    ) -> Result<(A::WriteGuard<'a>, B::WriteGuard<'a>)>
+    where
+        A: AsyncLockApi<TA>,
+        B: AsyncLockApi<TB>,
+    {
+        acquire2_async(
+            (a, a.lock_id()),
+            (b, b.lock_id()),
+            |a| a.write(),
+            |b| b.write(),
+        )
+        .await
+    }
+
+    pub async fn lock3_async<'a, A, B, C, TA, TB, TC>(
+        a: &'a A,
+        b: &'a B,
+        c: &'a C,
+    ) -> Result<(A::WriteGuard<'a>, B::WriteGuard<'a>, C::WriteGuard<'a>)>
+    where
+        A: AsyncLockApi<TA>,
+        B: AsyncLockApi<TB>,
+        C: AsyncLockApi<TC>,
+    {
+        let (mut ga, mut gb, mut gc) = (None, None, None);
+        for idx in order([a.lock_id(), b.lock_id(), c.lock_id()]) {
+            match idx {
+                0 => ga = Some(a.write().await?),
+                1 => gb = Some(b.write().await?),
+                _ => gc = Some(c.write().await?),
+            }
+        }
+        Ok((ga.unwrap(), gb.unwrap(), gc.unwrap()))
+    }
+
+    #[cfg(feature = "alloc")]
+    pub async fn lock_all_async<'a, L, T>(locks: &[&'a L]) -> Result<Vec<L::WriteGuard<'a>>>
+    where
+        L: AsyncLockApi<T>,
+    {
+        let mut order = (0..locks.len()).collect::<Vec<_>>();
+        order.sort_unstable_by_key(|idx| locks[*idx].lock_id());
+        assert_distinct(order.iter().map(|idx| locks[*idx].lock_id()));
+
+        let mut guards = (0..locks.len()).map(|_| None).collect::<Vec<_>>();
+        for idx in order {
+            guards[idx] = Some(locks[idx].write().await?);
+        }
+        Ok(guards.into_iter().map(Option::unwrap).collect())
+    }
+}
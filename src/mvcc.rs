@@ -0,0 +1,246 @@
+use alloc::sync::Arc;
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
+
+use crate::{
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    timed::TimedLockApi,
+    try_lock::TryLockApi,
+};
+
+struct Committed<T> {
+    version: u64,
+    value: Arc<T>,
+}
+
+/// Snapshot isolation: readers get the last committed version and never wait
+/// for the writer, which prepares the next version on a clone and commits it
+/// when its guard is dropped. Long reads only keep their old version alive.
+pub struct MvccLocket<T> {
+    // Only held to clone or replace the `Arc`.
+    committed: Mutex<Committed<T>>,
+    writer: Mutex<()>,
+}
+
+impl<T> MvccLocket<T> {
+    pub fn new(inner: T) -> MvccLocket<T> {
+        MvccLocket {
+            committed: Mutex::new(Committed {
+                version: 0,
+                value: Arc::new(inner),
+            }),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// The last committed version. Starts at 0 and grows by one per commit.
+    pub fn version(&self) -> u64 {
+        self.committed().version
+    }
+
+    pub fn load(&self) -> Arc<T> {
+        self.committed().value.clone()
+    }
+
+    fn committed(&self) -> MutexGuard<'_, Committed<T>> {
+        self.committed
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn snapshot(&self) -> MvccReadGuard<'_, T> {
+        let committed = self.committed();
+        MvccReadGuard {
+            value: committed.value.clone(),
+            version: committed.version,
+            _lock: PhantomData,
+        }
+    }
+
+    fn begin<'a>(&'a self, writer: MutexGuard<'a, ()>) -> MvccWriteGuard<'a, T>
+    where
+        T: Clone,
+    {
+        MvccWriteGuard {
+            value: Some(T::clone(&self.load())),
+            lock: self,
+            _writer: writer,
+        }
+    }
+}
+
+impl<T: Default> Default for MvccLocket<T> {
+    fn default() -> Self {
+        MvccLocket::new(T::default())
+    }
+}
+
+impl<T> LockApi<T> for MvccLocket<T>
+where
+    T: Clone,
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = MvccReadGuard<'a, T>;
+
+    type WriteGuard<'a> = MvccWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        Ok(self.snapshot())
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        let writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(self.begin(writer))
+    }
+
+    fn new(inner: T) -> Self {
+        MvccLocket::new(inner)
+    }
+}
+
+impl<T> TryLockApi<T> for MvccLocket<T>
+where
+    T: Clone,
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        LockApi::read(self)
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        let writer = match self.writer.try_lock() {
+            Ok(writer) => writer,
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            Err(TryLockError::WouldBlock) => return Err(LockError::WouldBlock),
+        };
+        Ok(self.begin(writer))
+    }
+}
+
+impl<T> TimedLockApi<T> for MvccLocket<T>
+where
+    T: Clone,
+    for<'a> T: 'a,
+{
+}
+
+impl<T> IntoInner<T> for MvccLocket<T>
+where
+    T: Clone,
+{
+    fn into_inner(self) -> Result<T> {
+        let committed = self
+            .committed
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        Ok(Arc::unwrap_or_clone(committed.value))
+    }
+}
+
+impl<T> PoisonApi for MvccLocket<T> {}
+
+impl<T> core::fmt::Debug for MvccLocket<T>
+where
+    T: Clone + core::fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MvccLocket")
+            .field("version", &self.version())
+            .field("data", &crate::peek::peek(self))
+            .finish()
+    }
+}
+
+pub struct MvccReadGuard<'a, T> {
+    value: Arc<T>,
+    version: u64,
+    _lock: PhantomData<&'a MvccLocket<T>>,
+}
+
+impl<T> MvccReadGuard<'_, T> {
+    /// The version this snapshot was taken at.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    pub fn snapshot(&self) -> Arc<T> {
+        self.value.clone()
+    }
+}
+
+impl<T> Deref for MvccReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for MvccReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+pub struct MvccWriteGuard<'a, T> {
+    value: Option<T>,
+    lock: &'a MvccLocket<T>,
+    _writer: MutexGuard<'a, ()>,
+}
+
+impl<T> MvccWriteGuard<'_, T> {
+    /// Drops the prepared version without committing it.
+    pub fn abort(mut self) {
+        self.value = None;
+    }
+}
+
+impl<T> Drop for MvccWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        // Do not commit a version a panicking writer may have left half done.
+        if std::thread::panicking() {
+            return;
+        }
+        if let Some(value) = self.value.take() {
+            let value = Arc::new(value);
+            let mut committed = self.lock.committed();
+            committed.version += 1;
+            let previous = core::mem::replace(&mut committed.value, value);
+            // Free the old version outside of the critical section.
+            drop(committed);
+            drop(previous);
+        }
+    }
+}
+
+impl<T> Deref for MvccWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_ref().unwrap()
+    }
+}
+
+impl<T> DerefMut for MvccWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value.as_mut().unwrap()
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for MvccWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for MvccWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
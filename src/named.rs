@@ -0,0 +1,272 @@
+use alloc::{string::String, sync::Arc};
+use core::{
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+use std::{
+    sync::{Mutex, PoisonError},
+    thread::{self, ThreadId},
+};
+
+use crate::{
+    error::Result,
+    locking::{AccessMode, LockApi, LockApiReadGuard, LockApiWriteGuard},
+};
+
+pub type Labels = &'static [(&'static str, &'static str)];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Holder {
+    pub thread_id: ThreadId,
+    pub thread_name: Option<String>,
+    pub mode: AccessMode,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocketInfo {
+    pub name: &'static str,
+    pub labels: Labels,
+    pub locked: bool,
+    pub locked_exclusive: bool,
+    pub readers: usize,
+    pub last_holder: Option<Holder>,
+    #[cfg(feature = "metrics")]
+    pub metrics: crate::metrics::MetricsSnapshot,
+    #[cfg(feature = "fairness")]
+    pub fairness: crate::metrics::FairnessSnapshot,
+}
+
+pub(crate) struct LocketState {
+    name: &'static str,
+    labels: Labels,
+    readers: AtomicUsize,
+    writer: AtomicBool,
+    last_holder: Mutex<Option<Holder>>,
+    #[cfg(feature = "metrics")]
+    metrics: crate::metrics::Recorder,
+}
+
+// What a guard needs to report its release.
+struct Held {
+    mode: AccessMode,
+    #[cfg(feature = "metrics")]
+    acquired: std::time::Instant,
+}
+
+impl LocketState {
+    fn new(name: &'static str, labels: Labels) -> Arc<LocketState> {
+        let state = Arc::new(LocketState {
+            name,
+            labels,
+            readers: AtomicUsize::new(0),
+            writer: AtomicBool::new(false),
+            last_holder: Mutex::new(None),
+            #[cfg(feature = "metrics")]
+            metrics: crate::metrics::Recorder::new(),
+        });
+        #[cfg(feature = "registry")]
+        crate::registry::register(&state);
+        state
+    }
+
+    fn acquire<G>(&self, mode: AccessMode, lock: impl FnOnce() -> Result<G>) -> Result<(G, Held)> {
+        #[cfg(feature = "metrics")]
+        let (guard, acquired) = self.metrics.acquire(mode == AccessMode::Write, lock)?;
+        #[cfg(not(feature = "metrics"))]
+        let guard = lock()?;
+        self.acquired(mode);
+        let held = Held {
+            mode,
+            #[cfg(feature = "metrics")]
+            acquired,
+        };
+        Ok((guard, held))
+    }
+
+    fn release(&self, held: &Held) {
+        self.released(held.mode);
+        #[cfg(feature = "metrics")]
+        self.metrics
+            .release(held.mode == AccessMode::Write, held.acquired);
+    }
+
+    pub(crate) fn info(&self) -> LocketInfo {
+        let readers = self.readers.load(Ordering::Relaxed);
+        let writer = self.writer.load(Ordering::Relaxed);
+        LocketInfo {
+            name: self.name,
+            labels: self.labels,
+            locked: writer || readers > 0,
+            locked_exclusive: writer,
+            readers,
+            last_holder: self
+                .last_holder
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .clone(),
+            #[cfg(feature = "metrics")]
+            metrics: self.metrics.snapshot(),
+            #[cfg(feature = "fairness")]
+            fairness: self.metrics.fairness(),
+        }
+    }
+
+    fn acquired(&self, mode: AccessMode) {
+        match mode {
+            AccessMode::Read => {
+                self.readers.fetch_add(1, Ordering::Relaxed);
+            }
+            AccessMode::Write => self.writer.store(true, Ordering::Relaxed),
+        }
+        let current = thread::current();
+        *self
+            .last_holder
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner) = Some(Holder {
+            thread_id: current.id(),
+            thread_name: current.name().map(String::from),
+            mode,
+        });
+    }
+
+    fn released(&self, mode: AccessMode) {
+        match mode {
+            AccessMode::Read => {
+                self.readers.fetch_sub(1, Ordering::Relaxed);
+            }
+            AccessMode::Write => self.writer.store(false, Ordering::Relaxed),
+        }
+    }
+}
+
+/// A lock carrying a name and labels, tracking who holds it. With the `registry`
+/// feature every named locket is listed by [`registry::lockets`](crate::registry::lockets).
+pub struct Named<L> {
+    inner: L,
+    state: Arc<LocketState>,
+}
+
+impl<L> Named<L> {
+    pub fn with_name(inner: L, name: &'static str) -> Named<L> {
+        Named::with_labels(inner, name, &[])
+    }
+
+    pub fn with_labels(inner: L, name: &'static str, labels: Labels) -> Named<L> {
+        let state = LocketState::new(name, labels);
+        Named { inner, state }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.state.name
+    }
+
+    pub fn labels(&self) -> Labels {
+        self.state.labels
+    }
+
+    pub fn info(&self) -> LocketInfo {
+        self.state.info()
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    fn acquire<'a, G>(
+        &'a self,
+        mode: AccessMode,
+        lock: impl FnOnce(&'a L) -> Result<G>,
+    ) -> Result<NamedGuard<'a, G>> {
+        let (guard, held) = self.state.acquire(mode, || lock(&self.inner))?;
+        Ok(NamedGuard {
+            guard,
+            state: &self.state,
+            held,
+        })
+    }
+}
+
+impl<L, T> LockApi<T> for Named<L>
+where
+    L: LockApi<T>,
+{
+    type ReadGuard<'a>
+        = NamedGuard<'a, L::ReadGuard<'a>>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = NamedGuard<'a, L::WriteGuard<'a>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        #[cfg(feature = "deadlock_detection")]
+        let _waiting = crate::deadlock::waiting(self.inner.lock_id(), Some(self.name()));
+        self.acquire(AccessMode::Read, |lock| lock.read())
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        #[cfg(feature = "deadlock_detection")]
+        let _waiting = crate::deadlock::waiting(self.inner.lock_id(), Some(self.name()));
+        self.acquire(AccessMode::Write, |lock| lock.write())
+    }
+
+    fn new(inner: T) -> Self {
+        Named::with_name(L::new(inner), core::any::type_name::<T>())
+    }
+}
+
+pub struct NamedGuard<'a, G> {
+    guard: G,
+    state: &'a LocketState,
+    held: Held,
+}
+
+impl<G> Drop for NamedGuard<'_, G> {
+    fn drop(&mut self) {
+        self.state.release(&self.held);
+    }
+}
+
+impl<G> Deref for NamedGuard<'_, G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<G> DerefMut for NamedGuard<'_, G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, T, G> LockApiReadGuard<'a, T> for NamedGuard<'a, G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.get()
+    }
+}
+
+impl<'a, T, G> LockApiWriteGuard<'a, T> for NamedGuard<'a, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.get_mut()
+    }
+}
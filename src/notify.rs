@@ -0,0 +1,151 @@
+use crate::{
+    async_event::AsyncEventApi, async_locking::AsyncLockApi, error::Result, locking::LockApi,
+    try_lock::TryLockApi,
+};
+
+/// A locket with an event next to it, for "wake the consumers, I wrote
+/// something" patterns. Unlike `WatchLocket` nothing is notified implicitly;
+/// call [`notify_one`](Notifying::notify_one) or
+/// [`notify_all`](Notifying::notify_all) when there is something to see. `E`
+/// is any [`AsyncEventApi`], such as tokio's `Notify` or event-listener's
+/// `Event`.
+pub struct Notifying<L, E> {
+    inner: L,
+    event: E,
+}
+
+impl<L, E> Notifying<L, E>
+where
+    E: AsyncEventApi,
+{
+    pub fn wrap(inner: L) -> Notifying<L, E> {
+        Notifying {
+            inner,
+            event: E::new(),
+        }
+    }
+}
+
+impl<L, E> Notifying<L, E> {
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn event(&self) -> &E {
+        &self.event
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+}
+
+impl<L, E> Notifying<L, E>
+where
+    E: AsyncEventApi,
+{
+    pub fn notify_one(&self) {
+        self.event.notify_one();
+    }
+
+    pub fn notify_all(&self) {
+        self.event.notify_all();
+    }
+
+    /// Resolves on the next notification. To not miss one sent while checking
+    /// the value, create the future before reading and await it after.
+    pub fn wait_notified(&self) -> E::Listener<'_> {
+        self.event.listen()
+    }
+}
+
+impl<L, E, T> LockApi<T> for Notifying<L, E>
+where
+    L: LockApi<T>,
+    E: AsyncEventApi,
+{
+    type ReadGuard<'a>
+        = L::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = L::WriteGuard<'a>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.inner.read()
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.inner.write()
+    }
+
+    fn new(inner: T) -> Self {
+        Notifying::wrap(L::new(inner))
+    }
+}
+
+impl<L, E, T> TryLockApi<T> for Notifying<L, E>
+where
+    L: TryLockApi<T>,
+    E: AsyncEventApi,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.inner.try_read()
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.inner.try_write()
+    }
+}
+
+impl<L, E, T> AsyncLockApi<T> for Notifying<L, E>
+where
+    L: AsyncLockApi<T>,
+    E: AsyncEventApi,
+{
+    type ReadGuard<'a>
+        = L::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = L::WriteGuard<'a>
+    where
+        Self: 'a;
+
+    type ReadFuture<'a>
+        = L::ReadFuture<'a>
+    where
+        Self: 'a;
+
+    type WriteFuture<'a>
+        = L::WriteFuture<'a>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Self::ReadFuture<'_> {
+        self.inner.read()
+    }
+
+    fn write(&self) -> Self::WriteFuture<'_> {
+        self.inner.write()
+    }
+
+    fn new(inner: T) -> Self {
+        Notifying::wrap(L::new(inner))
+    }
+}
+
+impl<L, E> core::fmt::Debug for Notifying<L, E>
+where
+    L: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Notifying")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,210 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::ops::{Deref, DerefMut};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+};
+
+type Observer<T> = Box<dyn Fn(&T) + Send + Sync>;
+
+/// Calls the registered observers with the new value after every write. They
+/// run when the write guard is dropped, before the lock is released, so they
+/// see exactly the value that was written.
+pub struct Observed<L, T> {
+    inner: L,
+    observers: Vec<Observer<T>>,
+}
+
+impl<L, T> Observed<L, T> {
+    pub fn wrap(inner: L) -> Observed<L, T> {
+        Observed {
+            inner,
+            observers: Vec::new(),
+        }
+    }
+
+    pub fn on_write<F>(mut self, observer: F) -> Observed<L, T>
+    where
+        F: Fn(&T) + Send + Sync + 'static,
+    {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+}
+
+impl<L, T> LockApi<T> for Observed<L, T>
+where
+    L: LockApi<T>,
+{
+    type ReadGuard<'a>
+        = L::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = ObservedGuard<'a, T, L::WriteGuard<'a>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.inner.read()
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        Ok(ObservedGuard {
+            guard: self.inner.write()?,
+            observers: &self.observers,
+        })
+    }
+
+    fn new(inner: T) -> Self {
+        Observed::wrap(L::new(inner))
+    }
+}
+
+pub struct ObservedGuard<'a, T, G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    guard: G,
+    observers: &'a [Observer<T>],
+}
+
+impl<'a, T, G> Drop for ObservedGuard<'a, T, G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn drop(&mut self) {
+        let value = self.guard.get();
+        for observer in self.observers {
+            observer(value);
+        }
+    }
+}
+
+impl<'a, T, G> Deref for ObservedGuard<'a, T, G>
+where
+    G: LockApiReadGuard<'a, T> + Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<'a, T, G> DerefMut for ObservedGuard<'a, T, G>
+where
+    G: LockApiReadGuard<'a, T> + DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, T, G> LockApiReadGuard<'a, T> for ObservedGuard<'a, T, G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.get()
+    }
+}
+
+impl<'a, T, G> LockApiWriteGuard<'a, T> for ObservedGuard<'a, T, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.get_mut()
+    }
+}
+
+#[cfg(feature = "async")]
+pub use self::async_impl::ObservedFuture;
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{Observed, ObservedGuard, Observer};
+    use crate::{async_locking::AsyncLockApi, error::Result, locking::LockApiReadGuard};
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{ready, Context, Poll},
+    };
+    use pin_project_lite::pin_project;
+
+    impl<L, T> AsyncLockApi<T> for Observed<L, T>
+    where
+        L: AsyncLockApi<T>,
+    {
+        type ReadGuard<'a>
+            = L::ReadGuard<'a>
+        where
+            Self: 'a;
+
+        type WriteGuard<'a>
+            = ObservedGuard<'a, T, L::WriteGuard<'a>>
+        where
+            Self: 'a;
+
+        type ReadFuture<'a>
+            = L::ReadFuture<'a>
+        where
+            Self: 'a;
+
+        type WriteFuture<'a>
+            = ObservedFuture<'a, T, L::WriteFuture<'a>>
+        where
+            Self: 'a;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            self.inner.read()
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            ObservedFuture {
+                future: self.inner.write(),
+                observers: &self.observers,
+            }
+        }
+
+        fn new(inner: T) -> Self {
+            Observed::wrap(L::new(inner))
+        }
+    }
+
+    pin_project! {
+        pub struct ObservedFuture<'a, T, F> {
+            #[pin]
+            future: F,
+            observers: &'a [Observer<T>],
+        }
+    }
+
+    impl<'a, T, F, G> Future for ObservedFuture<'a, T, F>
+    where
+        F: Future<Output = Result<G>>,
+        G: LockApiReadGuard<'a, T>,
+    {
+        type Output = Result<ObservedGuard<'a, T, G>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            let guard = ready!(this.future.poll(cx))?;
+            Poll::Ready(Ok(ObservedGuard {
+                guard,
+                observers: this.observers,
+            }))
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use core::cell::OnceCell;
+
+pub trait OnceApi<T> {
+    fn get(&self) -> Option<&T>;
+
+    fn set(&self, value: T) -> Result<(), T>;
+
+    fn get_or_init<F>(&self, init: F) -> &T
+    where
+        F: FnOnce() -> T;
+
+    fn get_or_try_init<F, E>(&self, init: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Result<T, E>;
+
+    fn new() -> Self;
+}
+
+impl<T> OnceApi<T> for OnceCell<T> {
+    fn get(&self) -> Option<&T> {
+        self.get()
+    }
+
+    fn set(&self, value: T) -> Result<(), T> {
+        self.set(value)
+    }
+
+    fn get_or_init<F>(&self, init: F) -> &T
+    where
+        F: FnOnce() -> T,
+    {
+        self.get_or_init(init)
+    }
+
+    fn get_or_try_init<F, E>(&self, init: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        if let Some(value) = self.get() {
+            return Ok(value);
+        }
+        let value = init()?;
+        Ok(self.get_or_init(|| value))
+    }
+
+    fn new() -> Self {
+        OnceCell::new()
+    }
+}
+
+#[cfg(feature = "std")]
+mod std_impl {
+    use super::OnceApi;
+    use std::sync::OnceLock;
+
+    impl<T> OnceApi<T> for OnceLock<T> {
+        fn get(&self) -> Option<&T> {
+            self.get()
+        }
+
+        fn set(&self, value: T) -> Result<(), T> {
+            self.set(value)
+        }
+
+        fn get_or_init<F>(&self, init: F) -> &T
+        where
+            F: FnOnce() -> T,
+        {
+            self.get_or_init(init)
+        }
+
+        // OnceLock::get_or_try_init is unstable, so racing initializers may both run.
+        // Only the first value is stored.
+        fn get_or_try_init<F, E>(&self, init: F) -> Result<&T, E>
+        where
+            F: FnOnce() -> Result<T, E>,
+        {
+            if let Some(value) = self.get() {
+                return Ok(value);
+            }
+            let value = init()?;
+            Ok(self.get_or_init(|| value))
+        }
+
+        fn new() -> Self {
+            OnceLock::new()
+        }
+    }
+}
+
+#[cfg(feature = "once_cell")]
+mod once_cell_impl {
+    use super::OnceApi;
+
+    impl<T> OnceApi<T> for once_cell::sync::OnceCell<T> {
+        fn get(&self) -> Option<&T> {
+            self.get()
+        }
+
+        fn set(&self, value: T) -> Result<(), T> {
+            self.set(value)
+        }
+
+        fn get_or_init<F>(&self, init: F) -> &T
+        where
+            F: FnOnce() -> T,
+        {
+            self.get_or_init(init)
+        }
+
+        fn get_or_try_init<F, E>(&self, init: F) -> Result<&T, E>
+        where
+            F: FnOnce() -> Result<T, E>,
+        {
+            self.get_or_try_init(init)
+        }
+
+        fn new() -> Self {
+            once_cell::sync::OnceCell::new()
+        }
+    }
+
+    impl<T> OnceApi<T> for once_cell::unsync::OnceCell<T> {
+        fn get(&self) -> Option<&T> {
+            self.get()
+        }
+
+        fn set(&self, value: T) -> Result<(), T> {
+            self.set(value)
+        }
+
+        fn get_or_init<F>(&self, init: F) -> &T
+        where
+            F: FnOnce() -> T,
+        {
+            self.get_or_init(init)
+        }
+
+        fn get_or_try_init<F, E>(&self, init: F) -> Result<&T, E>
+        where
+            F: FnOnce() -> Result<T, E>,
+        {
+            self.get_or_try_init(init)
+        }
+
+        fn new() -> Self {
+            once_cell::unsync::OnceCell::new()
+        }
+    }
+}
+
+#[cfg(feature = "spin")]
+mod spin_impl {
+    use super::OnceApi;
+    use spin::Once;
+
+    impl<T> OnceApi<T> for Once<T> {
+        fn get(&self) -> Option<&T> {
+            self.get()
+        }
+
+        fn set(&self, value: T) -> Result<(), T> {
+            let mut value = Some(value);
+            self.call_once(|| value.take().unwrap());
+            match value {
+                None => Ok(()),
+                Some(value) => Err(value),
+            }
+        }
+
+        fn get_or_init<F>(&self, init: F) -> &T
+        where
+            F: FnOnce() -> T,
+        {
+            self.call_once(init)
+        }
+
+        fn get_or_try_init<F, E>(&self, init: F) -> Result<&T, E>
+        where
+            F: FnOnce() -> Result<T, E>,
+        {
+            self.try_call_once(init)
+        }
+
+        fn new() -> Self {
+            Once::new()
+        }
+    }
+}
+
+#[cfg(all(feature = "async-lock", not(target_family = "wasm")))]
+mod async_lock_impl {
+    use super::OnceApi;
+    use async_lock::OnceCell;
+
+    impl<T> OnceApi<T> for OnceCell<T> {
+        fn get(&self) -> Option<&T> {
+            self.get()
+        }
+
+        fn set(&self, value: T) -> Result<(), T> {
+            self.set_blocking(value).map(|_| ())
+        }
+
+        fn get_or_init<F>(&self, init: F) -> &T
+        where
+            F: FnOnce() -> T,
+        {
+            match self.get_or_try_init_blocking(|| Ok::<_, core::convert::Infallible>(init())) {
+                Ok(value) => value,
+                Err(err) => match err {},
+            }
+        }
+
+        fn get_or_try_init<F, E>(&self, init: F) -> Result<&T, E>
+        where
+            F: FnOnce() -> Result<T, E>,
+        {
+            self.get_or_try_init_blocking(init)
+        }
+
+        fn new() -> Self {
+            OnceCell::new()
+        }
+    }
+}
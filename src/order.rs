@@ -0,0 +1,153 @@
+use alloc::vec::Vec;
+use core::{
+    cell::RefCell,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+};
+
+std::thread_local! {
+    static HELD_LEVELS: RefCell<Vec<u32>> = const { RefCell::new(Vec::new()) };
+}
+
+fn check_level(level: u32) {
+    let highest = HELD_LEVELS.with(|held| held.borrow().iter().copied().max());
+    let Some(highest) = highest.filter(|highest| *highest >= level) else {
+        return;
+    };
+    let message =
+        format_args!("lock order violation: acquiring level {level} while holding level {highest}");
+    if cfg!(debug_assertions) {
+        panic!("{message}");
+    } else {
+        std::eprintln!("{message}");
+    }
+}
+
+fn push_level(level: u32) {
+    HELD_LEVELS.with(|held| held.borrow_mut().push(level));
+}
+
+fn pop_level(level: u32) {
+    HELD_LEVELS.with(|held| {
+        let mut held = held.borrow_mut();
+        if let Some(idx) = held.iter().rposition(|held| *held == level) {
+            held.remove(idx);
+        }
+    });
+}
+
+/// A lock assigned to a level in a lock hierarchy. Acquiring it while the current
+/// thread holds a lock of the same or a higher level panics in debug builds;
+/// release builds print the violation to stderr and acquire the lock anyway.
+pub struct Ordered<L> {
+    inner: L,
+    level: u32,
+}
+
+impl<L> Ordered<L> {
+    pub const fn with_level(inner: L, level: u32) -> Ordered<L> {
+        Ordered { inner, level }
+    }
+
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    fn acquire<'a, G>(&'a self, lock: impl FnOnce(&'a L) -> Result<G>) -> Result<OrderedGuard<G>> {
+        check_level(self.level);
+        let guard = lock(&self.inner)?;
+        push_level(self.level);
+        Ok(OrderedGuard {
+            guard,
+            level: self.level,
+        })
+    }
+}
+
+impl<L, T> LockApi<T> for Ordered<L>
+where
+    L: LockApi<T>,
+{
+    type ReadGuard<'a>
+        = OrderedGuard<L::ReadGuard<'a>>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = OrderedGuard<L::WriteGuard<'a>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.acquire(|lock| lock.read())
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.acquire(|lock| lock.write())
+    }
+
+    fn new(inner: T) -> Self {
+        Ordered::with_level(L::new(inner), 0)
+    }
+}
+
+pub struct OrderedGuard<G> {
+    guard: G,
+    level: u32,
+}
+
+impl<G> Drop for OrderedGuard<G> {
+    fn drop(&mut self) {
+        pop_level(self.level);
+    }
+}
+
+impl<G> Deref for OrderedGuard<G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<G> DerefMut for OrderedGuard<G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, T, G> LockApiReadGuard<'a, T> for OrderedGuard<G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.get()
+    }
+}
+
+impl<'a, T, G> LockApiWriteGuard<'a, T> for OrderedGuard<G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.get_mut()
+    }
+}
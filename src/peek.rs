@@ -0,0 +1,58 @@
+use core::{fmt, marker::PhantomData};
+
+use crate::{error::LockError, locking::LockApiReadGuard, try_lock::TryLockApi};
+
+/// Formats the value behind a lock without blocking. A lock which is held for
+/// writing prints as `<locked>`, a poisoned one as `<poisoned>`.
+pub fn peek<L, T>(lock: &L) -> Peek<'_, L, T>
+where
+    L: TryLockApi<T>,
+{
+    Peek {
+        lock,
+        _value: PhantomData,
+    }
+}
+
+pub struct Peek<'a, L, T> {
+    lock: &'a L,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<L, T> Peek<'_, L, T>
+where
+    L: TryLockApi<T>,
+{
+    fn fmt_with(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        fmt: impl FnOnce(&T, &mut fmt::Formatter<'_>) -> fmt::Result,
+    ) -> fmt::Result {
+        match self.lock.try_read() {
+            Ok(guard) => fmt(guard.get(), f),
+            Err(LockError::WouldBlock) => f.write_str("<locked>"),
+            Err(LockError::Poisoned) => f.write_str("<poisoned>"),
+            Err(err) => write!(f, "<{err}>"),
+        }
+    }
+}
+
+impl<L, T> fmt::Debug for Peek<'_, L, T>
+where
+    L: TryLockApi<T>,
+    T: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, fmt::Debug::fmt)
+    }
+}
+
+impl<L, T> fmt::Display for Peek<'_, L, T>
+where
+    L: TryLockApi<T>,
+    T: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, fmt::Display::fmt)
+    }
+}
@@ -0,0 +1,280 @@
+use alloc::{rc::Rc, sync::Arc};
+use core::{
+    ops::{Deref, DerefMut},
+    pin::Pin,
+};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    try_lock::TryLockApi,
+};
+
+/// A handle whose lock lives at a fixed address shared by all of its clones,
+/// which [`PinnedLocket`] relies on to pin the value inside.
+///
+/// # Safety
+///
+/// The lock must not move or be dropped while any clone of the handle exists,
+/// and it must drop its value in place.
+pub unsafe trait StableHandle: Clone {}
+
+/// A lock which keeps its value in place and drops it there. Backends which
+/// copy the value out, swap in new versions or double-buffer it do not qualify.
+///
+/// # Safety
+///
+/// The value must not be moved while the lock lives, and must be dropped in
+/// place when the lock is dropped.
+pub unsafe trait PinStable {}
+
+// SAFETY: the lock lives in the shared allocation until the last handle is
+// dropped, and keeps its value in place.
+unsafe impl<L: PinStable> StableHandle for Arc<L> {}
+
+// SAFETY: as for `Arc`.
+unsafe impl<L: PinStable> StableHandle for Rc<L> {}
+
+// SAFETY: as for `Arc`.
+#[cfg(feature = "portable-atomic")]
+unsafe impl<L: PinStable> StableHandle for portable_atomic_util::Arc<L> {}
+
+// SAFETY: these locks store the value inline in a cell and drop it with
+// themselves.
+#[cfg(feature = "std-lock")]
+unsafe impl<T> PinStable for std::sync::Mutex<T> {}
+#[cfg(feature = "std-lock")]
+unsafe impl<T> PinStable for std::sync::RwLock<T> {}
+#[cfg(feature = "std-lock")]
+unsafe impl<T> PinStable for crate::poison::StdMutex<T> {}
+#[cfg(feature = "std-lock")]
+unsafe impl<T> PinStable for crate::poison::StdRwLock<T> {}
+#[cfg(feature = "parking_lot")]
+unsafe impl<T> PinStable for parking_lot::Mutex<T> {}
+#[cfg(feature = "parking_lot")]
+unsafe impl<T> PinStable for parking_lot::FairMutex<T> {}
+#[cfg(feature = "parking_lot")]
+unsafe impl<T> PinStable for parking_lot::RwLock<T> {}
+#[cfg(feature = "spin")]
+unsafe impl<T> PinStable for spin::Mutex<T> {}
+#[cfg(feature = "spin")]
+unsafe impl<T> PinStable for spin::RwLock<T> {}
+#[cfg(feature = "tokio")]
+unsafe impl<T> PinStable for tokio::sync::Mutex<T> {}
+#[cfg(feature = "tokio")]
+unsafe impl<T> PinStable for tokio::sync::RwLock<T> {}
+
+/// A locket whose value is pinned, for `!Unpin` values such as futures. Write
+/// guards give out `Pin<&mut T>` instead of `&mut T`, and the lock itself is
+/// never exposed, so the value stays put until it is dropped. Created with
+/// [`Locket::pin`](crate::Locket::pin).
+///
+/// It implements [`LockApi`] for `Unpin` values only.
+pub struct PinnedLocket<H> {
+    handle: H,
+}
+
+impl<H> PinnedLocket<H>
+where
+    H: StableHandle,
+{
+    pub fn new<T>(inner: T) -> PinnedLocket<H>
+    where
+        H: LockApi<T>,
+    {
+        PinnedLocket {
+            handle: H::new(inner),
+        }
+    }
+
+    pub fn read<T>(&self) -> Result<PinnedGuard<H::ReadGuard<'_>>>
+    where
+        H: LockApi<T>,
+    {
+        Ok(PinnedGuard {
+            guard: self.handle.read()?,
+        })
+    }
+
+    pub fn write_pinned<T>(&self) -> Result<PinnedGuard<H::WriteGuard<'_>>>
+    where
+        H: LockApi<T>,
+    {
+        Ok(PinnedGuard {
+            guard: self.handle.write()?,
+        })
+    }
+
+    pub fn try_read<T>(&self) -> Result<PinnedGuard<H::ReadGuard<'_>>>
+    where
+        H: TryLockApi<T>,
+    {
+        Ok(PinnedGuard {
+            guard: self.handle.try_read()?,
+        })
+    }
+
+    pub fn try_write_pinned<T>(&self) -> Result<PinnedGuard<H::WriteGuard<'_>>>
+    where
+        H: TryLockApi<T>,
+    {
+        Ok(PinnedGuard {
+            guard: self.handle.try_write()?,
+        })
+    }
+}
+
+impl<H> Clone for PinnedLocket<H>
+where
+    H: StableHandle,
+{
+    fn clone(&self) -> Self {
+        PinnedLocket {
+            handle: self.handle.clone(),
+        }
+    }
+}
+
+impl<H, T> LockApi<T> for PinnedLocket<H>
+where
+    H: LockApi<T> + StableHandle,
+    T: Unpin,
+{
+    type ReadGuard<'a>
+        = H::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = H::WriteGuard<'a>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.handle.read()
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.handle.write()
+    }
+
+    fn new(inner: T) -> Self {
+        PinnedLocket::new(inner)
+    }
+}
+
+impl<H> core::fmt::Debug for PinnedLocket<H>
+where
+    H: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PinnedLocket")
+            .field("inner", &self.handle)
+            .finish()
+    }
+}
+
+/// A guard of a [`PinnedLocket`]. Guards of backends whose read guards are
+/// exclusive too (mutexes) also hand out the value pinned, never as `&mut T`
+/// unless it is `Unpin`.
+pub struct PinnedGuard<G> {
+    guard: G,
+}
+
+impl<G> PinnedGuard<G>
+where
+    G: DerefMut,
+{
+    pub fn as_mut(&mut self) -> Pin<&mut G::Target> {
+        // SAFETY: the value lives in a `StableHandle` lock which is never
+        // exposed, and guards only reach it mutably through this method, so
+        // it is never moved.
+        unsafe { Pin::new_unchecked(&mut *self.guard) }
+    }
+
+    /// Replaces the value, dropping the old one in place.
+    pub fn set(&mut self, value: G::Target)
+    where
+        G::Target: Sized,
+    {
+        self.as_mut().set(value);
+    }
+}
+
+impl<G> Deref for PinnedGuard<G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<G> DerefMut for PinnedGuard<G>
+where
+    G: DerefMut,
+    G::Target: Unpin,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, T, G> LockApiReadGuard<'a, T> for PinnedGuard<G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.get()
+    }
+}
+
+impl<'a, T, G> LockApiWriteGuard<'a, T> for PinnedGuard<G>
+where
+    G: LockApiWriteGuard<'a, T>,
+    T: Unpin,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.get_mut()
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{PinnedGuard, PinnedLocket, StableHandle};
+    use crate::{async_locking::AsyncLockApi, error::Result};
+
+    impl<H> PinnedLocket<H>
+    where
+        H: StableHandle,
+    {
+        pub fn new_async<T>(inner: T) -> PinnedLocket<H>
+        where
+            H: AsyncLockApi<T>,
+        {
+            PinnedLocket {
+                handle: H::new(inner),
+            }
+        }
+
+        pub async fn read_async<T>(&self) -> Result<PinnedGuard<H::ReadGuard<'_>>>
+        where
+            H: AsyncLockApi<T>,
+        {
+            Ok(PinnedGuard {
+                guard: self.handle.read().await?,
+            })
+        }
+
+        pub async fn write_pinned_async<T>(&self) -> Result<PinnedGuard<H::WriteGuard<'_>>>
+        where
+            H: AsyncLockApi<T>,
+        {
+            Ok(PinnedGuard {
+                guard: self.handle.write().await?,
+            })
+        }
+    }
+}
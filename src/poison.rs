@@ -0,0 +1,369 @@
+#[cfg(feature = "alloc")]
+use alloc::{rc::Rc, sync::Arc};
+use core::cell::RefCell;
+
+/// Poisoning introspection and recovery. Backends without a notion of poisoning
+/// use the default implementations, which report a healthy lock.
+pub trait PoisonApi {
+    fn is_poisoned(&self) -> bool {
+        false
+    }
+
+    fn clear_poison(&self) {}
+}
+
+#[cfg(feature = "alloc")]
+impl<L> PoisonApi for Arc<L>
+where
+    L: PoisonApi,
+{
+    fn is_poisoned(&self) -> bool {
+        (**self).is_poisoned()
+    }
+
+    fn clear_poison(&self) {
+        (**self).clear_poison()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L> PoisonApi for Rc<L>
+where
+    L: PoisonApi,
+{
+    fn is_poisoned(&self) -> bool {
+        (**self).is_poisoned()
+    }
+
+    fn clear_poison(&self) {
+        (**self).clear_poison()
+    }
+}
+
+impl<T> PoisonApi for RefCell<T> {}
+
+#[cfg(feature = "atomic_refcell")]
+impl<T> PoisonApi for atomic_refcell::AtomicRefCell<T> {}
+
+#[cfg(feature = "parking_lot")]
+mod parking_lot_impl {
+    use super::PoisonApi;
+
+    impl<T> PoisonApi for parking_lot::Mutex<T> {}
+
+    impl<T> PoisonApi for parking_lot::FairMutex<T> {}
+
+    impl<T> PoisonApi for parking_lot::RwLock<T> {}
+}
+
+#[cfg(feature = "spin")]
+mod spin_impl {
+    use super::PoisonApi;
+
+    impl<T, R> PoisonApi for spin::mutex::Mutex<T, R> {}
+
+    impl<T, R> PoisonApi for spin::rwlock::RwLock<T, R> {}
+}
+
+#[cfg(feature = "async-lock")]
+mod async_lock_impl {
+    use super::PoisonApi;
+
+    impl<T> PoisonApi for async_lock::Mutex<T> {}
+
+    impl<T> PoisonApi for async_lock::RwLock<T> {}
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_impl {
+    use super::PoisonApi;
+
+    impl<T> PoisonApi for tokio::sync::Mutex<T> {}
+
+    impl<T> PoisonApi for tokio::sync::RwLock<T> {}
+}
+
+#[cfg(all(feature = "async-std", not(feature = "async-lock")))]
+mod async_std_impl {
+    use super::PoisonApi;
+
+    impl<T> PoisonApi for async_std::sync::Mutex<T> {}
+
+    impl<T> PoisonApi for async_std::sync::RwLock<T> {}
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_feature = "atomics"))]
+mod wasm_impl {
+    use super::PoisonApi;
+    use wasm_sync::{Mutex, RwLock};
+
+    impl<T> PoisonApi for Mutex<T> {
+        fn is_poisoned(&self) -> bool {
+            self.is_poisoned()
+        }
+
+        fn clear_poison(&self) {
+            self.clear_poison()
+        }
+    }
+
+    impl<T> PoisonApi for RwLock<T> {
+        fn is_poisoned(&self) -> bool {
+            self.is_poisoned()
+        }
+
+        fn clear_poison(&self) {
+            self.clear_poison()
+        }
+    }
+}
+
+#[cfg(all(loom, feature = "std-lock"))]
+mod loom_impl {
+    use super::PoisonApi;
+
+    impl<T> PoisonApi for loom::sync::Mutex<T> {}
+
+    impl<T> PoisonApi for loom::sync::RwLock<T> {}
+
+    impl<L> PoisonApi for loom::sync::Arc<L>
+    where
+        L: PoisonApi,
+    {
+        fn is_poisoned(&self) -> bool {
+            (**self).is_poisoned()
+        }
+
+        fn clear_poison(&self) {
+            (**self).clear_poison()
+        }
+    }
+}
+
+#[cfg(feature = "shuttle")]
+mod shuttle_impl {
+    use super::PoisonApi;
+
+    impl<T> PoisonApi for shuttle::sync::Mutex<T> {}
+
+    impl<T> PoisonApi for shuttle::sync::RwLock<T> {}
+}
+
+#[cfg(feature = "std-lock")]
+pub use self::std_impl::{PoisonPolicy, StdMutex, StdRwLock};
+
+#[cfg(feature = "std-lock")]
+mod std_impl {
+    use super::PoisonApi;
+    use crate::{
+        error::{LockError, Result},
+        locking::LockApi,
+    };
+    use std::sync::{LockResult, Mutex, MutexGuard, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+    /// How the std backend wrappers react to a lock poisoned by a panicking holder.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum PoisonPolicy {
+        /// Hand out the guard anyway, leaving the lock poisoned.
+        Ignore,
+        /// Fail with a `LockError`, like the plain std implementations.
+        #[default]
+        Error,
+        /// Hand out the guard and clear the poison flag.
+        Recover,
+    }
+
+    impl PoisonPolicy {
+        fn apply<G>(self, result: LockResult<G>, clear: impl FnOnce()) -> Result<G> {
+            match result {
+                Ok(guard) => Ok(guard),
+                Err(err) => match self {
+                    PoisonPolicy::Ignore => Ok(err.into_inner()),
+                    PoisonPolicy::Error => Err(LockError::Poisoned),
+                    PoisonPolicy::Recover => {
+                        let guard = err.into_inner();
+                        clear();
+                        Ok(guard)
+                    }
+                },
+            }
+        }
+    }
+
+    pub struct StdMutex<T> {
+        inner: Mutex<T>,
+        policy: PoisonPolicy,
+    }
+
+    impl<T> StdMutex<T> {
+        pub const fn new(inner: T) -> StdMutex<T> {
+            StdMutex::with_policy(inner, PoisonPolicy::Error)
+        }
+
+        pub const fn with_policy(inner: T, policy: PoisonPolicy) -> StdMutex<T> {
+            StdMutex {
+                inner: Mutex::new(inner),
+                policy,
+            }
+        }
+
+        pub fn policy(&self) -> PoisonPolicy {
+            self.policy
+        }
+
+        pub fn get_ref(&self) -> &Mutex<T> {
+            &self.inner
+        }
+
+        pub fn into_inner(self) -> Result<T> {
+            self.policy.apply(self.inner.into_inner(), || ())
+        }
+    }
+
+    impl<T: Default> Default for StdMutex<T> {
+        fn default() -> Self {
+            StdMutex::new(T::default())
+        }
+    }
+
+    impl<T> LockApi<T> for StdMutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = MutexGuard<'a, T>;
+
+        type WriteGuard<'a> = MutexGuard<'a, T>;
+
+        fn read(&self) -> Result<Self::ReadGuard<'_>> {
+            self.policy
+                .apply(self.inner.lock(), || self.inner.clear_poison())
+        }
+
+        fn write(&self) -> Result<Self::WriteGuard<'_>> {
+            self.policy
+                .apply(self.inner.lock(), || self.inner.clear_poison())
+        }
+
+        fn new(inner: T) -> Self {
+            StdMutex::new(inner)
+        }
+    }
+
+    pub struct StdRwLock<T> {
+        inner: RwLock<T>,
+        policy: PoisonPolicy,
+    }
+
+    impl<T> StdRwLock<T> {
+        pub const fn new(inner: T) -> StdRwLock<T> {
+            StdRwLock::with_policy(inner, PoisonPolicy::Error)
+        }
+
+        pub const fn with_policy(inner: T, policy: PoisonPolicy) -> StdRwLock<T> {
+            StdRwLock {
+                inner: RwLock::new(inner),
+                policy,
+            }
+        }
+
+        pub fn policy(&self) -> PoisonPolicy {
+            self.policy
+        }
+
+        pub fn get_ref(&self) -> &RwLock<T> {
+            &self.inner
+        }
+
+        pub fn into_inner(self) -> Result<T> {
+            self.policy.apply(self.inner.into_inner(), || ())
+        }
+    }
+
+    impl<T: Default> Default for StdRwLock<T> {
+        fn default() -> Self {
+            StdRwLock::new(T::default())
+        }
+    }
+
+    impl<T> LockApi<T> for StdRwLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = RwLockReadGuard<'a, T>;
+
+        type WriteGuard<'a> = RwLockWriteGuard<'a, T>;
+
+        fn read(&self) -> Result<Self::ReadGuard<'_>> {
+            self.policy
+                .apply(self.inner.read(), || self.inner.clear_poison())
+        }
+
+        fn write(&self) -> Result<Self::WriteGuard<'_>> {
+            self.policy
+                .apply(self.inner.write(), || self.inner.clear_poison())
+        }
+
+        fn new(inner: T) -> Self {
+            StdRwLock::new(inner)
+        }
+    }
+
+    impl<T> PoisonApi for Mutex<T> {
+        fn is_poisoned(&self) -> bool {
+            self.is_poisoned()
+        }
+
+        fn clear_poison(&self) {
+            self.clear_poison()
+        }
+    }
+
+    impl<T> PoisonApi for RwLock<T> {
+        fn is_poisoned(&self) -> bool {
+            self.is_poisoned()
+        }
+
+        fn clear_poison(&self) {
+            self.clear_poison()
+        }
+    }
+
+    impl<T> PoisonApi for StdMutex<T> {
+        fn is_poisoned(&self) -> bool {
+            self.inner.is_poisoned()
+        }
+
+        fn clear_poison(&self) {
+            self.inner.clear_poison()
+        }
+    }
+
+    impl<T> PoisonApi for StdRwLock<T> {
+        fn is_poisoned(&self) -> bool {
+            self.inner.is_poisoned()
+        }
+
+        fn clear_poison(&self) {
+            self.inner.clear_poison()
+        }
+    }
+}
+
+#[cfg(feature = "portable-atomic")]
+mod portable_atomic_impl {
+    use super::PoisonApi;
+    use portable_atomic_util::Arc;
+
+    impl<L> PoisonApi for Arc<L>
+    where
+        L: PoisonApi,
+    {
+        fn is_poisoned(&self) -> bool {
+            (**self).is_poisoned()
+        }
+
+        fn clear_poison(&self) {
+            (**self).clear_poison()
+        }
+    }
+}
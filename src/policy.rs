@@ -0,0 +1,99 @@
+#[cfg(feature = "alloc")]
+use alloc::{rc::Rc, sync::Arc};
+
+/// Who goes first when readers and writers contend for a read-write lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum RwPolicy {
+    /// New readers join active readers even while writers wait. Highest read
+    /// throughput, but a steady stream of readers starves writers.
+    ReadPreferring,
+    /// New readers queue behind waiting writers.
+    #[default]
+    WritePreferring,
+    /// Readers and writers alternate: when a writer releases, the readers
+    /// waiting at that moment go before the next writer.
+    PhaseFair,
+}
+
+/// Reports the preference policy of a read-write lock, so generic code can
+/// require or log it. std's `RwLock` does not implement it since its policy
+/// depends on the platform.
+pub trait RwPolicyApi {
+    fn rw_policy(&self) -> RwPolicy;
+}
+
+#[cfg(feature = "alloc")]
+impl<L> RwPolicyApi for Arc<L>
+where
+    L: RwPolicyApi,
+{
+    fn rw_policy(&self) -> RwPolicy {
+        (**self).rw_policy()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L> RwPolicyApi for Rc<L>
+where
+    L: RwPolicyApi,
+{
+    fn rw_policy(&self) -> RwPolicy {
+        (**self).rw_policy()
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+mod parking_lot_impl {
+    use super::{RwPolicy, RwPolicyApi};
+
+    impl<T> RwPolicyApi for parking_lot::RwLock<T> {
+        fn rw_policy(&self) -> RwPolicy {
+            RwPolicy::WritePreferring
+        }
+    }
+}
+
+#[cfg(feature = "spin")]
+mod spin_impl {
+    use super::{RwPolicy, RwPolicyApi};
+
+    impl<T, R> RwPolicyApi for spin::rwlock::RwLock<T, R> {
+        fn rw_policy(&self) -> RwPolicy {
+            RwPolicy::ReadPreferring
+        }
+    }
+}
+
+#[cfg(feature = "async-lock")]
+mod async_lock_impl {
+    use super::{RwPolicy, RwPolicyApi};
+
+    impl<T> RwPolicyApi for async_lock::RwLock<T> {
+        fn rw_policy(&self) -> RwPolicy {
+            RwPolicy::WritePreferring
+        }
+    }
+}
+
+#[cfg(feature = "tokio")]
+mod tokio_impl {
+    use super::{RwPolicy, RwPolicyApi};
+
+    // Waiters are served in FIFO order, so a waiting writer blocks new readers.
+    impl<T> RwPolicyApi for tokio::sync::RwLock<T> {
+        fn rw_policy(&self) -> RwPolicy {
+            RwPolicy::WritePreferring
+        }
+    }
+}
+
+#[cfg(all(feature = "async-std", not(feature = "async-lock")))]
+mod async_std_impl {
+    use super::{RwPolicy, RwPolicyApi};
+
+    impl<T> RwPolicyApi for async_std::sync::RwLock<T> {
+        fn rw_policy(&self) -> RwPolicy {
+            RwPolicy::WritePreferring
+        }
+    }
+}
@@ -0,0 +1,201 @@
+use alloc::{boxed::Box, sync::Arc};
+use core::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{ready, Context, Poll},
+};
+
+use crate::{
+    async_locking::AsyncLockApi,
+    error::Result,
+    guarded::{ArcReadGuardAsync, ArcWriteGuardAsync, GuardedArc},
+};
+
+/// Poll-style access to an async lock, for hand written `Future` and `Stream`
+/// implementations. Keeps the pending acquisition boxed between polls, so the
+/// caller does not have to name or pin the lock's future types. Guards borrow
+/// the lock, not the `PollLock`, so it can be polled again while one is held.
+pub struct PollLock<'a, T, L>
+where
+    L: AsyncLockApi<T> + 'a,
+{
+    lock: &'a L,
+    read: Option<Pin<Box<L::ReadFuture<'a>>>>,
+    write: Option<Pin<Box<L::WriteFuture<'a>>>>,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<'a, T, L> PollLock<'a, T, L>
+where
+    L: AsyncLockApi<T> + 'a,
+{
+    pub fn new(lock: &'a L) -> PollLock<'a, T, L> {
+        PollLock {
+            lock,
+            read: None,
+            write: None,
+            _value: PhantomData,
+        }
+    }
+
+    pub fn lock(&self) -> &'a L {
+        self.lock
+    }
+
+    pub fn poll_read(&mut self, cx: &mut Context<'_>) -> Poll<Result<L::ReadGuard<'a>>> {
+        let lock = self.lock;
+        let future = self.read.get_or_insert_with(|| Box::pin(lock.read()));
+        let ret = ready!(future.as_mut().poll(cx));
+        self.read = None;
+        Poll::Ready(ret)
+    }
+
+    pub fn poll_write(&mut self, cx: &mut Context<'_>) -> Poll<Result<L::WriteGuard<'a>>> {
+        let lock = self.lock;
+        let future = self.write.get_or_insert_with(|| Box::pin(lock.write()));
+        let ret = ready!(future.as_mut().poll(cx));
+        self.write = None;
+        Poll::Ready(ret)
+    }
+
+    /// Whether an acquisition was started and has not completed yet.
+    pub fn is_pending(&self) -> bool {
+        self.read.is_some() || self.write.is_some()
+    }
+
+    /// Drops any pending acquisition, giving up its place in the queue.
+    pub fn cancel(&mut self) {
+        self.read = None;
+        self.write = None;
+    }
+}
+
+impl<'a, T, L> Clone for PollLock<'a, T, L>
+where
+    L: AsyncLockApi<T> + 'a,
+{
+    fn clone(&self) -> Self {
+        PollLock::new(self.lock)
+    }
+}
+
+impl<'a, T, L> core::fmt::Debug for PollLock<'a, T, L>
+where
+    L: AsyncLockApi<T> + core::fmt::Debug + 'a,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PollLock")
+            .field("lock", &self.lock)
+            .field("read_pending", &self.read.is_some())
+            .field("write_pending", &self.write.is_some())
+            .finish()
+    }
+}
+
+/// Like [`PollLock`], holding the lock through an `Arc` instead of borrowing
+/// it, so it can live in a `'static` future or stream such as a spawned task.
+/// Guards are [`GuardedArc`]s, which keep the lock alive in turn.
+pub struct OwnedPollLock<T, L>
+where
+    L: AsyncLockApi<T> + 'static,
+{
+    // Declared before the lock so pending acquisitions are dropped first.
+    read: Option<Pin<Box<L::ReadFuture<'static>>>>,
+    write: Option<Pin<Box<L::WriteFuture<'static>>>>,
+    lock: Arc<L>,
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T, L> OwnedPollLock<T, L>
+where
+    L: AsyncLockApi<T> + 'static,
+{
+    pub fn new(lock: Arc<L>) -> OwnedPollLock<T, L> {
+        OwnedPollLock {
+            read: None,
+            write: None,
+            lock,
+            _value: PhantomData,
+        }
+    }
+
+    pub fn lock(&self) -> &Arc<L> {
+        &self.lock
+    }
+
+    /// Drops any pending acquisition, giving back the `Arc`.
+    pub fn into_lock(self) -> Arc<L> {
+        let OwnedPollLock {
+            read, write, lock, ..
+        } = self;
+        drop((read, write));
+        lock
+    }
+
+    fn target(&self) -> &'static L {
+        // SAFETY: the value behind an `Arc` does not move. Pending futures
+        // are dropped before `self.lock`, and completed guards are wrapped in
+        // a `GuardedArc` with a clone of it, so the borrow never outlives the
+        // lock.
+        unsafe { &*Arc::as_ptr(&self.lock) }
+    }
+
+    pub fn poll_read(&mut self, cx: &mut Context<'_>) -> Poll<Result<ArcReadGuardAsync<L, T>>> {
+        let lock = self.target();
+        let future = self.read.get_or_insert_with(|| Box::pin(lock.read()));
+        let ret = ready!(future.as_mut().poll(cx));
+        self.read = None;
+        Poll::Ready(ret.map(|guard| GuardedArc::from_parts(guard, self.lock.clone())))
+    }
+
+    pub fn poll_write(&mut self, cx: &mut Context<'_>) -> Poll<Result<ArcWriteGuardAsync<L, T>>> {
+        let lock = self.target();
+        let future = self.write.get_or_insert_with(|| Box::pin(lock.write()));
+        let ret = ready!(future.as_mut().poll(cx));
+        self.write = None;
+        Poll::Ready(ret.map(|guard| GuardedArc::from_parts(guard, self.lock.clone())))
+    }
+
+    /// Whether an acquisition was started and has not completed yet.
+    pub fn is_pending(&self) -> bool {
+        self.read.is_some() || self.write.is_some()
+    }
+
+    /// Drops any pending acquisition, giving up its place in the queue.
+    pub fn cancel(&mut self) {
+        self.read = None;
+        self.write = None;
+    }
+}
+
+impl<T, L> Clone for OwnedPollLock<T, L>
+where
+    L: AsyncLockApi<T> + 'static,
+{
+    fn clone(&self) -> Self {
+        OwnedPollLock::new(self.lock.clone())
+    }
+}
+
+impl<T, L> From<Arc<L>> for OwnedPollLock<T, L>
+where
+    L: AsyncLockApi<T> + 'static,
+{
+    fn from(lock: Arc<L>) -> Self {
+        OwnedPollLock::new(lock)
+    }
+}
+
+impl<T, L> core::fmt::Debug for OwnedPollLock<T, L>
+where
+    L: AsyncLockApi<T> + core::fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OwnedPollLock")
+            .field("lock", &self.lock)
+            .field("read_pending", &self.read.is_some())
+            .field("write_pending", &self.write.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,16 @@
+// `Lockable`, `TryLockable` and `LockStats` are left out: their methods would
+// shadow the inherent ones of the same name when called on an `Arc<Mutex<T>>`.
+pub use crate::{
+    Downgrade, FairLock, IntoInner, Leak, LockApi, LockApiFairGuard, LockApiReadGuard,
+    LockApiWriteGuard, Locket, OnceApi, PoisonApi, ReentrantLockApi, RwPolicyApi, StaticLockApi,
+    TransactionApi, TryLockApi, TryUnwrap, Upgrade,
+};
+
+#[cfg(feature = "std")]
+pub use crate::TimedLockApi;
+
+#[cfg(feature = "distributed")]
+pub use crate::AsyncDistributedLock;
+
+#[cfg(feature = "async")]
+pub use crate::{AsyncCondvarApi, AsyncEventApi, AsyncLockApi, AsyncLocket, AsyncOnceApi};
@@ -0,0 +1,192 @@
+use alloc::{boxed::Box, collections::VecDeque};
+use core::ops::{Deref, DerefMut};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::{
+    error::{LockError, Result},
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    try_lock::TryLockApi,
+};
+
+type Mutation<T> = Box<dyn FnOnce(&mut T) + Send>;
+
+/// Lets writers enqueue mutations instead of waiting for the lock. A queued
+/// mutation is applied right away when the lock is free, otherwise by whoever
+/// holds it, when their guard is dropped.
+pub struct Queued<L, T> {
+    inner: L,
+    queue: Mutex<VecDeque<Mutation<T>>>,
+}
+
+impl<L, T> Queued<L, T> {
+    pub fn wrap(inner: L) -> Queued<L, T> {
+        Queued {
+            inner,
+            queue: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Number of mutations waiting to be applied.
+    pub fn pending(&self) -> usize {
+        self.queue().len()
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    fn queue(&self) -> MutexGuard<'_, VecDeque<Mutation<T>>> {
+        self.queue.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn apply(&self, value: &mut T) {
+        loop {
+            // Pop in a statement of its own so the queue is unlocked while the
+            // mutation runs and may enqueue more.
+            let next = self.queue().pop_front();
+            match next {
+                Some(mutation) => mutation(value),
+                None => break,
+            }
+        }
+    }
+}
+
+impl<L, T> Queued<L, T>
+where
+    L: TryLockApi<T>,
+{
+    /// Enqueues `mutation` without blocking on the lock.
+    pub fn send<F>(&self, mutation: F) -> Result<()>
+    where
+        F: FnOnce(&mut T) + Send + 'static,
+    {
+        self.queue().push_back(Box::new(mutation));
+        self.try_flush()
+    }
+
+    /// Blocks until every queued mutation has been applied.
+    pub fn flush(&self) -> Result<()> {
+        let mut guard = self.inner.write()?;
+        self.apply(guard.get_mut());
+        Ok(())
+    }
+
+    fn try_flush(&self) -> Result<()> {
+        // Recheck after releasing: a mutation pushed while we held the lock
+        // may have failed to acquire it and relies on us to apply it.
+        while self.pending() > 0 {
+            match self.inner.try_write() {
+                Ok(mut guard) => self.apply(guard.get_mut()),
+                Err(LockError::WouldBlock) => break,
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+}
+
+trait Flush {
+    fn try_flush(&self);
+}
+
+impl<L, T> Flush for Queued<L, T>
+where
+    L: TryLockApi<T>,
+{
+    fn try_flush(&self) {
+        // A poisoned lock is reported to the next caller of `send` or `flush`.
+        let _ = Queued::try_flush(self);
+    }
+}
+
+impl<L, T> LockApi<T> for Queued<L, T>
+where
+    L: TryLockApi<T>,
+{
+    type ReadGuard<'a>
+        = QueuedGuard<'a, L::ReadGuard<'a>>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = QueuedGuard<'a, L::WriteGuard<'a>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        Ok(QueuedGuard {
+            guard: Some(self.inner.read()?),
+            queue: self,
+        })
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        let mut guard = self.inner.write()?;
+        // Apply what is already queued so the writer sees the latest state.
+        self.apply(guard.get_mut());
+        Ok(QueuedGuard {
+            guard: Some(guard),
+            queue: self,
+        })
+    }
+
+    fn new(inner: T) -> Self {
+        Queued::wrap(L::new(inner))
+    }
+}
+
+/// Applies mutations queued while it was held once it is dropped.
+pub struct QueuedGuard<'a, G> {
+    guard: Option<G>,
+    queue: &'a dyn Flush,
+}
+
+impl<G> Drop for QueuedGuard<'_, G> {
+    fn drop(&mut self) {
+        drop(self.guard.take());
+        self.queue.try_flush();
+    }
+}
+
+impl<G> Deref for QueuedGuard<'_, G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard.as_ref().unwrap()
+    }
+}
+
+impl<G> DerefMut for QueuedGuard<'_, G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard.as_mut().unwrap()
+    }
+}
+
+impl<'a, T, G> LockApiReadGuard<'a, T> for QueuedGuard<'a, G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.as_ref().unwrap().get()
+    }
+}
+
+impl<'a, T, G> LockApiWriteGuard<'a, T> for QueuedGuard<'a, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.as_mut().unwrap().get_mut()
+    }
+}
@@ -0,0 +1,378 @@
+use core::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{
+    backoff::Backoff,
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    stats::LockStats,
+    try_lock::TryLockApi,
+};
+
+/// A read-write lock without guards or data. Implement this for a new
+/// backend and wrap it in [`RawLocket`] to get the guard-based [`LockApi`],
+/// or call it directly where guards cannot be used, e.g. across FFI.
+///
+/// # Safety
+///
+/// Implementations must actually exclude: while `lock` or a successful
+/// `try_lock` is outstanding no other lock may be taken, and while a shared
+/// lock is outstanding no exclusive lock may be taken.
+pub unsafe trait RawLockApi {
+    /// An unlocked lock.
+    const INIT: Self;
+
+    /// Takes a shared lock, blocking until it is available.
+    fn lock_shared(&self);
+
+    fn try_lock_shared(&self) -> bool;
+
+    /// # Safety
+    ///
+    /// A shared lock must be held by the caller's context.
+    unsafe fn unlock_shared(&self);
+
+    /// Takes the exclusive lock, blocking until it is available.
+    fn lock(&self);
+
+    fn try_lock(&self) -> bool;
+
+    /// # Safety
+    ///
+    /// The exclusive lock must be held by the caller's context.
+    unsafe fn unlock(&self);
+
+    fn is_locked(&self) -> bool;
+
+    fn is_locked_exclusive(&self) -> bool;
+}
+
+/// A spinning [`RawLockApi`] in a single word.
+#[derive(Debug)]
+pub struct RawSpinRwLock {
+    // `WRITER` when exclusively locked, else the number of readers.
+    state: AtomicUsize,
+}
+
+const WRITER: usize = usize::MAX;
+
+impl RawSpinRwLock {
+    pub const fn new() -> RawSpinRwLock {
+        RawSpinRwLock {
+            state: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl Default for RawSpinRwLock {
+    fn default() -> Self {
+        RawSpinRwLock::new()
+    }
+}
+
+unsafe impl RawLockApi for RawSpinRwLock {
+    const INIT: Self = RawSpinRwLock::new();
+
+    fn lock_shared(&self) {
+        let mut snooze = Backoff::Spin.start();
+        while !self.try_lock_shared() {
+            snooze.snooze();
+        }
+    }
+
+    fn try_lock_shared(&self) -> bool {
+        let mut state = self.state.load(Ordering::Relaxed);
+        // One below `WRITER`, so a reader count never reads as locked.
+        while state < WRITER - 1 {
+            match self.state.compare_exchange_weak(
+                state,
+                state + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return true,
+                Err(current) => state = current,
+            }
+        }
+        false
+    }
+
+    unsafe fn unlock_shared(&self) {
+        self.state.fetch_sub(1, Ordering::Release);
+    }
+
+    fn lock(&self) {
+        let mut snooze = Backoff::Spin.start();
+        while !self.try_lock() {
+            snooze.snooze();
+        }
+    }
+
+    fn try_lock(&self) -> bool {
+        self.state
+            .compare_exchange(0, WRITER, Ordering::Acquire, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    unsafe fn unlock(&self) {
+        self.state.store(0, Ordering::Release);
+    }
+
+    fn is_locked(&self) -> bool {
+        self.state.load(Ordering::Relaxed) != 0
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        self.state.load(Ordering::Relaxed) == WRITER
+    }
+}
+
+/// The guard-based lock over a [`RawLockApi`].
+pub struct RawLocket<R, T: ?Sized> {
+    raw: R,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<R: RawLockApi + Send, T: ?Sized + Send> Send for RawLocket<R, T> {}
+unsafe impl<R: RawLockApi + Sync, T: ?Sized + Send + Sync> Sync for RawLocket<R, T> {}
+
+impl<R: RawLockApi, T> RawLocket<R, T> {
+    pub const fn new(inner: T) -> RawLocket<R, T> {
+        RawLocket::from_raw(R::INIT, inner)
+    }
+
+    pub const fn from_raw(raw: R, inner: T) -> RawLocket<R, T> {
+        RawLocket {
+            raw,
+            data: UnsafeCell::new(inner),
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<R: RawLockApi, T: ?Sized> RawLocket<R, T> {
+    /// The underlying lock, for locking by hand. While holding it, reach the
+    /// value through [`data_ptr`](RawLocket::data_ptr).
+    pub fn raw(&self) -> &R {
+        &self.raw
+    }
+
+    pub fn data_ptr(&self) -> *mut T {
+        self.data.get()
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    /// # Safety
+    ///
+    /// A shared lock must be held through [`raw`](RawLocket::raw); the guard
+    /// releases it.
+    pub unsafe fn make_read_guard_unchecked(&self) -> RawReadGuard<'_, R, T> {
+        RawReadGuard {
+            lock: self,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// # Safety
+    ///
+    /// The exclusive lock must be held through [`raw`](RawLocket::raw); the
+    /// guard releases it.
+    pub unsafe fn make_write_guard_unchecked(&self) -> RawWriteGuard<'_, R, T> {
+        RawWriteGuard {
+            lock: self,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl<R: RawLockApi, T: Default> Default for RawLocket<R, T> {
+    fn default() -> Self {
+        RawLocket::new(T::default())
+    }
+}
+
+impl<R, T> LockApi<T> for RawLocket<R, T>
+where
+    R: RawLockApi,
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a>
+        = RawReadGuard<'a, R, T>
+    where
+        R: 'a;
+
+    type WriteGuard<'a>
+        = RawWriteGuard<'a, R, T>
+    where
+        R: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.raw.lock_shared();
+        Ok(RawReadGuard {
+            lock: self,
+            _not_send: PhantomData,
+        })
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.raw.lock();
+        Ok(RawWriteGuard {
+            lock: self,
+            _not_send: PhantomData,
+        })
+    }
+
+    fn new(inner: T) -> Self {
+        RawLocket::new(inner)
+    }
+}
+
+impl<R, T> TryLockApi<T> for RawLocket<R, T>
+where
+    R: RawLockApi,
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        match self.raw.try_lock_shared() {
+            true => Ok(RawReadGuard {
+                lock: self,
+                _not_send: PhantomData,
+            }),
+            false => Err(LockError::WouldBlock),
+        }
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        match self.raw.try_lock() {
+            true => Ok(RawWriteGuard {
+                lock: self,
+                _not_send: PhantomData,
+            }),
+            false => Err(LockError::WouldBlock),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, T> crate::timed::TimedLockApi<T> for RawLocket<R, T>
+where
+    R: RawLockApi,
+    for<'a> T: 'a,
+{
+}
+
+impl<R: RawLockApi, T> IntoInner<T> for RawLocket<R, T> {
+    fn into_inner(self) -> Result<T> {
+        Ok(RawLocket::into_inner(self))
+    }
+}
+
+impl<R: RawLockApi, T: ?Sized> PoisonApi for RawLocket<R, T> {}
+
+impl<R: RawLockApi, T: ?Sized> LockStats for RawLocket<R, T> {
+    fn is_locked(&self) -> bool {
+        self.raw.is_locked()
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        self.raw.is_locked_exclusive()
+    }
+}
+
+impl<R, T> core::fmt::Debug for RawLocket<R, T>
+where
+    R: RawLockApi,
+    T: core::fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RawLocket")
+            .field("data", &crate::peek::peek(self))
+            .finish()
+    }
+}
+
+/// Not `Send`: backends may require the lock to be released on the thread
+/// which took it.
+pub struct RawReadGuard<'a, R: RawLockApi, T: ?Sized> {
+    lock: &'a RawLocket<R, T>,
+    _not_send: PhantomData<*const ()>,
+}
+
+unsafe impl<R: RawLockApi + Sync, T: ?Sized + Sync> Sync for RawReadGuard<'_, R, T> {}
+
+impl<R: RawLockApi, T: ?Sized> Drop for RawReadGuard<'_, R, T> {
+    fn drop(&mut self) {
+        // SAFETY: the guard holds a shared lock.
+        unsafe { self.lock.raw.unlock_shared() }
+    }
+}
+
+impl<R: RawLockApi, T: ?Sized> Deref for RawReadGuard<'_, R, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: shared locks exclude the exclusive one.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<'a, R: RawLockApi, T> LockApiReadGuard<'a, T> for RawReadGuard<'a, R, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+/// Not `Send`, like [`RawReadGuard`].
+pub struct RawWriteGuard<'a, R: RawLockApi, T: ?Sized> {
+    lock: &'a RawLocket<R, T>,
+    _not_send: PhantomData<*const ()>,
+}
+
+unsafe impl<R: RawLockApi + Sync, T: ?Sized + Sync> Sync for RawWriteGuard<'_, R, T> {}
+
+impl<R: RawLockApi, T: ?Sized> Drop for RawWriteGuard<'_, R, T> {
+    fn drop(&mut self) {
+        // SAFETY: the guard holds the exclusive lock.
+        unsafe { self.lock.raw.unlock() }
+    }
+}
+
+impl<R: RawLockApi, T: ?Sized> Deref for RawWriteGuard<'_, R, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the exclusive lock excludes every other guard.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<R: RawLockApi, T: ?Sized> DerefMut for RawWriteGuard<'_, R, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: as above.
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<'a, R: RawLockApi, T> LockApiReadGuard<'a, T> for RawWriteGuard<'a, R, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, R: RawLockApi, T> LockApiWriteGuard<'a, T> for RawWriteGuard<'a, R, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self
+    }
+}
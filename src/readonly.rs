@@ -0,0 +1,176 @@
+use core::{
+    convert::Infallible,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    stats::LockStats,
+    try_lock::TryLockApi,
+};
+
+/// A "lock" around a value which never changes. Reads hand out a plain
+/// reference, writes fail with [`LockError::ReadOnly`]. Use it to pass
+/// immutable data through code generic over [`LockApi`] without paying for
+/// synchronization.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ReadOnlyLock<T> {
+    value: T,
+}
+
+impl<T> ReadOnlyLock<T> {
+    pub const fn new(inner: T) -> ReadOnlyLock<T> {
+        ReadOnlyLock { value: inner }
+    }
+
+    pub const fn get(&self) -> &T {
+        &self.value
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> LockApi<T> for ReadOnlyLock<T>
+where
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = &'a T;
+
+    type WriteGuard<'a> = ReadOnlyWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        Ok(&self.value)
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        Err(LockError::ReadOnly)
+    }
+
+    fn new(inner: T) -> Self {
+        ReadOnlyLock::new(inner)
+    }
+}
+
+impl<T> TryLockApi<T> for ReadOnlyLock<T>
+where
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        Ok(&self.value)
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        Err(LockError::ReadOnly)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> crate::timed::TimedLockApi<T> for ReadOnlyLock<T> where for<'a> T: 'a {}
+
+impl<T> IntoInner<T> for ReadOnlyLock<T> {
+    fn into_inner(self) -> Result<T> {
+        Ok(self.value)
+    }
+}
+
+impl<T> PoisonApi for ReadOnlyLock<T> {}
+
+// Reads take no lock, and nothing can write.
+impl<T> LockStats for ReadOnlyLock<T> {
+    fn is_locked(&self) -> bool {
+        false
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        false
+    }
+}
+
+impl<T> core::fmt::Debug for ReadOnlyLock<T>
+where
+    T: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ReadOnlyLock")
+            .field("data", &self.value)
+            .finish()
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for &'a T {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+/// The write guard of [`ReadOnlyLock`]. It cannot be constructed.
+pub struct ReadOnlyWriteGuard<'a, T> {
+    never: Infallible,
+    _lock: PhantomData<&'a mut T>,
+}
+
+impl<T> Deref for ReadOnlyWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        match self.never {}
+    }
+}
+
+impl<T> DerefMut for ReadOnlyWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        match self.never {}
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for ReadOnlyWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        match self.never {}
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for ReadOnlyWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        match self.never {}
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{ReadOnlyLock, ReadOnlyWriteGuard};
+    use crate::{
+        async_locking::AsyncLockApi,
+        error::{LockError, Result},
+    };
+
+    impl<T> AsyncLockApi<T> for ReadOnlyLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = &'a T;
+
+        type WriteGuard<'a> = ReadOnlyWriteGuard<'a, T>;
+
+        type ReadFuture<'a> = core::future::Ready<Result<Self::ReadGuard<'a>>>;
+
+        type WriteFuture<'a> = core::future::Ready<Result<Self::WriteGuard<'a>>>;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            core::future::ready(Ok(&self.value))
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            core::future::ready(Err(LockError::ReadOnly))
+        }
+
+        fn new(inner: T) -> Self {
+            ReadOnlyLock::new(inner)
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use alloc::vec::Vec;
+use core::{
+    cell::RefCell,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    error::Result,
+    locking::{AccessMode, LockApi, LockApiReadGuard, LockApiWriteGuard},
+    policy::{RwPolicy, RwPolicyApi},
+    try_lock::TryLockApi,
+};
+
+std::thread_local! {
+    static HELD: RefCell<Vec<(usize, AccessMode)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn report(message: core::fmt::Arguments<'_>) {
+    if cfg!(debug_assertions) {
+        panic!("{message}");
+    } else {
+        std::eprintln!("{message}");
+    }
+}
+
+fn check(lock: usize, name: &'static str, mode: AccessMode, shared_reads: bool) {
+    let held = HELD.with(|held| {
+        held.borrow()
+            .iter()
+            .rev()
+            .find(|(held, _)| *held == lock)
+            .map(|(_, mode)| *mode)
+    });
+    match (held, mode) {
+        (None, _) => {}
+        (Some(AccessMode::Read), AccessMode::Read) if shared_reads => {}
+        (Some(AccessMode::Read), AccessMode::Read) => report(format_args!(
+            "recursive read of {name}: deadlocks if a writer queues in between"
+        )),
+        (Some(held), _) => report(format_args!(
+            "recursive {} of {name} while holding it for {}: deadlocks",
+            mode.as_str(),
+            held.as_str()
+        )),
+    }
+}
+
+fn push(lock: usize, mode: AccessMode) {
+    HELD.with(|held| held.borrow_mut().push((lock, mode)));
+}
+
+fn pop(lock: usize) {
+    HELD.with(|held| {
+        let mut held = held.borrow_mut();
+        if let Some(idx) = held.iter().rposition(|(held, _)| *held == lock) {
+            held.remove(idx);
+        }
+    });
+}
+
+/// Catches a thread taking a lock it already holds, which hangs on most
+/// backends: a second write, a write under a read, or a second read, which
+/// deadlocks once a writer queues in between unless the lock is
+/// read-preferring. Panics in debug builds and logs otherwise.
+///
+/// Second reads are reported unless the lock is built with
+/// [`with_rw_policy`](RecursionChecked::with_rw_policy) and reports
+/// [`RwPolicy::ReadPreferring`].
+///
+/// Locks are told apart by the [`lock_id`](LockApi::lock_id) of the inner
+/// lock, so checkers around clones of one `Arc` see each other's guards.
+/// Held locks are tracked per thread, so guards held across `.await` in
+/// multi-threaded executors are not followed.
+pub struct RecursionChecked<L> {
+    inner: L,
+    name: &'static str,
+    policy: Option<fn(&L) -> RwPolicy>,
+}
+
+impl<L> RecursionChecked<L> {
+    pub const fn with_name(inner: L, name: &'static str) -> RecursionChecked<L> {
+        RecursionChecked {
+            inner,
+            name,
+            policy: None,
+        }
+    }
+
+    /// Like [`with_name`](RecursionChecked::with_name), but asks the lock
+    /// whether second reads can deadlock.
+    pub fn with_rw_policy(inner: L, name: &'static str) -> RecursionChecked<L>
+    where
+        L: RwPolicyApi,
+    {
+        RecursionChecked {
+            inner,
+            name,
+            policy: Some(L::rw_policy),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    fn shared_reads(&self) -> bool {
+        self.policy
+            .is_some_and(|policy| policy(&self.inner) == RwPolicy::ReadPreferring)
+    }
+
+    fn acquire<'a, G>(
+        &'a self,
+        id: usize,
+        mode: AccessMode,
+        check_first: bool,
+        lock: impl FnOnce(&'a L) -> Result<G>,
+    ) -> Result<RecursionGuard<G>> {
+        if check_first {
+            check(id, self.name, mode, self.shared_reads());
+        }
+        let guard = lock(&self.inner)?;
+        push(id, mode);
+        Ok(RecursionGuard { guard, lock: id })
+    }
+}
+
+impl<L, T> LockApi<T> for RecursionChecked<L>
+where
+    L: LockApi<T>,
+{
+    type ReadGuard<'a>
+        = RecursionGuard<L::ReadGuard<'a>>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = RecursionGuard<L::WriteGuard<'a>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.acquire(self.inner.lock_id(), AccessMode::Read, true, |lock| {
+            lock.read()
+        })
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.acquire(self.inner.lock_id(), AccessMode::Write, true, |lock| {
+            lock.write()
+        })
+    }
+
+    fn new(inner: T) -> Self {
+        RecursionChecked::with_name(L::new(inner), core::any::type_name::<L>())
+    }
+}
+
+impl<L> RwPolicyApi for RecursionChecked<L>
+where
+    L: RwPolicyApi,
+{
+    fn rw_policy(&self) -> RwPolicy {
+        self.inner.rw_policy()
+    }
+}
+
+// Try-locks cannot hang, so they are not reported.
+impl<L, T> TryLockApi<T> for RecursionChecked<L>
+where
+    L: TryLockApi<T>,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.acquire(self.inner.lock_id(), AccessMode::Read, false, |lock| {
+            lock.try_read()
+        })
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.acquire(self.inner.lock_id(), AccessMode::Write, false, |lock| {
+            lock.try_write()
+        })
+    }
+}
+
+pub struct RecursionGuard<G> {
+    guard: G,
+    lock: usize,
+}
+
+impl<G> Drop for RecursionGuard<G> {
+    fn drop(&mut self) {
+        pop(self.lock);
+    }
+}
+
+impl<G> Deref for RecursionGuard<G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<G> DerefMut for RecursionGuard<G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, T, G> LockApiReadGuard<'a, T> for RecursionGuard<G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.get()
+    }
+}
+
+impl<'a, T, G> LockApiWriteGuard<'a, T> for RecursionGuard<G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.get_mut()
+    }
+}
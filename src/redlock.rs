@@ -0,0 +1,247 @@
+use alloc::{format, string::String, vec::Vec};
+use core::{
+    hash::{BuildHasher, Hasher},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use std::{collections::hash_map::RandomState, time::Instant};
+
+use redis::{aio::MultiplexedConnection, IntoConnectionInfo, Script};
+
+use crate::{
+    distributed::{AsyncDistributedLock, Lease},
+    error::{LockError, Result},
+};
+
+// Returns one more than this node's fencing counter, or 0 if the key is taken.
+const ACQUIRE: &str = r#"
+if redis.call('SET', KEYS[1], ARGV[1], 'NX', 'PX', ARGV[2]) then
+    return (tonumber(redis.call('GET', KEYS[2])) or 0) + 1
+end
+return 0
+"#;
+
+// Raises the fencing counter to the token in ARGV[2] while the key is held.
+const FENCE: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    if (tonumber(redis.call('GET', KEYS[2])) or 0) < tonumber(ARGV[2]) then
+        redis.call('SET', KEYS[2], ARGV[2])
+    end
+    return 1
+end
+return 0
+"#;
+
+const REFRESH: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+const RELEASE: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+// Unique enough to tell holders apart; it does not need to be secret.
+fn owner() -> String {
+    static NEXT: AtomicU64 = AtomicU64::new(0);
+    let mut hasher = RandomState::new().build_hasher();
+    hasher.write_u64(NEXT.fetch_add(1, Ordering::Relaxed));
+    format!("{}-{:016x}", std::process::id(), hasher.finish())
+}
+
+// The hash tag keeps the counter in the key's cluster slot, which scripts
+// touching both require.
+fn fence_key(key: &str) -> String {
+    format!("{{{key}}}:fence")
+}
+
+// Allowance for clock drift between the nodes, as suggested by Redlock.
+fn drift(ttl: Duration) -> Duration {
+    ttl / 100 + Duration::from_millis(2)
+}
+
+fn millis(ttl: Duration) -> u64 {
+    ttl.as_millis().try_into().unwrap_or(u64::MAX).max(1)
+}
+
+// How many nodes agreed, out of how many answered.
+struct Votes {
+    granted: usize,
+    reachable: usize,
+}
+
+/// [`AsyncDistributedLock`] on Redis. With a single node this is the usual
+/// `SET NX PX` lock; with several independent nodes it follows Redlock and
+/// holds the lock while a majority of them agree.
+///
+/// Fencing tokens come from a counter next to the key (`{<key>}:fence`). A new
+/// token is one more than the largest counter among the granting nodes, and is
+/// written back to them before the lease is handed out. Any two majorities
+/// share a node, so tokens grow as long as a majority keeps its data.
+pub struct RedisLock {
+    nodes: Vec<MultiplexedConnection>,
+    retry_delay: Duration,
+    acquire: Script,
+    fence: Script,
+    refresh: Script,
+    release: Script,
+}
+
+impl RedisLock {
+    pub const DEFAULT_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+    /// # Panics
+    ///
+    /// Panics if `nodes` is empty.
+    pub fn new(nodes: Vec<MultiplexedConnection>) -> RedisLock {
+        assert!(!nodes.is_empty(), "RedisLock needs at least one node");
+        RedisLock {
+            nodes,
+            retry_delay: RedisLock::DEFAULT_RETRY_DELAY,
+            acquire: Script::new(ACQUIRE),
+            fence: Script::new(FENCE),
+            refresh: Script::new(REFRESH),
+            release: Script::new(RELEASE),
+        }
+    }
+
+    pub async fn connect<I>(urls: I) -> Result<RedisLock>
+    where
+        I: IntoIterator,
+        I::Item: IntoConnectionInfo,
+    {
+        let mut nodes = Vec::new();
+        for url in urls {
+            let client = redis::Client::open(url).map_err(|_| LockError::Unavailable)?;
+            let node = client
+                .get_multiplexed_async_connection()
+                .await
+                .map_err(|_| LockError::Unavailable)?;
+            nodes.push(node);
+        }
+        Ok(RedisLock::new(nodes))
+    }
+
+    /// How long [`AsyncDistributedLock::acquire`] waits between attempts.
+    pub fn retry_delay(&self) -> Duration {
+        self.retry_delay
+    }
+
+    pub fn set_retry_delay(&mut self, retry_delay: Duration) {
+        self.retry_delay = retry_delay;
+    }
+
+    fn quorum(&self) -> usize {
+        self.nodes.len() / 2 + 1
+    }
+
+    // Runs `script` on every node with the owner and `arg`. `granted` counts
+    // the positive replies, `max` is the largest reply.
+    async fn vote(&self, script: &Script, key: &str, owner: &str, arg: u64) -> (Votes, u64) {
+        let mut votes = Votes {
+            granted: 0,
+            reachable: 0,
+        };
+        let mut max = 0;
+        for node in &self.nodes {
+            let reply = script
+                .key(key)
+                .key(fence_key(key))
+                .arg(owner)
+                .arg(arg)
+                .invoke_async::<u64>(&mut node.clone())
+                .await;
+            if let Ok(reply) = reply {
+                votes.reachable += 1;
+                if reply > 0 {
+                    votes.granted += 1;
+                    max = max.max(reply);
+                }
+            }
+        }
+        (votes, max)
+    }
+
+    fn outcome(&self, votes: Votes) -> Result<()> {
+        if votes.granted >= self.quorum() {
+            Ok(())
+        } else if votes.reachable < self.quorum() {
+            Err(LockError::Unavailable)
+        } else {
+            Err(LockError::Expired)
+        }
+    }
+}
+
+impl AsyncDistributedLock for RedisLock {
+    async fn acquire(&self, key: &str, ttl: Duration) -> Result<Lease> {
+        loop {
+            if let Some(lease) = self.try_acquire(key, ttl).await? {
+                return Ok(lease);
+            }
+            tokio::time::sleep(self.retry_delay).await;
+        }
+    }
+
+    async fn try_acquire(&self, key: &str, ttl: Duration) -> Result<Option<Lease>> {
+        let owner = owner();
+        let start = Instant::now();
+        let (votes, fencing_token) = self.vote(&self.acquire, key, &owner, millis(ttl)).await;
+        // The token has to reach a majority, so the next holder sees it.
+        let fenced = votes.granted >= self.quorum() && {
+            let (fence, _) = self.vote(&self.fence, key, &owner, fencing_token).await;
+            fence.granted >= self.quorum()
+        };
+
+        // Time spent asking the nodes counts against the lease.
+        let valid = ttl
+            .checked_sub(start.elapsed() + drift(ttl))
+            .filter(|valid| !valid.is_zero());
+        if let (true, Some(valid)) = (fenced, valid) {
+            return Ok(Some(Lease::new(
+                key.into(),
+                owner,
+                fencing_token,
+                start + valid,
+            )));
+        }
+
+        // Undo partial grants so the key frees up before the TTL runs out.
+        self.vote(&self.release, key, &owner, 0).await;
+        if votes.reachable < self.quorum() {
+            return Err(LockError::Unavailable);
+        }
+        Ok(None)
+    }
+
+    async fn refresh(&self, lease: &mut Lease, ttl: Duration) -> Result<()> {
+        let start = Instant::now();
+        let (votes, _) = self
+            .vote(&self.refresh, lease.key(), lease.owner(), millis(ttl))
+            .await;
+        self.outcome(votes)?;
+        lease.set_expires_at(start + ttl.saturating_sub(drift(ttl)));
+        Ok(())
+    }
+
+    async fn release(&self, lease: Lease) -> Result<()> {
+        let (votes, _) = self
+            .vote(&self.release, lease.key(), lease.owner(), 0)
+            .await;
+        self.outcome(votes)
+    }
+}
+
+impl core::fmt::Debug for RedisLock {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RedisLock")
+            .field("nodes", &self.nodes.len())
+            .field("retry_delay", &self.retry_delay)
+            .finish()
+    }
+}
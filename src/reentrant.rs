@@ -0,0 +1,284 @@
+#[cfg(feature = "alloc")]
+use alloc::{rc::Rc, sync::Arc};
+
+use crate::{error::Result, locking::LockApiReadGuard};
+
+pub trait ReentrantLockApi<T> {
+    type Guard<'a>: LockApiReadGuard<'a, T>
+    where
+        Self: 'a;
+
+    fn lock(&self) -> Result<Self::Guard<'_>>;
+
+    fn new(inner: T) -> Self;
+}
+
+#[cfg(feature = "alloc")]
+impl<L, T> ReentrantLockApi<T> for Arc<L>
+where
+    L: ReentrantLockApi<T>,
+    for<'a> L: 'a,
+{
+    type Guard<'a> = L::Guard<'a>;
+
+    fn lock(&self) -> Result<Self::Guard<'_>> {
+        (**self).lock()
+    }
+
+    fn new(inner: T) -> Self {
+        Arc::new(L::new(inner))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L, T> ReentrantLockApi<T> for Rc<L>
+where
+    L: ReentrantLockApi<T>,
+    for<'a> L: 'a,
+{
+    type Guard<'a> = L::Guard<'a>;
+
+    fn lock(&self) -> Result<Self::Guard<'_>> {
+        (**self).lock()
+    }
+
+    fn new(inner: T) -> Self {
+        Rc::new(L::new(inner))
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+mod parking_lot_impl {
+    use super::ReentrantLockApi;
+    use crate::{
+        error::Result,
+        locking::{LockApiFairGuard, LockApiReadGuard},
+    };
+    use core::ops::Deref;
+    use parking_lot::{ReentrantMutex, ReentrantMutexGuard};
+
+    impl<'a, T> LockApiReadGuard<'a, T> for ReentrantMutexGuard<'a, T> {
+        fn get(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<'a, T> LockApiFairGuard<'a, T> for ReentrantMutexGuard<'a, T> {
+        fn unlock_fair(self) {
+            ReentrantMutexGuard::unlock_fair(self)
+        }
+
+        fn bump(&mut self) {
+            ReentrantMutexGuard::bump(self)
+        }
+    }
+
+    impl<T> ReentrantLockApi<T> for ReentrantMutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        type Guard<'a> = ReentrantMutexGuard<'a, T>;
+
+        fn lock(&self) -> Result<Self::Guard<'_>> {
+            Ok(self.lock())
+        }
+
+        fn new(inner: T) -> Self {
+            ReentrantMutex::new(inner)
+        }
+    }
+}
+
+// `std::sync::ReentrantLock` is still unstable.
+#[cfg(feature = "nightly")]
+mod nightly_impl {
+    use super::ReentrantLockApi;
+    use crate::{error::Result, locking::LockApiReadGuard};
+    use core::ops::Deref;
+    use std::sync::{ReentrantLock, ReentrantLockGuard};
+
+    impl<'a, T> LockApiReadGuard<'a, T> for ReentrantLockGuard<'a, T> {
+        fn get(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<T> ReentrantLockApi<T> for ReentrantLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        type Guard<'a> = ReentrantLockGuard<'a, T>;
+
+        fn lock(&self) -> Result<Self::Guard<'_>> {
+            Ok(self.lock())
+        }
+
+        fn new(inner: T) -> Self {
+            ReentrantLock::new(inner)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+pub use self::std_impl::{ReentrantMutex, ReentrantMutexGuard};
+
+#[cfg(feature = "std")]
+mod std_impl {
+    use super::ReentrantLockApi;
+    use crate::{error::Result, locking::LockApiReadGuard};
+    use core::{
+        cell::Cell,
+        marker::PhantomData,
+        ops::Deref,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+    use std::sync::{Condvar, Mutex, PoisonError};
+
+    fn current_thread() -> usize {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+        std::thread_local! {
+            static THREAD_ID: usize = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        }
+        THREAD_ID.with(|id| *id)
+    }
+
+    /// A mutex which can be locked multiple times by the thread holding it.
+    /// Ownership is tracked by thread id, so only shared access is handed out.
+    pub struct ReentrantMutex<T> {
+        locked: Mutex<bool>,
+        available: Condvar,
+        owner: AtomicUsize,
+        count: Cell<usize>,
+        data: T,
+    }
+
+    unsafe impl<T: Send> Send for ReentrantMutex<T> {}
+    unsafe impl<T: Send> Sync for ReentrantMutex<T> {}
+
+    impl<T> ReentrantMutex<T> {
+        pub const fn new(inner: T) -> ReentrantMutex<T> {
+            ReentrantMutex {
+                locked: Mutex::new(false),
+                available: Condvar::new(),
+                owner: AtomicUsize::new(0),
+                count: Cell::new(0),
+                data: inner,
+            }
+        }
+
+        pub fn lock(&self) -> ReentrantMutexGuard<'_, T> {
+            let id = current_thread();
+            // Only the owning thread ever stores its own id, so a relaxed load suffices.
+            if self.owner.load(Ordering::Relaxed) == id {
+                let count = self
+                    .count
+                    .get()
+                    .checked_add(1)
+                    .expect("lock count overflow");
+                self.count.set(count);
+            } else {
+                let mut locked = self.locked.lock().unwrap_or_else(PoisonError::into_inner);
+                while *locked {
+                    locked = self
+                        .available
+                        .wait(locked)
+                        .unwrap_or_else(PoisonError::into_inner);
+                }
+                *locked = true;
+                drop(locked);
+                self.owner.store(id, Ordering::Relaxed);
+                self.count.set(1);
+            }
+
+            ReentrantMutexGuard {
+                lock: self,
+                _not_send: PhantomData,
+            }
+        }
+
+        pub fn into_inner(self) -> T {
+            self.data
+        }
+
+        fn unlock(&self) {
+            let count = self.count.get() - 1;
+            self.count.set(count);
+            if count == 0 {
+                self.owner.store(0, Ordering::Relaxed);
+                *self.locked.lock().unwrap_or_else(PoisonError::into_inner) = false;
+                self.available.notify_one();
+            }
+        }
+    }
+
+    pub struct ReentrantMutexGuard<'a, T> {
+        lock: &'a ReentrantMutex<T>,
+        _not_send: PhantomData<*const ()>,
+    }
+
+    unsafe impl<T: Sync> Sync for ReentrantMutexGuard<'_, T> {}
+
+    impl<T> Deref for ReentrantMutexGuard<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            &self.lock.data
+        }
+    }
+
+    impl<T> Drop for ReentrantMutexGuard<'_, T> {
+        fn drop(&mut self) {
+            self.lock.unlock();
+        }
+    }
+
+    impl<'a, T> LockApiReadGuard<'a, T> for ReentrantMutexGuard<'a, T> {
+        fn get(&self) -> &T {
+            self.deref()
+        }
+    }
+
+    impl<T: Default> Default for ReentrantMutex<T> {
+        fn default() -> Self {
+            ReentrantMutex::new(T::default())
+        }
+    }
+
+    impl<T> ReentrantLockApi<T> for ReentrantMutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        type Guard<'a> = ReentrantMutexGuard<'a, T>;
+
+        fn lock(&self) -> Result<Self::Guard<'_>> {
+            Ok(self.lock())
+        }
+
+        fn new(inner: T) -> Self {
+            ReentrantMutex::new(inner)
+        }
+    }
+}
+
+#[cfg(feature = "portable-atomic")]
+mod portable_atomic_impl {
+    use super::ReentrantLockApi;
+    use crate::error::Result;
+    use portable_atomic_util::Arc;
+
+    impl<L, T> ReentrantLockApi<T> for Arc<L>
+    where
+        L: ReentrantLockApi<T>,
+        for<'a> L: 'a,
+    {
+        type Guard<'a> = L::Guard<'a>;
+
+        fn lock(&self) -> Result<Self::Guard<'_>> {
+            (**self).lock()
+        }
+
+        fn new(inner: T) -> Self {
+            Arc::new(L::new(inner))
+        }
+    }
+}
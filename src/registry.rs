@@ -0,0 +1,26 @@
+use alloc::{
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use std::sync::{Mutex, PoisonError};
+
+use crate::named::{LocketInfo, LocketState};
+
+static REGISTRY: Mutex<Vec<Weak<LocketState>>> = Mutex::new(Vec::new());
+
+pub(crate) fn register(state: &Arc<LocketState>) {
+    let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+    registry.retain(|entry| entry.strong_count() > 0);
+    registry.push(Arc::downgrade(state));
+}
+
+/// Lists the state of every live named locket.
+pub fn lockets() -> Vec<LocketInfo> {
+    let mut registry = REGISTRY.lock().unwrap_or_else(PoisonError::into_inner);
+    registry.retain(|entry| entry.strong_count() > 0);
+    registry
+        .iter()
+        .filter_map(Weak::upgrade)
+        .map(|state| state.info())
+        .collect()
+}
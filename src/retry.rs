@@ -0,0 +1,198 @@
+use crate::{
+    backoff::Backoff,
+    error::{LockError, Result},
+    try_lock::TryLockApi,
+};
+
+/// Acquires a lock by repeating `try_read`/`try_write` while it would block,
+/// waiting between attempts as `backoff` says. Without an attempt budget
+/// this retries until the lock is free, or until the crate-wide maximum wait
+/// with the `max-wait` feature, e.g.
+/// `retry(Backoff::Yield).attempts(100).read(&cell)`.
+pub fn retry(backoff: Backoff) -> Retry {
+    Retry {
+        backoff,
+        attempts: None,
+    }
+}
+
+/// A retry strategy, see [`retry`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Retry {
+    backoff: Backoff,
+    attempts: Option<u32>,
+}
+
+impl Retry {
+    /// Gives up after `attempts` tries, returning [`LockError::WouldBlock`].
+    pub fn attempts(mut self, attempts: u32) -> Retry {
+        self.attempts = Some(attempts);
+        self
+    }
+
+    pub fn backoff(&self) -> Backoff {
+        self.backoff
+    }
+
+    pub fn budget(&self) -> Option<u32> {
+        self.attempts
+    }
+
+    pub fn read<'a, L, T>(&self, lock: &'a L) -> Result<L::ReadGuard<'a>>
+    where
+        L: TryLockApi<T>,
+    {
+        self.run(|| lock.try_read())
+    }
+
+    pub fn write<'a, L, T>(&self, lock: &'a L) -> Result<L::WriteGuard<'a>>
+    where
+        L: TryLockApi<T>,
+    {
+        self.run(|| lock.try_write())
+    }
+
+    // Without an attempt budget, retrying stops at the crate-wide maximum wait.
+    #[cfg(feature = "max-wait")]
+    fn deadline(&self) -> Option<std::time::Instant> {
+        match self.attempts {
+            Some(_) => None,
+            None => crate::config::max_wait().map(|max| crate::clock::now() + max),
+        }
+    }
+
+    fn run<G>(&self, mut attempt: impl FnMut() -> Result<G>) -> Result<G> {
+        let mut snooze = self.backoff.start();
+        let mut tries = 0u32;
+        #[cfg(feature = "max-wait")]
+        let deadline = self.deadline();
+        loop {
+            match attempt() {
+                Err(LockError::WouldBlock) => {}
+                ret => return ret,
+            }
+            tries = tries.saturating_add(1);
+            if self.attempts.is_some_and(|attempts| tries >= attempts) {
+                return Err(LockError::WouldBlock);
+            }
+            #[cfg(feature = "max-wait")]
+            if deadline.is_some_and(|deadline| crate::clock::now() >= deadline) {
+                return Err(LockError::Timeout);
+            }
+            snooze.snooze();
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::Retry;
+    use crate::{
+        async_timed::AsyncTimer,
+        error::{LockError, Result},
+        try_lock::TryLockApi,
+    };
+    use core::{future::Future, time::Duration};
+
+    // Async attempts are spaced by timer delays, doubling from the first up to
+    // the last, since a generic lock cannot wake us when it is released.
+    const FIRST_DELAY: Duration = Duration::from_micros(100);
+    const MAX_DELAY: Duration = Duration::from_millis(10);
+
+    impl Retry {
+        /// Like [`Retry::read`], but waits on a [`DefaultTimer`] delay between
+        /// attempts instead of as the backoff says, so no thread is blocked.
+        ///
+        /// [`DefaultTimer`]: crate::DefaultTimer
+        #[cfg(any(feature = "futures-timer", feature = "async-io"))]
+        pub fn read_async<'a, L, T>(
+            &self,
+            lock: &'a L,
+        ) -> impl Future<Output = Result<L::ReadGuard<'a>>> + 'a
+        where
+            L: TryLockApi<T>,
+            T: 'a,
+        {
+            self.read_async_with(lock, crate::async_timed::DefaultTimer::default())
+        }
+
+        /// Like [`Retry::write`], but waits on a [`DefaultTimer`] delay between
+        /// attempts instead of as the backoff says, so no thread is blocked.
+        ///
+        /// [`DefaultTimer`]: crate::DefaultTimer
+        #[cfg(any(feature = "futures-timer", feature = "async-io"))]
+        pub fn write_async<'a, L, T>(
+            &self,
+            lock: &'a L,
+        ) -> impl Future<Output = Result<L::WriteGuard<'a>>> + 'a
+        where
+            L: TryLockApi<T>,
+            T: 'a,
+        {
+            self.write_async_with(lock, crate::async_timed::DefaultTimer::default())
+        }
+
+        /// Like [`Retry::read_async`], with delays from the given timer.
+        pub fn read_async_with<'a, L, T, Tm>(
+            &self,
+            lock: &'a L,
+            _timer: Tm,
+        ) -> impl Future<Output = Result<L::ReadGuard<'a>>> + 'a
+        where
+            L: TryLockApi<T>,
+            T: 'a,
+            Tm: AsyncTimer + 'a,
+        {
+            self.run_async::<_, Tm>(move || lock.try_read())
+        }
+
+        /// Like [`Retry::write_async`], with delays from the given timer.
+        pub fn write_async_with<'a, L, T, Tm>(
+            &self,
+            lock: &'a L,
+            _timer: Tm,
+        ) -> impl Future<Output = Result<L::WriteGuard<'a>>> + 'a
+        where
+            L: TryLockApi<T>,
+            T: 'a,
+            Tm: AsyncTimer + 'a,
+        {
+            self.run_async::<_, Tm>(move || lock.try_write())
+        }
+
+        fn run_async<G, Tm>(
+            &self,
+            mut attempt: impl FnMut() -> Result<G>,
+        ) -> impl Future<Output = Result<G>>
+        where
+            Tm: AsyncTimer,
+        {
+            let attempts = self.attempts;
+            #[cfg(feature = "max-wait")]
+            let deadline = self.deadline();
+            async move {
+                let mut tries = 0u32;
+                let mut delay = FIRST_DELAY;
+                loop {
+                    match attempt() {
+                        Err(LockError::WouldBlock) => {}
+                        ret => return ret,
+                    }
+                    tries = tries.saturating_add(1);
+                    if attempts.is_some_and(|attempts| tries >= attempts) {
+                        return Err(LockError::WouldBlock);
+                    }
+                    #[cfg(feature = "max-wait")]
+                    if let Some(deadline) = deadline {
+                        match deadline.checked_duration_since(crate::clock::now()) {
+                            Some(left) if !left.is_zero() => delay = delay.min(left),
+                            _ => return Err(LockError::Timeout),
+                        }
+                    }
+                    Tm::delay(delay).await;
+                    delay = (delay * 2).min(MAX_DELAY);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,543 @@
+use alloc::vec::Vec;
+use core::{
+    cell::UnsafeCell,
+    ops::{Deref, DerefMut},
+    task::Waker,
+};
+use std::{
+    sync::{Condvar, Mutex, MutexGuard, PoisonError},
+    time::Instant,
+};
+
+use crate::{
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{AccessMode, LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    policy::{RwPolicy, RwPolicyApi},
+    stats::LockStats,
+    timed::TimedLockApi,
+    try_lock::TryLockApi,
+};
+
+#[derive(Default)]
+struct State {
+    readers: usize,
+    writer: bool,
+    waiting_readers: usize,
+    waiting_writers: usize,
+    // Waiting readers admitted ahead of the next writer (phase-fair only).
+    read_batch: usize,
+    wakers: Vec<Waker>,
+}
+
+impl State {
+    fn can_read(&self, policy: RwPolicy, max_readers: usize, waiting: bool) -> bool {
+        if self.writer || self.readers >= max_readers {
+            return false;
+        }
+        match policy {
+            RwPolicy::ReadPreferring => true,
+            RwPolicy::WritePreferring => self.waiting_writers == 0,
+            RwPolicy::PhaseFair => self.waiting_writers == 0 || (waiting && self.read_batch > 0),
+        }
+    }
+
+    fn can_write(&self, policy: RwPolicy) -> bool {
+        if self.writer || self.readers > 0 {
+            return false;
+        }
+        match policy {
+            RwPolicy::ReadPreferring | RwPolicy::WritePreferring => true,
+            RwPolicy::PhaseFair => self.read_batch == 0,
+        }
+    }
+
+    fn stop_waiting(&mut self, mode: AccessMode) {
+        match mode {
+            AccessMode::Read => {
+                self.waiting_readers -= 1;
+                self.read_batch = self.read_batch.saturating_sub(1);
+            }
+            AccessMode::Write => self.waiting_writers -= 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Wait {
+    Never,
+    Forever,
+    Until(Instant),
+}
+
+/// A read-write lock with a configurable [`RwPolicy`], for threads and tasks
+/// alike. Defaults to [`RwPolicy::WritePreferring`]. Optionally caps the
+/// number of read guards held at once; further readers wait.
+pub struct PolicyRwLock<T: ?Sized> {
+    policy: RwPolicy,
+    max_readers: usize,
+    state: Mutex<State>,
+    released: Condvar,
+    data: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for PolicyRwLock<T> {}
+unsafe impl<T: ?Sized + Send + Sync> Sync for PolicyRwLock<T> {}
+
+impl<T> PolicyRwLock<T> {
+    pub fn new(inner: T) -> PolicyRwLock<T> {
+        PolicyRwLock::with_policy(inner, RwPolicy::default())
+    }
+
+    pub fn with_policy(inner: T, policy: RwPolicy) -> PolicyRwLock<T> {
+        PolicyRwLock {
+            policy,
+            max_readers: usize::MAX,
+            state: Mutex::new(State::default()),
+            released: Condvar::new(),
+            data: UnsafeCell::new(inner),
+        }
+    }
+
+    /// Caps the number of read guards held at once. A limit of 0 is taken
+    /// as 1.
+    pub fn with_max_readers(mut self, max_readers: usize) -> PolicyRwLock<T> {
+        self.max_readers = max_readers.max(1);
+        self
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+}
+
+impl<T: ?Sized> PolicyRwLock<T> {
+    pub fn policy(&self) -> RwPolicy {
+        self.policy
+    }
+
+    /// The reader limit, if one was set.
+    pub fn max_readers(&self) -> Option<usize> {
+        (self.max_readers != usize::MAX).then_some(self.max_readers)
+    }
+
+    pub fn get_mut(&mut self) -> &mut T {
+        self.data.get_mut()
+    }
+
+    pub fn try_read(&self) -> Option<PolicyReadGuard<'_, T>> {
+        let mut state = self.state();
+        if !state.can_read(self.policy, self.max_readers, false) {
+            return None;
+        }
+        state.readers += 1;
+        Some(PolicyReadGuard { lock: self })
+    }
+
+    pub fn try_write(&self) -> Option<PolicyWriteGuard<'_, T>> {
+        let mut state = self.state();
+        if !state.can_write(self.policy) {
+            return None;
+        }
+        state.writer = true;
+        Some(PolicyWriteGuard { lock: self })
+    }
+
+    // The state is only touched in short sections which cannot panic.
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn wake_all(&self, state: &mut State) {
+        state.wakers.drain(..).for_each(Waker::wake);
+        self.released.notify_all();
+    }
+
+    fn lock(&self, mode: AccessMode, wait: Wait) -> Result<()> {
+        let mut state = self.state();
+        let mut waiting = false;
+        loop {
+            let free = match mode {
+                AccessMode::Read => state.can_read(self.policy, self.max_readers, waiting),
+                AccessMode::Write => state.can_write(self.policy),
+            };
+            if free {
+                if waiting {
+                    state.stop_waiting(mode);
+                }
+                match mode {
+                    AccessMode::Read => state.readers += 1,
+                    AccessMode::Write => state.writer = true,
+                }
+                return Ok(());
+            }
+
+            let timeout = match wait {
+                Wait::Never => return Err(LockError::WouldBlock),
+                Wait::Forever => None,
+                Wait::Until(deadline) => {
+                    match deadline.checked_duration_since(crate::clock::now()) {
+                        Some(timeout) if !timeout.is_zero() => Some(timeout),
+                        _ => {
+                            if waiting {
+                                state.stop_waiting(mode);
+                                // Others may have been held back for us.
+                                self.wake_all(&mut state);
+                            }
+                            return Err(LockError::Timeout);
+                        }
+                    }
+                }
+            };
+            if !waiting {
+                match mode {
+                    AccessMode::Read => state.waiting_readers += 1,
+                    AccessMode::Write => state.waiting_writers += 1,
+                }
+                waiting = true;
+            }
+            state = match timeout {
+                Some(timeout) => {
+                    self.released
+                        .wait_timeout(state, timeout)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .released
+                    .wait(state)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+
+    fn unlock_read(&self) {
+        let mut state = self.state();
+        state.readers -= 1;
+        // With a reader limit, a freed slot lets another reader in.
+        if state.readers == 0 || self.max_readers != usize::MAX {
+            self.wake_all(&mut state);
+        }
+    }
+
+    fn unlock_write(&self) {
+        let mut state = self.state();
+        state.writer = false;
+        if self.policy == RwPolicy::PhaseFair {
+            state.read_batch = state.waiting_readers;
+        }
+        self.wake_all(&mut state);
+    }
+}
+
+impl<T: Default> Default for PolicyRwLock<T> {
+    fn default() -> Self {
+        PolicyRwLock::new(T::default())
+    }
+}
+
+impl<T> LockApi<T> for PolicyRwLock<T>
+where
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = PolicyReadGuard<'a, T>;
+
+    type WriteGuard<'a> = PolicyWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.lock(AccessMode::Read, Wait::Forever)?;
+        Ok(PolicyReadGuard { lock: self })
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.lock(AccessMode::Write, Wait::Forever)?;
+        Ok(PolicyWriteGuard { lock: self })
+    }
+
+    fn new(inner: T) -> Self {
+        PolicyRwLock::new(inner)
+    }
+}
+
+impl<T> TryLockApi<T> for PolicyRwLock<T>
+where
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.lock(AccessMode::Read, Wait::Never)?;
+        Ok(PolicyReadGuard { lock: self })
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.lock(AccessMode::Write, Wait::Never)?;
+        Ok(PolicyWriteGuard { lock: self })
+    }
+}
+
+impl<T> TimedLockApi<T> for PolicyRwLock<T>
+where
+    for<'a> T: 'a,
+{
+    fn read_until(&self, deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+        self.lock(AccessMode::Read, Wait::Until(deadline))?;
+        Ok(PolicyReadGuard { lock: self })
+    }
+
+    fn write_until(&self, deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+        self.lock(AccessMode::Write, Wait::Until(deadline))?;
+        Ok(PolicyWriteGuard { lock: self })
+    }
+}
+
+impl<T: ?Sized> RwPolicyApi for PolicyRwLock<T> {
+    fn rw_policy(&self) -> RwPolicy {
+        self.policy
+    }
+}
+
+impl<T: ?Sized> LockStats for PolicyRwLock<T> {
+    fn is_locked(&self) -> bool {
+        let state = self.state();
+        state.writer || state.readers > 0
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        self.state().writer
+    }
+
+    fn reader_count(&self) -> Option<usize> {
+        Some(self.state().readers)
+    }
+}
+
+impl<T> IntoInner<T> for PolicyRwLock<T> {
+    fn into_inner(self) -> Result<T> {
+        Ok(PolicyRwLock::into_inner(self))
+    }
+}
+
+impl<T: ?Sized> PoisonApi for PolicyRwLock<T> {}
+
+impl<T> core::fmt::Debug for PolicyRwLock<T>
+where
+    T: core::fmt::Debug + ?Sized,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut f = f.debug_struct("PolicyRwLock");
+        f.field("policy", &self.policy);
+        match self.try_read() {
+            Some(guard) => f.field("data", &&*guard),
+            None => f.field("data", &format_args!("<locked>")),
+        };
+        f.finish()
+    }
+}
+
+pub struct PolicyReadGuard<'a, T: ?Sized> {
+    lock: &'a PolicyRwLock<T>,
+}
+
+impl<T: ?Sized> Drop for PolicyReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.unlock_read();
+    }
+}
+
+impl<T: ?Sized> Deref for PolicyReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: readers exclude the writer.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for PolicyReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+pub struct PolicyWriteGuard<'a, T: ?Sized> {
+    lock: &'a PolicyRwLock<T>,
+}
+
+impl<T: ?Sized> Drop for PolicyWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.unlock_write();
+    }
+}
+
+impl<T: ?Sized> Deref for PolicyWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the writer holds the lock exclusively.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for PolicyWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the writer holds the lock exclusively.
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for PolicyWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for PolicyWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self
+    }
+}
+
+#[cfg(feature = "async")]
+pub use self::async_impl::{PolicyReadFuture, PolicyWriteFuture};
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{PolicyReadGuard, PolicyRwLock, PolicyWriteGuard};
+    use crate::{async_locking::AsyncLockApi, error::Result, locking::AccessMode};
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    impl<T: ?Sized> PolicyRwLock<T> {
+        fn poll_acquire(&self, mode: AccessMode, waiting: &mut bool, cx: &mut Context<'_>) -> bool {
+            let mut state = self.state();
+            let acquired = match mode {
+                AccessMode::Read => state.can_read(self.policy, self.max_readers, *waiting),
+                AccessMode::Write => state.can_write(self.policy),
+            };
+
+            if acquired {
+                if *waiting {
+                    state.stop_waiting(mode);
+                    *waiting = false;
+                }
+                match mode {
+                    AccessMode::Read => state.readers += 1,
+                    AccessMode::Write => state.writer = true,
+                }
+                return true;
+            }
+
+            if !*waiting {
+                match mode {
+                    AccessMode::Read => state.waiting_readers += 1,
+                    AccessMode::Write => state.waiting_writers += 1,
+                }
+                *waiting = true;
+            }
+            if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                state.wakers.push(cx.waker().clone());
+            }
+            false
+        }
+
+        fn cancel(&self, mode: AccessMode) {
+            let mut state = self.state();
+            state.stop_waiting(mode);
+            // A writer may have been held back for this reader, or readers for
+            // this writer.
+            self.wake_all(&mut state);
+        }
+    }
+
+    impl<T> AsyncLockApi<T> for PolicyRwLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = PolicyReadGuard<'a, T>;
+
+        type WriteGuard<'a> = PolicyWriteGuard<'a, T>;
+
+        type ReadFuture<'a> = PolicyReadFuture<'a, T>;
+
+        type WriteFuture<'a> = PolicyWriteFuture<'a, T>;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            PolicyReadFuture {
+                lock: self,
+                waiting: false,
+            }
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            PolicyWriteFuture {
+                lock: self,
+                waiting: false,
+            }
+        }
+
+        fn new(inner: T) -> Self {
+            PolicyRwLock::new(inner)
+        }
+    }
+
+    pub struct PolicyReadFuture<'a, T: ?Sized> {
+        lock: &'a PolicyRwLock<T>,
+        waiting: bool,
+    }
+
+    impl<'a, T: ?Sized> Future for PolicyReadFuture<'a, T> {
+        type Output = Result<PolicyReadGuard<'a, T>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.get_mut();
+            if this
+                .lock
+                .poll_acquire(AccessMode::Read, &mut this.waiting, cx)
+            {
+                Poll::Ready(Ok(PolicyReadGuard { lock: this.lock }))
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    impl<T: ?Sized> Drop for PolicyReadFuture<'_, T> {
+        fn drop(&mut self) {
+            if self.waiting {
+                self.lock.cancel(AccessMode::Read);
+            }
+        }
+    }
+
+    pub struct PolicyWriteFuture<'a, T: ?Sized> {
+        lock: &'a PolicyRwLock<T>,
+        waiting: bool,
+    }
+
+    impl<'a, T: ?Sized> Future for PolicyWriteFuture<'a, T> {
+        type Output = Result<PolicyWriteGuard<'a, T>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.get_mut();
+            if this
+                .lock
+                .poll_acquire(AccessMode::Write, &mut this.waiting, cx)
+            {
+                Poll::Ready(Ok(PolicyWriteGuard { lock: this.lock }))
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    impl<T: ?Sized> Drop for PolicyWriteFuture<'_, T> {
+        fn drop(&mut self) {
+            if self.waiting {
+                self.lock.cancel(AccessMode::Write);
+            }
+        }
+    }
+}
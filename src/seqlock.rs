@@ -0,0 +1,349 @@
+use core::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+    ptr,
+    sync::atomic::{fence, AtomicUsize, Ordering},
+};
+
+use crate::{
+    backoff::Backoff,
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    stats::LockStats,
+    try_lock::TryLockApi,
+};
+
+/// A sequence lock for small `Copy` values. Readers copy the value without
+/// taking a lock and retry if a write happened meanwhile; writers are exclusive.
+pub struct SeqLock<T> {
+    seq: AtomicUsize,
+    data: UnsafeCell<T>,
+    backoff: Backoff,
+    #[cfg(all(feature = "async", feature = "alloc"))]
+    wakers: crate::wakers::WakerList,
+}
+
+unsafe impl<T: Copy + Send> Send for SeqLock<T> {}
+unsafe impl<T: Copy + Send> Sync for SeqLock<T> {}
+
+impl<T> SeqLock<T>
+where
+    T: Copy,
+{
+    pub const fn new(inner: T) -> SeqLock<T> {
+        SeqLock::with_backoff(inner, Backoff::Spin)
+    }
+
+    pub const fn with_backoff(inner: T, backoff: Backoff) -> SeqLock<T> {
+        SeqLock {
+            seq: AtomicUsize::new(0),
+            data: UnsafeCell::new(inner),
+            backoff,
+            #[cfg(all(feature = "async", feature = "alloc"))]
+            wakers: crate::wakers::WakerList::new(),
+        }
+    }
+
+    pub fn backoff(&self) -> Backoff {
+        self.backoff
+    }
+
+    /// Returns a consistent copy of the value.
+    pub fn load(&self) -> T {
+        let mut snooze = self.backoff.start();
+        loop {
+            if let Some(value) = self.try_load() {
+                return value;
+            }
+            snooze.snooze();
+        }
+    }
+
+    // One attempt at copying the value, failing if a write is in progress or
+    // happened meanwhile.
+    fn try_load(&self) -> Option<T> {
+        let before = self.seq.load(Ordering::Acquire);
+        if before & 1 == 1 {
+            return None;
+        }
+        // SAFETY: the copy may be torn by a concurrent writer, so it is only
+        // assumed initialised once the sequence number shows no write happened.
+        let value = unsafe { ptr::read_volatile(self.data.get().cast::<MaybeUninit<T>>()) };
+        fence(Ordering::Acquire);
+        if self.seq.load(Ordering::Relaxed) != before {
+            return None;
+        }
+        // SAFETY: no writer touched the value while it was copied.
+        Some(unsafe { value.assume_init() })
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data.into_inner()
+    }
+
+    fn try_begin_write(&self) -> Option<SeqWriteGuard<'_, T>> {
+        let seq = self.seq.load(Ordering::Relaxed);
+        if seq & 1 == 1 {
+            return None;
+        }
+        self.seq
+            .compare_exchange(seq, seq + 1, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        // Keep the data writes after the odd sequence number.
+        fence(Ordering::Release);
+        Some(SeqWriteGuard { lock: self, seq })
+    }
+}
+
+impl<T: Copy + Default> Default for SeqLock<T> {
+    fn default() -> Self {
+        SeqLock::new(T::default())
+    }
+}
+
+impl<T> LockApi<T> for SeqLock<T>
+where
+    T: Copy,
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = SeqReadGuard<'a, T>;
+
+    type WriteGuard<'a> = SeqWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        Ok(SeqReadGuard {
+            value: self.load(),
+            _lock: PhantomData,
+        })
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        let mut snooze = self.backoff.start();
+        loop {
+            if let Some(guard) = self.try_begin_write() {
+                return Ok(guard);
+            }
+            snooze.snooze();
+        }
+    }
+
+    fn new(inner: T) -> Self {
+        SeqLock::new(inner)
+    }
+}
+
+impl<T> IntoInner<T> for SeqLock<T>
+where
+    T: Copy,
+{
+    fn into_inner(self) -> Result<T> {
+        Ok(SeqLock::into_inner(self))
+    }
+}
+
+impl<T> TryLockApi<T> for SeqLock<T>
+where
+    T: Copy,
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        LockApi::read(self)
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.try_begin_write().ok_or(LockError::WouldBlock)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> crate::timed::TimedLockApi<T> for SeqLock<T>
+where
+    T: Copy,
+    for<'a> T: 'a,
+{
+}
+
+// Readers do not hold the lock, so only a writer shows up.
+impl<T> LockStats for SeqLock<T> {
+    fn is_locked(&self) -> bool {
+        self.seq.load(Ordering::Relaxed) & 1 == 1
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        LockStats::is_locked(self)
+    }
+}
+
+impl<T> core::fmt::Debug for SeqLock<T>
+where
+    T: Copy + core::fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SeqLock")
+            .field("data", &crate::peek::peek(self))
+            .finish()
+    }
+}
+
+impl<T> PoisonApi for SeqLock<T> {}
+
+/// Holds a validated copy of the value taken when the guard was created.
+pub struct SeqReadGuard<'a, T> {
+    value: T,
+    _lock: PhantomData<&'a SeqLock<T>>,
+}
+
+impl<T> Deref for SeqReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for SeqReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+pub struct SeqWriteGuard<'a, T> {
+    lock: &'a SeqLock<T>,
+    seq: usize,
+}
+
+impl<T> Drop for SeqWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock
+            .seq
+            .store(self.seq.wrapping_add(2), Ordering::Release);
+        #[cfg(all(feature = "async", feature = "alloc"))]
+        self.lock.wakers.wake_all();
+    }
+}
+
+impl<T> Deref for SeqWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: writers are exclusive and readers only copy the value.
+        unsafe { &*self.lock.data.get() }
+    }
+}
+
+impl<T> DerefMut for SeqWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: see `deref`.
+        unsafe { &mut *self.lock.data.get() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for SeqWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for SeqWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
+
+#[cfg(all(feature = "async", feature = "alloc"))]
+pub use self::async_impl::{SeqReadFuture, SeqWriteFuture};
+
+#[cfg(all(feature = "async", feature = "alloc"))]
+mod async_impl {
+    use super::{SeqLock, SeqReadGuard, SeqWriteGuard};
+    use crate::{async_locking::AsyncLockApi, error::Result};
+    use core::{
+        future::Future,
+        marker::PhantomData,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    impl<T> AsyncLockApi<T> for SeqLock<T>
+    where
+        T: Copy,
+        for<'a> T: 'a,
+    {
+        type ReadGuard<'a> = SeqReadGuard<'a, T>;
+
+        type WriteGuard<'a> = SeqWriteGuard<'a, T>;
+
+        type ReadFuture<'a> = SeqReadFuture<'a, T>;
+
+        type WriteFuture<'a> = SeqWriteFuture<'a, T>;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            SeqReadFuture { lock: self }
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            SeqWriteFuture { lock: self }
+        }
+
+        fn new(inner: T) -> Self {
+            SeqLock::new(inner)
+        }
+    }
+
+    /// Retries copying the value whenever a write guard is dropped.
+    pub struct SeqReadFuture<'a, T> {
+        lock: &'a SeqLock<T>,
+    }
+
+    impl<'a, T> Future for SeqReadFuture<'a, T>
+    where
+        T: Copy,
+    {
+        type Output = Result<SeqReadGuard<'a, T>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let lock = self.lock;
+            let value = match lock.try_load() {
+                Some(value) => value,
+                None => {
+                    lock.wakers.register(cx.waker());
+                    match lock.try_load() {
+                        Some(value) => value,
+                        None => return Poll::Pending,
+                    }
+                }
+            };
+            Poll::Ready(Ok(SeqReadGuard {
+                value,
+                _lock: PhantomData,
+            }))
+        }
+    }
+
+    /// Retries acquiring the write side whenever a write guard is dropped.
+    pub struct SeqWriteFuture<'a, T> {
+        lock: &'a SeqLock<T>,
+    }
+
+    impl<'a, T> Future for SeqWriteFuture<'a, T>
+    where
+        T: Copy,
+    {
+        type Output = Result<SeqWriteGuard<'a, T>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            if let Some(guard) = self.lock.try_begin_write() {
+                return Poll::Ready(Ok(guard));
+            }
+            self.lock.wakers.register(cx.waker());
+            match self.lock.try_begin_write() {
+                Some(guard) => Poll::Ready(Ok(guard)),
+                None => Poll::Pending,
+            }
+        }
+    }
+}
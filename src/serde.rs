@@ -0,0 +1,26 @@
+//! Serializes a locket through a read lock and deserializes it into a new one.
+//! Use with `#[serde(with = "locket::serde")]` on fields holding a lock, such
+//! as `Arc<RwLock<Config>>`.
+
+use ::serde::{ser, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::locking::{LockApi, LockApiReadGuard};
+
+pub fn serialize<L, T, S>(lock: &L, serializer: S) -> Result<S::Ok, S::Error>
+where
+    L: LockApi<T>,
+    T: Serialize,
+    S: Serializer,
+{
+    let guard = lock.read().map_err(ser::Error::custom)?;
+    guard.get().serialize(serializer)
+}
+
+pub fn deserialize<'de, L, T, D>(deserializer: D) -> Result<L, D::Error>
+where
+    L: LockApi<T>,
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(L::new)
+}
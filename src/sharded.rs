@@ -0,0 +1,108 @@
+use core::{
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+use crate::{error::Result, locking::LockApi};
+
+// FNV-1a, so shard selection works without std's hashers.
+struct FnvHasher(u64);
+
+impl Hasher for FnvHasher {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+}
+
+/// State split across `N` independently locked shards, selected by index or by
+/// key hash. Bulk operations lock the shards in index order.
+pub struct ShardedLocket<T, L, const N: usize> {
+    shards: [L; N],
+    _value: PhantomData<fn() -> T>,
+}
+
+impl<T, L, const N: usize> ShardedLocket<T, L, N>
+where
+    L: LockApi<T>,
+{
+    pub fn new(mut init: impl FnMut(usize) -> T) -> ShardedLocket<T, L, N> {
+        ShardedLocket::from_shards(core::array::from_fn(|idx| L::new(init(idx))))
+    }
+
+    pub fn from_shards(shards: [L; N]) -> ShardedLocket<T, L, N> {
+        const { assert!(N > 0, "a sharded locket needs at least one shard") };
+        ShardedLocket {
+            shards,
+            _value: PhantomData,
+        }
+    }
+
+    pub fn shards(&self) -> &[L; N] {
+        &self.shards
+    }
+
+    pub fn shard(&self, index: usize) -> &L {
+        &self.shards[index]
+    }
+
+    pub fn index_for<K>(&self, key: &K) -> usize
+    where
+        K: Hash + ?Sized,
+    {
+        let mut hasher = FnvHasher(0xcbf2_9ce4_8422_2325);
+        key.hash(&mut hasher);
+        (hasher.finish() % N as u64) as usize
+    }
+
+    pub fn shard_for<K>(&self, key: &K) -> &L
+    where
+        K: Hash + ?Sized,
+    {
+        &self.shards[self.index_for(key)]
+    }
+
+    pub fn read_shard(&self, index: usize) -> Result<L::ReadGuard<'_>> {
+        self.shards[index].read()
+    }
+
+    pub fn write_shard(&self, index: usize) -> Result<L::WriteGuard<'_>> {
+        self.shards[index].write()
+    }
+
+    pub fn read_key<K>(&self, key: &K) -> Result<L::ReadGuard<'_>>
+    where
+        K: Hash + ?Sized,
+    {
+        self.shard_for(key).read()
+    }
+
+    pub fn write_key<K>(&self, key: &K) -> Result<L::WriteGuard<'_>>
+    where
+        K: Hash + ?Sized,
+    {
+        self.shard_for(key).write()
+    }
+
+    pub fn read_all(&self) -> Result<[L::ReadGuard<'_>; N]> {
+        collect(self.shards.iter().map(|shard| shard.read()))
+    }
+
+    pub fn write_all(&self) -> Result<[L::WriteGuard<'_>; N]> {
+        collect(self.shards.iter().map(|shard| shard.write()))
+    }
+}
+
+fn collect<G, const N: usize>(guards: impl Iterator<Item = Result<G>>) -> Result<[G; N]> {
+    let mut slots: [Option<G>; N] = core::array::from_fn(|_| None);
+    for (slot, guard) in slots.iter_mut().zip(guards) {
+        *slot = Some(guard?);
+    }
+    Ok(slots.map(|guard| guard.expect("one guard per shard")))
+}
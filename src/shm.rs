@@ -0,0 +1,345 @@
+use core::{
+    marker::PhantomData,
+    mem,
+    ops::{Deref, DerefMut},
+    ptr,
+    sync::atomic::{AtomicU32, Ordering},
+};
+use std::{
+    fs::OpenOptions,
+    io,
+    path::{Path, PathBuf},
+};
+
+use bytemuck::Pod;
+use memmap2::MmapMut;
+
+use crate::{
+    backoff::Backoff,
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    stats::LockStats,
+    timed::TimedLockApi,
+    try_lock::TryLockApi,
+};
+
+const WRITER: u32 = u32::MAX;
+
+const UNINIT: u32 = 0;
+const INITIALIZING: u32 = 1;
+const READY: u32 = 2;
+
+// Start of the segment, followed by the value.
+#[repr(C)]
+struct Header {
+    // Number of readers, or `WRITER`.
+    state: AtomicU32,
+    init: AtomicU32,
+}
+
+#[repr(C)]
+struct Segment<T> {
+    header: Header,
+    value: T,
+}
+
+/// A read-write lock living in a memory mapped file together with its value,
+/// so every process mapping the same path shares both. On Linux, paths under
+/// `/dev/shm` stay in memory.
+///
+/// The value must be plain data ([`Pod`]) since other processes see its raw
+/// bytes. Waiting is done by spinning with the configured [`Backoff`]. A
+/// process which dies while holding the lock, or while initializing the
+/// segment, leaves it locked for good.
+pub struct ShmLock<T> {
+    map: MmapMut,
+    path: Option<PathBuf>,
+    backoff: Backoff,
+    _value: PhantomData<T>,
+}
+
+unsafe impl<T: Send> Send for ShmLock<T> {}
+unsafe impl<T: Send + Sync> Sync for ShmLock<T> {}
+
+impl<T> ShmLock<T>
+where
+    T: Pod,
+{
+    /// Maps `path`, creating it and storing `init` if this process is the
+    /// first to open it. Otherwise `init` is ignored.
+    pub fn open(path: impl AsRef<Path>, init: T) -> io::Result<ShmLock<T>> {
+        ShmLock::open_with_backoff(path, init, Backoff::Yield)
+    }
+
+    pub fn open_with_backoff(
+        path: impl AsRef<Path>,
+        init: T,
+        backoff: Backoff,
+    ) -> io::Result<ShmLock<T>> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&path)?;
+
+        let size = mem::size_of::<Segment<T>>() as u64;
+        match file.metadata()?.len() {
+            0 => file.set_len(size)?,
+            len if len == size => {}
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "shared memory segment has a different size",
+                ))
+            }
+        }
+
+        // SAFETY: the file is only accessed through `Segment<T>`, whose fields
+        // are atomics or guarded by them.
+        let map = unsafe { MmapMut::map_mut(&file)? };
+        Ok(ShmLock::init(map, Some(path), init, backoff))
+    }
+
+    fn init(map: MmapMut, path: Option<PathBuf>, init: T, backoff: Backoff) -> ShmLock<T> {
+        let lock: ShmLock<T> = ShmLock {
+            map,
+            path,
+            backoff,
+            _value: PhantomData,
+        };
+
+        let header = lock.header();
+        match header.init.compare_exchange(
+            UNINIT,
+            INITIALIZING,
+            Ordering::Acquire,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => {
+                // SAFETY: nobody reads the value before `init` is `READY`.
+                unsafe { lock.value_ptr().write(init) };
+                header.init.store(READY, Ordering::Release);
+            }
+            Err(_) => {
+                let mut snooze = lock.backoff.start();
+                while header.init.load(Ordering::Acquire) != READY {
+                    snooze.snooze();
+                }
+            }
+        }
+        lock
+    }
+}
+
+impl<T> ShmLock<T> {
+    /// The mapped file, or `None` for an anonymous segment.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub fn backoff(&self) -> Backoff {
+        self.backoff
+    }
+
+    fn segment(&self) -> *mut Segment<T> {
+        self.map.as_ptr() as *mut Segment<T>
+    }
+
+    fn header(&self) -> &Header {
+        // SAFETY: the mapping is page aligned and at least a `Segment<T>` long.
+        unsafe { &(*self.segment()).header }
+    }
+
+    fn value_ptr(&self) -> *mut T {
+        // SAFETY: as above.
+        unsafe { ptr::addr_of_mut!((*self.segment()).value) }
+    }
+
+    fn try_begin_read(&self) -> Option<ShmReadGuard<'_, T>> {
+        let state = &self.header().state;
+        let mut current = state.load(Ordering::Relaxed);
+        while current < WRITER - 1 {
+            match state.compare_exchange_weak(
+                current,
+                current + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(ShmReadGuard { lock: self }),
+                Err(actual) => current = actual,
+            }
+        }
+        None
+    }
+
+    fn try_begin_write(&self) -> Option<ShmWriteGuard<'_, T>> {
+        self.header()
+            .state
+            .compare_exchange(0, WRITER, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        Some(ShmWriteGuard { lock: self })
+    }
+}
+
+impl<T> LockApi<T> for ShmLock<T>
+where
+    T: Pod,
+{
+    type ReadGuard<'a> = ShmReadGuard<'a, T>;
+
+    type WriteGuard<'a> = ShmWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        let mut snooze = self.backoff.start();
+        loop {
+            if let Some(guard) = self.try_begin_read() {
+                return Ok(guard);
+            }
+            snooze.snooze();
+        }
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        let mut snooze = self.backoff.start();
+        loop {
+            if let Some(guard) = self.try_begin_write() {
+                return Ok(guard);
+            }
+            snooze.snooze();
+        }
+    }
+
+    /// Creates an anonymous segment. It is private to this process, so this
+    /// behaves like an in-process spin lock; use [`ShmLock::open`] to share it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the segment cannot be mapped.
+    fn new(inner: T) -> Self {
+        let map =
+            MmapMut::map_anon(mem::size_of::<Segment<T>>()).expect("failed to map shared memory");
+        ShmLock::init(map, None, inner, Backoff::Yield)
+    }
+}
+
+impl<T> TryLockApi<T> for ShmLock<T>
+where
+    T: Pod,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.try_begin_read().ok_or(LockError::WouldBlock)
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.try_begin_write().ok_or(LockError::WouldBlock)
+    }
+}
+
+impl<T> TimedLockApi<T> for ShmLock<T> where T: Pod {}
+
+impl<T> IntoInner<T> for ShmLock<T>
+where
+    T: Pod,
+{
+    fn into_inner(self) -> Result<T> {
+        Ok(*LockApi::read(&self)?)
+    }
+}
+
+impl<T> PoisonApi for ShmLock<T> {}
+
+impl<T> LockStats for ShmLock<T> {
+    fn is_locked(&self) -> bool {
+        self.header().state.load(Ordering::Relaxed) != 0
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        self.header().state.load(Ordering::Relaxed) == WRITER
+    }
+
+    fn reader_count(&self) -> Option<usize> {
+        match self.header().state.load(Ordering::Relaxed) {
+            WRITER => Some(0),
+            readers => Some(readers as usize),
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for ShmLock<T>
+where
+    T: Pod + core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ShmLock")
+            .field("path", &self.path)
+            .field("data", &crate::peek::peek(self))
+            .finish()
+    }
+}
+
+pub struct ShmReadGuard<'a, T> {
+    lock: &'a ShmLock<T>,
+}
+
+impl<T> Drop for ShmReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.header().state.fetch_sub(1, Ordering::Release);
+    }
+}
+
+impl<T> Deref for ShmReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: readers exclude writers in every process.
+        unsafe { &*self.lock.value_ptr() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for ShmReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+pub struct ShmWriteGuard<'a, T> {
+    lock: &'a ShmLock<T>,
+}
+
+impl<T> Drop for ShmWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.header().state.store(0, Ordering::Release);
+    }
+}
+
+impl<T> Deref for ShmWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the writer holds the lock exclusively in every process.
+        unsafe { &*self.lock.value_ptr() }
+    }
+}
+
+impl<T> DerefMut for ShmWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: as above.
+        unsafe { &mut *self.lock.value_ptr() }
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for ShmWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for ShmWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self
+    }
+}
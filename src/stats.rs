@@ -0,0 +1,178 @@
+#[cfg(feature = "alloc")]
+use alloc::{rc::Rc, sync::Arc};
+
+/// A snapshot of a lock's state for health checks and metrics. It may be out
+/// of date by the time it is used, so do not base synchronization on it.
+///
+/// Only backends which can read their state without touching the lock
+/// implement it. std, tokio and async-lock could only answer by trying to
+/// take the lock, which can itself hold back other threads, and a waiting
+/// writer would look like a held write lock.
+pub trait LockStats {
+    fn is_locked(&self) -> bool;
+
+    fn is_locked_exclusive(&self) -> bool;
+
+    /// The number of read guards currently held, for backends which count
+    /// them.
+    fn reader_count(&self) -> Option<usize> {
+        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L> LockStats for Arc<L>
+where
+    L: LockStats,
+{
+    fn is_locked(&self) -> bool {
+        (**self).is_locked()
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        (**self).is_locked_exclusive()
+    }
+
+    fn reader_count(&self) -> Option<usize> {
+        (**self).reader_count()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L> LockStats for Rc<L>
+where
+    L: LockStats,
+{
+    fn is_locked(&self) -> bool {
+        (**self).is_locked()
+    }
+
+    fn is_locked_exclusive(&self) -> bool {
+        (**self).is_locked_exclusive()
+    }
+
+    fn reader_count(&self) -> Option<usize> {
+        (**self).reader_count()
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+mod parking_lot_impl {
+    use super::LockStats;
+    use parking_lot::{FairMutex, Mutex, RwLock};
+
+    impl<T> LockStats for Mutex<T> {
+        fn is_locked(&self) -> bool {
+            Mutex::is_locked(self)
+        }
+
+        fn is_locked_exclusive(&self) -> bool {
+            Mutex::is_locked(self)
+        }
+    }
+
+    impl<T> LockStats for FairMutex<T> {
+        fn is_locked(&self) -> bool {
+            FairMutex::is_locked(self)
+        }
+
+        fn is_locked_exclusive(&self) -> bool {
+            FairMutex::is_locked(self)
+        }
+    }
+
+    impl<T> LockStats for RwLock<T> {
+        fn is_locked(&self) -> bool {
+            RwLock::is_locked(self)
+        }
+
+        fn is_locked_exclusive(&self) -> bool {
+            RwLock::is_locked_exclusive(self)
+        }
+    }
+}
+
+#[cfg(feature = "spin")]
+mod spin_impl {
+    use super::LockStats;
+    use spin::{mutex::Mutex, rwlock::RwLock};
+
+    impl<T, R> LockStats for Mutex<T, R> {
+        fn is_locked(&self) -> bool {
+            Mutex::is_locked(self)
+        }
+
+        fn is_locked_exclusive(&self) -> bool {
+            Mutex::is_locked(self)
+        }
+    }
+
+    impl<T, R> LockStats for RwLock<T, R> {
+        fn is_locked(&self) -> bool {
+            self.writer_count() > 0 || self.reader_count() > 0
+        }
+
+        fn is_locked_exclusive(&self) -> bool {
+            self.writer_count() > 0
+        }
+
+        fn reader_count(&self) -> Option<usize> {
+            Some(RwLock::reader_count(self))
+        }
+    }
+}
+
+/// The state of every live named locket, see [`dump`].
+#[cfg(feature = "registry")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatsReport {
+    pub lockets: alloc::vec::Vec<crate::named::LocketInfo>,
+}
+
+/// Collects a report on every live [`Named`](crate::Named) locket. With the
+/// `metrics` feature it includes acquisition counts, contention and hold
+/// times, and with `fairness` the wait times of each thread. Its `Display`
+/// output is meant for logs and debug endpoints.
+#[cfg(feature = "registry")]
+pub fn dump() -> StatsReport {
+    StatsReport {
+        lockets: crate::registry::lockets(),
+    }
+}
+
+#[cfg(feature = "registry")]
+impl core::fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for info in &self.lockets {
+            write!(f, "{}", info.name)?;
+            for (key, value) in info.labels {
+                write!(f, " {key}={value}")?;
+            }
+            #[cfg(feature = "metrics")]
+            write!(
+                f,
+                ": acquisitions={} contention={:.2} max_hold={:?}",
+                info.metrics.acquisitions(),
+                info.metrics.contention_ratio(),
+                info.metrics.hold.max(),
+            )?;
+            #[cfg(feature = "fairness")]
+            write!(f, " fairness={:.2}", info.fairness.index())?;
+            match &info.last_holder {
+                Some(holder) if info.locked => {
+                    write!(f, " held by {:?}", holder.thread_id)?;
+                    if let Some(name) = &holder.thread_name {
+                        write!(f, " ({name})")?;
+                    }
+                    write!(f, " for {:?}", holder.mode)?;
+                    if info.readers > 1 {
+                        write!(f, " with {} readers", info.readers)?;
+                    }
+                }
+                _ => write!(f, " unlocked")?,
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
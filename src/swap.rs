@@ -0,0 +1,201 @@
+use alloc::sync::Arc;
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+use std::sync::{Mutex, MutexGuard, PoisonError, TryLockError};
+
+use arc_swap::ArcSwap;
+
+use crate::{
+    error::{LockError, Result},
+    inner::IntoInner,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+    timed::TimedLockApi,
+    try_lock::TryLockApi,
+};
+
+/// A read-mostly lock built on `arc-swap`. Reads take an `Arc` snapshot without
+/// blocking; writes clone the value, modify the copy and publish it when the
+/// guard is dropped. Writers are serialized so no update is lost.
+pub struct SwapLock<T> {
+    value: ArcSwap<T>,
+    writer: Mutex<()>,
+}
+
+impl<T> SwapLock<T> {
+    pub fn new(inner: T) -> SwapLock<T> {
+        SwapLock {
+            value: ArcSwap::from_pointee(inner),
+            writer: Mutex::new(()),
+        }
+    }
+
+    pub fn load(&self) -> Arc<T> {
+        self.value.load_full()
+    }
+
+    pub fn store(&self, value: T) {
+        let _writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        self.value.store(Arc::new(value));
+    }
+}
+
+impl<T: Default> Default for SwapLock<T> {
+    fn default() -> Self {
+        SwapLock::new(T::default())
+    }
+}
+
+impl<T> LockApi<T> for SwapLock<T>
+where
+    T: Clone,
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = SwapReadGuard<'a, T>;
+
+    type WriteGuard<'a> = SwapWriteGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        Ok(SwapReadGuard {
+            value: self.load(),
+            _lock: PhantomData,
+        })
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        let writer = self.writer.lock().unwrap_or_else(PoisonError::into_inner);
+        Ok(SwapWriteGuard {
+            value: Some(T::clone(&self.value.load())),
+            lock: self,
+            _writer: writer,
+        })
+    }
+
+    fn new(inner: T) -> Self {
+        SwapLock::new(inner)
+    }
+}
+
+impl<T> IntoInner<T> for SwapLock<T>
+where
+    T: Clone,
+{
+    fn into_inner(self) -> Result<T> {
+        Ok(Arc::unwrap_or_clone(self.value.into_inner()))
+    }
+}
+
+impl<T> TryLockApi<T> for SwapLock<T>
+where
+    T: Clone,
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        LockApi::read(self)
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        let writer = match self.writer.try_lock() {
+            Ok(writer) => writer,
+            Err(TryLockError::Poisoned(err)) => err.into_inner(),
+            Err(TryLockError::WouldBlock) => return Err(LockError::WouldBlock),
+        };
+        Ok(SwapWriteGuard {
+            value: Some(T::clone(&self.value.load())),
+            lock: self,
+            _writer: writer,
+        })
+    }
+}
+
+impl<T> TimedLockApi<T> for SwapLock<T>
+where
+    T: Clone,
+    for<'a> T: 'a,
+{
+}
+
+impl<T> core::fmt::Debug for SwapLock<T>
+where
+    T: Clone + core::fmt::Debug + 'static,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("SwapLock")
+            .field("data", &crate::peek::peek(self))
+            .finish()
+    }
+}
+
+impl<T> PoisonApi for SwapLock<T> {}
+
+pub struct SwapReadGuard<'a, T> {
+    value: Arc<T>,
+    _lock: PhantomData<&'a SwapLock<T>>,
+}
+
+impl<T> SwapReadGuard<'_, T> {
+    pub fn snapshot(&self) -> Arc<T> {
+        self.value.clone()
+    }
+}
+
+impl<T> Deref for SwapReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for SwapReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+pub struct SwapWriteGuard<'a, T> {
+    value: Option<T>,
+    lock: &'a SwapLock<T>,
+    _writer: MutexGuard<'a, ()>,
+}
+
+impl<T> Drop for SwapWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        // A writer which panicked may have left its copy half modified; keep
+        // the previously published value instead.
+        if std::thread::panicking() {
+            return;
+        }
+        if let Some(value) = self.value.take() {
+            self.lock.value.store(Arc::new(value));
+        }
+    }
+}
+
+impl<T> Deref for SwapWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_ref().unwrap()
+    }
+}
+
+impl<T> DerefMut for SwapWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.value.as_mut().unwrap()
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for SwapWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for SwapWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
@@ -0,0 +1,240 @@
+use core::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use std::thread;
+
+use crate::{
+    error::{LockError, Result},
+    locking::LockApi,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChaosConfig {
+    pub seed: u64,
+    /// Chance (0.0 - 1.0) of sleeping up to `max_delay` before acquiring.
+    pub delay_probability: f64,
+    pub max_delay: Duration,
+    /// Chance of failing an acquisition with `LockError::WouldBlock`.
+    pub fail_probability: f64,
+    /// Chance of yielding to other threads before acquiring, shuffling the order
+    /// in which contending waiters get the lock.
+    pub yield_probability: f64,
+}
+
+impl Default for ChaosConfig {
+    fn default() -> Self {
+        ChaosConfig {
+            seed: 0x5eed,
+            delay_probability: 0.1,
+            max_delay: Duration::from_millis(1),
+            fail_probability: 0.0,
+            yield_probability: 0.25,
+        }
+    }
+}
+
+enum Chaos {
+    Fail,
+    Delay(Duration),
+    Yield(u32),
+    None,
+}
+
+/// Wraps a lock and injects delays, spurious `WouldBlock` failures and yields
+/// drawn from a seeded generator, so a failing interleaving can be replayed.
+pub struct ChaosLock<L> {
+    inner: L,
+    config: ChaosConfig,
+    state: AtomicU64,
+}
+
+impl<L> ChaosLock<L> {
+    pub fn with_config(inner: L, config: ChaosConfig) -> ChaosLock<L> {
+        ChaosLock {
+            inner,
+            config,
+            state: AtomicU64::new(config.seed),
+        }
+    }
+
+    pub fn config(&self) -> &ChaosConfig {
+        &self.config
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    // splitmix64
+    fn next(&self) -> u64 {
+        let mut z = self
+            .state
+            .fetch_add(0x9e37_79b9_7f4a_7c15, Ordering::Relaxed)
+            .wrapping_add(0x9e37_79b9_7f4a_7c15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn chance(&self, probability: f64) -> bool {
+        probability > 0.0 && (self.next() >> 11) as f64 / (1u64 << 53) as f64 <= probability
+    }
+
+    fn roll(&self) -> Chaos {
+        if self.chance(self.config.fail_probability) {
+            Chaos::Fail
+        } else if self.chance(self.config.delay_probability) {
+            let max = self.config.max_delay.as_nanos().max(1) as u64;
+            Chaos::Delay(Duration::from_nanos(self.next() % max))
+        } else if self.chance(self.config.yield_probability) {
+            Chaos::Yield(1 + (self.next() % 8) as u32)
+        } else {
+            Chaos::None
+        }
+    }
+
+    fn disturb(&self) -> Result<()> {
+        match self.roll() {
+            Chaos::Fail => return Err(LockError::WouldBlock),
+            Chaos::Delay(delay) => thread::sleep(delay),
+            Chaos::Yield(count) => (0..count).for_each(|_| thread::yield_now()),
+            Chaos::None => {}
+        }
+        Ok(())
+    }
+}
+
+impl<L, T> LockApi<T> for ChaosLock<L>
+where
+    L: LockApi<T>,
+{
+    type ReadGuard<'a>
+        = L::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = L::WriteGuard<'a>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.disturb()?;
+        self.inner.read()
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.disturb()?;
+        self.inner.write()
+    }
+
+    fn new(inner: T) -> Self {
+        ChaosLock::with_config(L::new(inner), ChaosConfig::default())
+    }
+}
+
+#[cfg(feature = "async")]
+pub use self::async_impl::ChaosFuture;
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{Chaos, ChaosConfig, ChaosLock};
+    use crate::{
+        async_locking::AsyncLockApi,
+        error::{LockError, Result},
+    };
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+    use pin_project_lite::pin_project;
+
+    impl<L> ChaosLock<L> {
+        fn future<F>(&self, future: F) -> ChaosFuture<F> {
+            // Delays become extra yields, since sleeping would block the executor.
+            let (fail, yields) = match self.roll() {
+                Chaos::Fail => (true, 0),
+                Chaos::Delay(delay) => (false, 1 + (delay.as_micros() % 16) as u32),
+                Chaos::Yield(count) => (false, count),
+                Chaos::None => (false, 0),
+            };
+            ChaosFuture {
+                future,
+                fail,
+                yields,
+            }
+        }
+    }
+
+    impl<L, T> AsyncLockApi<T> for ChaosLock<L>
+    where
+        L: AsyncLockApi<T>,
+    {
+        type ReadGuard<'a>
+            = L::ReadGuard<'a>
+        where
+            Self: 'a;
+
+        type WriteGuard<'a>
+            = L::WriteGuard<'a>
+        where
+            Self: 'a;
+
+        type ReadFuture<'a>
+            = ChaosFuture<L::ReadFuture<'a>>
+        where
+            Self: 'a;
+
+        type WriteFuture<'a>
+            = ChaosFuture<L::WriteFuture<'a>>
+        where
+            Self: 'a;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            self.future(self.inner.read())
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            self.future(self.inner.write())
+        }
+
+        fn new(inner: T) -> Self {
+            ChaosLock::with_config(L::new(inner), ChaosConfig::default())
+        }
+    }
+
+    pin_project! {
+        pub struct ChaosFuture<F> {
+            #[pin]
+            future: F,
+            fail: bool,
+            yields: u32,
+        }
+    }
+
+    impl<F, G> Future for ChaosFuture<F>
+    where
+        F: Future<Output = Result<G>>,
+    {
+        type Output = Result<G>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            if *this.fail {
+                return Poll::Ready(Err(LockError::WouldBlock));
+            }
+            if *this.yields > 0 {
+                *this.yields -= 1;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            this.future.poll(cx)
+        }
+    }
+}
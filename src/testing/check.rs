@@ -0,0 +1,344 @@
+use alloc::{format, sync::Arc};
+use core::{
+    marker::PhantomData,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use std::{sync::mpsc, thread};
+
+use crate::locking::{LockApi, LockApiReadGuard, LockApiWriteGuard};
+
+/// The value guarded by the lock under test. Writers bump both halves with a
+/// yield in between, so readers see them differ unless writes are exclusive.
+pub type Probe = (u64, u64);
+
+/// Hammers a [`LockApi`] implementation from several threads, panicking when
+/// a writer overlaps another guard, a reader sees a half-done write, a write
+/// is lost, or a dropped guard does not release the lock. A broken lock may
+/// also hang the hammering phase, so run it under a test timeout.
+///
+/// ```ignore
+/// LockCheck::new().shared_reads(true).run::<parking_lot::RwLock<_>>();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockCheck {
+    threads: usize,
+    iterations: usize,
+    shared_reads: bool,
+    snapshot_reads: bool,
+    timeout: Duration,
+}
+
+impl Default for LockCheck {
+    fn default() -> Self {
+        LockCheck::new()
+    }
+}
+
+impl LockCheck {
+    pub fn new() -> LockCheck {
+        LockCheck {
+            threads: 4,
+            iterations: 1000,
+            shared_reads: false,
+            snapshot_reads: false,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    pub fn threads(mut self, threads: usize) -> LockCheck {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Acquisitions per thread, every fourth of them a write.
+    pub fn iterations(mut self, iterations: usize) -> LockCheck {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Also checks that read guards can be held at the same time, for
+    /// read-write locks.
+    pub fn shared_reads(mut self, shared_reads: bool) -> LockCheck {
+        self.shared_reads = shared_reads;
+        self
+    }
+
+    /// Read guards are copies which do not keep writers out, as with
+    /// [`SeqLock`](crate::SeqLock), so only torn reads and lost writes are
+    /// checked for, not guards overlapping.
+    pub fn snapshot_reads(mut self, snapshot_reads: bool) -> LockCheck {
+        self.snapshot_reads = snapshot_reads;
+        self
+    }
+
+    /// How long the single-step checks wait before declaring the lock stuck.
+    pub fn timeout(mut self, timeout: Duration) -> LockCheck {
+        self.timeout = timeout;
+        self
+    }
+
+    pub fn run<L>(&self)
+    where
+        L: LockApi<Probe> + Send + Sync + 'static,
+    {
+        self.run_with::<L, Blocking>();
+    }
+
+    /// Like [`run`](LockCheck::run), through [`AsyncLockApi`]. Every thread
+    /// polls its own futures, so they need not be `Send`.
+    ///
+    /// [`AsyncLockApi`]: crate::AsyncLockApi
+    #[cfg(feature = "async")]
+    pub fn run_async<L>(&self)
+    where
+        L: crate::async_locking::AsyncLockApi<Probe> + Send + Sync + 'static,
+    {
+        self.run_with::<L, async_impl::Async>();
+    }
+
+    fn run_with<L, H>(&self)
+    where
+        L: Send + Sync + 'static,
+        H: Harness<L>,
+    {
+        let checked = Arc::new(Checked::<L, H> {
+            lock: H::create((0, 0)),
+            readers: AtomicUsize::new(0),
+            writers: AtomicUsize::new(0),
+            snapshot_reads: self.snapshot_reads,
+            _harness: PhantomData,
+        });
+
+        thread::scope(|scope| {
+            for _ in 0..self.threads {
+                scope.spawn(|| {
+                    for i in 0..self.iterations {
+                        match i % 4 {
+                            0 => checked.write(),
+                            _ => checked.read(),
+                        }
+                    }
+                });
+            }
+        });
+
+        // A dropped guard must let a writer on another thread in.
+        let write_after = |what: &str| {
+            let checked = checked.clone();
+            self.within(&format!("write after dropping a {what} guard"), move || {
+                checked.write()
+            });
+        };
+        checked.write();
+        write_after("write");
+        checked.read();
+        write_after("read");
+
+        if self.shared_reads {
+            self.check_shared_reads(&checked);
+        }
+
+        let expected = (self.threads * self.iterations.div_ceil(4) + 3) as u64;
+        let mut seen = 0;
+        H::read(&checked.lock, &mut |probe| seen = probe.0);
+        assert_eq!(seen, expected, "lost writes");
+    }
+
+    fn check_shared_reads<L, H>(&self, checked: &Arc<Checked<L, H>>)
+    where
+        L: Send + Sync + 'static,
+        H: Harness<L>,
+    {
+        let (held_tx, held) = mpsc::channel();
+        let (release, release_rx) = mpsc::channel::<()>();
+        let holder = {
+            let checked = checked.clone();
+            thread::spawn(move || {
+                H::read(&checked.lock, &mut |probe| {
+                    checked.enter_read(probe);
+                    held_tx.send(()).unwrap();
+                    release_rx.recv().ok();
+                    checked.readers.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+        };
+        held.recv().expect("reader panicked");
+
+        let other = checked.clone();
+        self.within("read while another read guard is held", move || {
+            other.read()
+        });
+        release.send(()).unwrap();
+        holder.join().unwrap();
+    }
+
+    // Runs `f` on its own thread and panics if it does not finish in time.
+    fn within(&self, what: &str, f: impl FnOnce() + Send + 'static) {
+        let (done, finished) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            f();
+            done.send(()).ok();
+        });
+        match finished.recv_timeout(self.timeout) {
+            Ok(()) => handle.join().unwrap(),
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                if let Err(panic) = handle.join() {
+                    std::panic::resume_unwind(panic)
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                panic!("{what}: still blocked after {:?}", self.timeout)
+            }
+        }
+    }
+}
+
+/// Runs the default [`LockCheck`] against `L`, without checking that reads
+/// are shared.
+pub fn check_lock_api<L>()
+where
+    L: LockApi<Probe> + Send + Sync + 'static,
+{
+    LockCheck::new().run::<L>();
+}
+
+// Takes guards either through `LockApi` or `AsyncLockApi`.
+trait Harness<L>: 'static {
+    fn create(probe: Probe) -> L;
+
+    fn read(lock: &L, f: &mut dyn FnMut(&Probe));
+
+    fn write(lock: &L, f: &mut dyn FnMut(&mut Probe));
+}
+
+struct Blocking;
+
+impl<L> Harness<L> for Blocking
+where
+    L: LockApi<Probe>,
+{
+    fn create(probe: Probe) -> L {
+        L::new(probe)
+    }
+
+    fn read(lock: &L, f: &mut dyn FnMut(&Probe)) {
+        f(lock.read().expect("read failed").get())
+    }
+
+    fn write(lock: &L, f: &mut dyn FnMut(&mut Probe)) {
+        f(lock.write().expect("write failed").get_mut())
+    }
+}
+
+// The lock under test, with the guards the harness believes are held.
+struct Checked<L, H> {
+    lock: L,
+    readers: AtomicUsize,
+    writers: AtomicUsize,
+    snapshot_reads: bool,
+    _harness: PhantomData<fn() -> H>,
+}
+
+impl<L, H> Checked<L, H>
+where
+    H: Harness<L>,
+{
+    fn enter_read(&self, probe: &Probe) {
+        self.readers.fetch_add(1, Ordering::SeqCst);
+        if !self.snapshot_reads {
+            assert_eq!(
+                self.writers.load(Ordering::SeqCst),
+                0,
+                "read guard held alongside a write guard"
+            );
+        }
+        assert_eq!(probe.0, probe.1, "read saw a half-done write");
+    }
+
+    fn read(&self) {
+        H::read(&self.lock, &mut |probe| {
+            self.enter_read(probe);
+            self.readers.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+
+    fn write(&self) {
+        H::write(&self.lock, &mut |probe| {
+            assert_eq!(
+                self.writers.fetch_add(1, Ordering::SeqCst),
+                0,
+                "two write guards held at once"
+            );
+            if !self.snapshot_reads {
+                assert_eq!(
+                    self.readers.load(Ordering::SeqCst),
+                    0,
+                    "write guard held alongside a read guard"
+                );
+            }
+            probe.0 += 1;
+            thread::yield_now();
+            probe.1 += 1;
+            self.writers.fetch_sub(1, Ordering::SeqCst);
+        });
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{Harness, Probe};
+    use crate::{
+        async_locking::AsyncLockApi,
+        locking::{LockApiReadGuard, LockApiWriteGuard},
+    };
+    use alloc::sync::Arc;
+    use core::{
+        future::Future,
+        pin::pin,
+        task::{Context, Poll, Waker},
+    };
+    use std::{
+        task::Wake,
+        thread::{self, Thread},
+    };
+
+    pub(super) struct Async;
+
+    impl<L> Harness<L> for Async
+    where
+        L: AsyncLockApi<Probe>,
+    {
+        fn create(probe: Probe) -> L {
+            L::new(probe)
+        }
+
+        fn read(lock: &L, f: &mut dyn FnMut(&Probe)) {
+            f(block_on(lock.read()).expect("read failed").get())
+        }
+
+        fn write(lock: &L, f: &mut dyn FnMut(&mut Probe)) {
+            f(block_on(lock.write()).expect("write failed").get_mut())
+        }
+    }
+
+    struct Unpark(Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = pin!(future);
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            thread::park();
+        }
+    }
+}
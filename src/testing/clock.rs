@@ -0,0 +1,129 @@
+use alloc::sync::Arc;
+use core::{cell::RefCell, time::Duration};
+use std::{
+    sync::{Mutex, PoisonError},
+    time::Instant,
+};
+
+use crate::clock::Clock;
+
+std::thread_local! {
+    static CLOCK: RefCell<Option<Arc<dyn Clock>>> = const { RefCell::new(None) };
+}
+
+pub(crate) fn thread_clock_now() -> Option<Instant> {
+    CLOCK.with(|clock| clock.borrow().as_ref().map(|clock| clock.now()))
+}
+
+/// Makes timeouts and deadlines computed by this crate on the current thread,
+/// and the wait and hold times measured by its wrappers, use `clock` until
+/// the returned guard is dropped.
+///
+/// Backends which park with a deadline, like parking_lot, keep waiting in
+/// real time; the retrying default methods of
+/// [`TimedLockApi`](crate::TimedLockApi) follow the clock.
+pub fn set_thread_clock(clock: Arc<dyn Clock>) -> ThreadClockGuard {
+    let previous = CLOCK.with(|current| current.replace(Some(clock)));
+    ThreadClockGuard { previous }
+}
+
+#[must_use = "the clock is reset when the guard is dropped"]
+pub struct ThreadClockGuard {
+    previous: Option<Arc<dyn Clock>>,
+}
+
+impl Drop for ThreadClockGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        CLOCK.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+struct MockState {
+    elapsed: Duration,
+    step: Duration,
+}
+
+/// A clock which only moves when told to: by [`advance`](MockClock::advance),
+/// or by a fixed step on every reading. Clones share the same time, so one
+/// can be installed with [`set_thread_clock`] and advanced from another thread.
+#[derive(Clone)]
+pub struct MockClock {
+    start: Instant,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        MockClock::new()
+    }
+}
+
+impl MockClock {
+    pub fn new() -> MockClock {
+        MockClock {
+            start: Instant::now(),
+            state: Arc::new(Mutex::new(MockState {
+                elapsed: Duration::ZERO,
+                step: Duration::ZERO,
+            })),
+        }
+    }
+
+    /// Moves the clock forward by `step` after every reading, so a loop
+    /// waiting for a deadline runs a predictable number of times.
+    pub fn with_step(self, step: Duration) -> MockClock {
+        self.lock().step = step;
+        self
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.lock().elapsed += duration;
+    }
+
+    /// The time passed since the clock was created.
+    pub fn elapsed(&self) -> Duration {
+        self.lock().elapsed
+    }
+
+    /// Installs a clone of this clock for the current thread, see
+    /// [`set_thread_clock`].
+    pub fn install(&self) -> ThreadClockGuard {
+        set_thread_clock(Arc::new(self.clone()))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        let mut state = self.lock();
+        let now = self.start + state.elapsed;
+        let step = state.step;
+        state.elapsed += step;
+        now
+    }
+}
+
+impl core::fmt::Debug for MockClock {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MockClock")
+            .field("elapsed", &self.elapsed())
+            .finish()
+    }
+}
+
+/// Reads tokio's clock, which stands still under `tokio::time::pause` and
+/// moves with `tokio::time::advance`.
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioClock;
+
+#[cfg(feature = "tokio")]
+impl Clock for TokioClock {
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+}
@@ -0,0 +1,217 @@
+use alloc::collections::VecDeque;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+use std::sync::{
+    Mutex, MutexGuard, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard, TryLockError,
+};
+
+use crate::{
+    async_locking::AsyncLockApi,
+    error::{LockError, Result},
+    locking::AccessMode,
+};
+
+struct Waiter {
+    id: u64,
+    mode: AccessMode,
+    granted: bool,
+    waker: Option<Waker>,
+}
+
+#[derive(Default)]
+struct State {
+    next_id: u64,
+    queue: VecDeque<Waiter>,
+}
+
+/// An async lock whose acquisitions only complete when the test grants them,
+/// so tasks contending for a locket can be interleaved deterministically.
+/// Acquisitions queue up in the order their futures were created.
+pub struct ManualAsyncLock<T> {
+    data: RwLock<T>,
+    state: Mutex<State>,
+}
+
+impl<T> ManualAsyncLock<T> {
+    pub fn new(inner: T) -> ManualAsyncLock<T> {
+        ManualAsyncLock {
+            data: RwLock::new(inner),
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    /// Number of acquisitions waiting to be granted.
+    pub fn pending(&self) -> usize {
+        self.state().queue.iter().filter(|w| !w.granted).count()
+    }
+
+    pub fn pending_reads(&self) -> usize {
+        self.pending_of(AccessMode::Read)
+    }
+
+    pub fn pending_writes(&self) -> usize {
+        self.pending_of(AccessMode::Write)
+    }
+
+    /// Grants the oldest pending acquisition. Returns `false` if none is waiting.
+    pub fn release_next(&self) -> bool {
+        self.grant(|_| true)
+    }
+
+    /// Grants the oldest pending read acquisition.
+    pub fn grant_read(&self) -> bool {
+        self.grant(|mode| mode == AccessMode::Read)
+    }
+
+    /// Grants the oldest pending write acquisition.
+    pub fn grant_write(&self) -> bool {
+        self.grant(|mode| mode == AccessMode::Write)
+    }
+
+    /// Grants every pending acquisition, returning how many were granted.
+    pub fn grant_all(&self) -> usize {
+        let mut count = 0;
+        while self.release_next() {
+            count += 1;
+        }
+        count
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn pending_of(&self, mode: AccessMode) -> usize {
+        self.state()
+            .queue
+            .iter()
+            .filter(|w| !w.granted && w.mode == mode)
+            .count()
+    }
+
+    fn grant(&self, filter: impl Fn(AccessMode) -> bool) -> bool {
+        let mut state = self.state();
+        match state
+            .queue
+            .iter_mut()
+            .find(|w| !w.granted && filter(w.mode))
+        {
+            Some(waiter) => {
+                waiter.granted = true;
+                if let Some(waker) = waiter.waker.take() {
+                    waker.wake();
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn enqueue<'a, G>(
+        &'a self,
+        mode: AccessMode,
+        acquire: fn(&'a RwLock<T>) -> Result<G>,
+    ) -> ManualFuture<'a, T, G> {
+        let mut state = self.state();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.queue.push_back(Waiter {
+            id,
+            mode,
+            granted: false,
+            waker: None,
+        });
+        ManualFuture {
+            lock: self,
+            id,
+            acquire,
+            done: false,
+        }
+    }
+}
+
+fn try_read<T>(lock: &RwLock<T>) -> Result<RwLockReadGuard<'_, T>> {
+    lock.try_read().map_err(|err| match err {
+        TryLockError::Poisoned(_) => LockError::Poisoned,
+        TryLockError::WouldBlock => LockError::WouldBlock,
+    })
+}
+
+fn try_write<T>(lock: &RwLock<T>) -> Result<RwLockWriteGuard<'_, T>> {
+    lock.try_write().map_err(|err| match err {
+        TryLockError::Poisoned(_) => LockError::Poisoned,
+        TryLockError::WouldBlock => LockError::WouldBlock,
+    })
+}
+
+impl<T> AsyncLockApi<T> for ManualAsyncLock<T>
+where
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = RwLockReadGuard<'a, T>;
+
+    type WriteGuard<'a> = RwLockWriteGuard<'a, T>;
+
+    type ReadFuture<'a> = ManualFuture<'a, T, Self::ReadGuard<'a>>;
+
+    type WriteFuture<'a> = ManualFuture<'a, T, Self::WriteGuard<'a>>;
+
+    fn read(&self) -> Self::ReadFuture<'_> {
+        self.enqueue(AccessMode::Read, try_read)
+    }
+
+    fn write(&self) -> Self::WriteFuture<'_> {
+        self.enqueue(AccessMode::Write, try_write)
+    }
+
+    fn new(inner: T) -> Self {
+        ManualAsyncLock::new(inner)
+    }
+}
+
+/// Resolves once granted. Granting an acquisition which conflicts with a guard
+/// that is still held resolves it with `LockError::WouldBlock`. Dropping the
+/// future withdraws the acquisition from the queue.
+pub struct ManualFuture<'a, T, G> {
+    lock: &'a ManualAsyncLock<T>,
+    id: u64,
+    acquire: fn(&'a RwLock<T>) -> Result<G>,
+    done: bool,
+}
+
+impl<T, G> Future for ManualFuture<'_, T, G> {
+    type Output = Result<G>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let lock = self.lock;
+        let mut state = lock.state();
+        let Some(idx) = state.queue.iter().position(|w| w.id == self.id) else {
+            panic!("ManualFuture polled after completion");
+        };
+        if !state.queue[idx].granted {
+            state.queue[idx].waker = Some(cx.waker().clone());
+            return Poll::Pending;
+        }
+        state.queue.remove(idx);
+        drop(state);
+        self.done = true;
+        Poll::Ready((self.acquire)(&lock.data))
+    }
+}
+
+impl<T, G> Drop for ManualFuture<'_, T, G> {
+    fn drop(&mut self) {
+        if !self.done {
+            self.lock.state().queue.retain(|w| w.id != self.id);
+        }
+    }
+}
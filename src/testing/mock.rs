@@ -0,0 +1,372 @@
+use alloc::vec::Vec;
+use core::{
+    ops::{Deref, DerefMut},
+    panic::Location,
+};
+use std::{
+    sync::{Mutex, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    thread::{self, ThreadId},
+    time::Instant,
+};
+
+use crate::{
+    error::{LockError, Result},
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MockEventKind {
+    ReadAttempt,
+    ReadAcquired,
+    ReadReleased,
+    WriteAttempt,
+    WriteAcquired,
+    WriteReleased,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MockEvent {
+    pub seq: usize,
+    pub kind: MockEventKind,
+    pub location: &'static Location<'static>,
+    pub thread: ThreadId,
+    pub at: Instant,
+}
+
+#[derive(Default)]
+struct Log {
+    // Bumped by `clear`, so releases of guards acquired before it are dropped
+    // along with their acquisitions.
+    generation: usize,
+    events: Vec<MockEvent>,
+}
+
+impl Log {
+    fn push(&mut self, kind: MockEventKind, location: &'static Location<'static>) {
+        let seq = self.events.len();
+        self.events.push(MockEvent {
+            seq,
+            kind,
+            location,
+            thread: thread::current().id(),
+            at: crate::clock::now(),
+        });
+    }
+}
+
+#[derive(Default)]
+struct Recorder {
+    log: Mutex<Log>,
+    // The threads holding a guard, and whether it is a write guard.
+    holders: Mutex<Vec<(ThreadId, bool)>>,
+}
+
+impl Recorder {
+    fn log(&self) -> std::sync::MutexGuard<'_, Log> {
+        self.log.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn holders(&self) -> std::sync::MutexGuard<'_, Vec<(ThreadId, bool)>> {
+        self.holders.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // The std lock behind the mock would deadlock if this thread wrote while
+    // holding a guard, or read while holding the write guard.
+    fn check_reentry(&self, write: bool) -> Result<()> {
+        let current = thread::current().id();
+        let reentry = self
+            .holders()
+            .iter()
+            .any(|&(thread, held_write)| thread == current && (write || held_write));
+        match reentry {
+            true => Err(LockError::WouldBlock),
+            false => Ok(()),
+        }
+    }
+
+    fn hold(&self, write: bool) {
+        self.holders().push((thread::current().id(), write));
+    }
+
+    fn unhold(&self, write: bool) {
+        let current = (thread::current().id(), write);
+        let mut holders = self.holders();
+        if let Some(idx) = holders.iter().position(|holder| *holder == current) {
+            holders.swap_remove(idx);
+        }
+    }
+
+    /// Records `kind` and returns the generation it was recorded in.
+    fn record(&self, kind: MockEventKind, location: &'static Location<'static>) -> usize {
+        let mut log = self.log();
+        log.push(kind, location);
+        log.generation
+    }
+
+    fn record_release(
+        &self,
+        kind: MockEventKind,
+        location: &'static Location<'static>,
+        generation: usize,
+    ) {
+        let mut log = self.log();
+        if log.generation == generation {
+            log.push(kind, location);
+        }
+    }
+}
+
+/// A test backend recording every acquisition and release, so tests can assert
+/// on the locking discipline of code generic over `LockApi`/`AsyncLockApi`.
+/// Async acquisitions block the calling thread.
+///
+/// A thread writing while it holds a guard, or reading while it holds the
+/// write guard, would wait for itself forever; such an acquisition fails with
+/// [`LockError::WouldBlock`] instead, after recording the attempt.
+pub struct MockLock<T> {
+    data: RwLock<T>,
+    recorder: Recorder,
+}
+
+impl<T> MockLock<T> {
+    pub fn new(inner: T) -> MockLock<T> {
+        MockLock {
+            data: RwLock::new(inner),
+            recorder: Recorder::default(),
+        }
+    }
+
+    pub fn events(&self) -> Vec<MockEvent> {
+        self.recorder.log().events.clone()
+    }
+
+    /// Forgets the recorded events. Guards held across the call are not
+    /// recorded as released either.
+    pub fn clear(&self) {
+        let mut log = self.recorder.log();
+        log.events.clear();
+        log.generation += 1;
+    }
+
+    pub fn count(&self, kind: MockEventKind) -> usize {
+        self.events()
+            .iter()
+            .filter(|event| event.kind == kind)
+            .count()
+    }
+
+    pub fn reads(&self) -> usize {
+        self.count(MockEventKind::ReadAcquired)
+    }
+
+    pub fn writes(&self) -> usize {
+        self.count(MockEventKind::WriteAcquired)
+    }
+
+    pub fn max_concurrent_readers(&self) -> usize {
+        let mut readers = 0usize;
+        let mut max = 0;
+        for event in self.events() {
+            match event.kind {
+                MockEventKind::ReadAcquired => {
+                    readers += 1;
+                    max = max.max(readers);
+                }
+                MockEventKind::ReadReleased => readers -= 1,
+                _ => {}
+            }
+        }
+        max
+    }
+
+    /// Panics if a write was attempted while a read guard was outstanding.
+    #[track_caller]
+    pub fn assert_no_write_while_reading(&self) {
+        let mut readers = Vec::new();
+        for event in self.events() {
+            match event.kind {
+                MockEventKind::ReadAcquired => readers.push(event),
+                MockEventKind::ReadReleased => {
+                    if let Some(idx) = readers.iter().position(|r| r.thread == event.thread) {
+                        readers.remove(idx);
+                    }
+                }
+                MockEventKind::WriteAttempt => {
+                    if let Some(reader) = readers.first() {
+                        panic!(
+                            "write attempted at {} while a read guard acquired at {} was outstanding",
+                            event.location, reader.location
+                        );
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Panics if any guard has not been released.
+    #[track_caller]
+    pub fn assert_released(&self) {
+        let acquired = self.reads() + self.writes();
+        let released =
+            self.count(MockEventKind::ReadReleased) + self.count(MockEventKind::WriteReleased);
+        assert_eq!(
+            acquired,
+            released,
+            "{} guard(s) still outstanding",
+            acquired - released
+        );
+    }
+
+    pub fn into_inner(self) -> T {
+        self.data
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn acquire_read(
+        &self,
+        location: &'static Location<'static>,
+    ) -> Result<MockGuard<'_, RwLockReadGuard<'_, T>>> {
+        self.recorder.record(MockEventKind::ReadAttempt, location);
+        self.recorder.check_reentry(false)?;
+        let guard = self.data.read().map_err(|_| LockError::Poisoned)?;
+        self.recorder.hold(false);
+        let generation = self.recorder.record(MockEventKind::ReadAcquired, location);
+        Ok(MockGuard {
+            guard,
+            recorder: &self.recorder,
+            write: false,
+            released: MockEventKind::ReadReleased,
+            location,
+            generation,
+        })
+    }
+
+    fn acquire_write(
+        &self,
+        location: &'static Location<'static>,
+    ) -> Result<MockGuard<'_, RwLockWriteGuard<'_, T>>> {
+        self.recorder.record(MockEventKind::WriteAttempt, location);
+        self.recorder.check_reentry(true)?;
+        let guard = self.data.write().map_err(|_| LockError::Poisoned)?;
+        self.recorder.hold(true);
+        let generation = self.recorder.record(MockEventKind::WriteAcquired, location);
+        Ok(MockGuard {
+            guard,
+            recorder: &self.recorder,
+            write: true,
+            released: MockEventKind::WriteReleased,
+            location,
+            generation,
+        })
+    }
+}
+
+impl<T> LockApi<T> for MockLock<T>
+where
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = MockGuard<'a, RwLockReadGuard<'a, T>>;
+
+    type WriteGuard<'a> = MockGuard<'a, RwLockWriteGuard<'a, T>>;
+
+    #[track_caller]
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.acquire_read(Location::caller())
+    }
+
+    #[track_caller]
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.acquire_write(Location::caller())
+    }
+
+    fn new(inner: T) -> Self {
+        MockLock::new(inner)
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T> crate::async_locking::AsyncLockApi<T> for MockLock<T>
+where
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = MockGuard<'a, RwLockReadGuard<'a, T>>;
+
+    type WriteGuard<'a> = MockGuard<'a, RwLockWriteGuard<'a, T>>;
+
+    type ReadFuture<'a> = core::future::Ready<Result<Self::ReadGuard<'a>>>;
+
+    type WriteFuture<'a> = core::future::Ready<Result<Self::WriteGuard<'a>>>;
+
+    #[track_caller]
+    fn read(&self) -> Self::ReadFuture<'_> {
+        core::future::ready(self.acquire_read(Location::caller()))
+    }
+
+    #[track_caller]
+    fn write(&self) -> Self::WriteFuture<'_> {
+        core::future::ready(self.acquire_write(Location::caller()))
+    }
+
+    fn new(inner: T) -> Self {
+        MockLock::new(inner)
+    }
+}
+
+pub struct MockGuard<'a, G> {
+    guard: G,
+    recorder: &'a Recorder,
+    write: bool,
+    released: MockEventKind,
+    location: &'static Location<'static>,
+    generation: usize,
+}
+
+// The std guard inside is not `Send`, so this runs on the thread which
+// acquired it.
+impl<G> Drop for MockGuard<'_, G> {
+    fn drop(&mut self) {
+        self.recorder.unhold(self.write);
+        self.recorder
+            .record_release(self.released, self.location, self.generation);
+    }
+}
+
+impl<G> Deref for MockGuard<'_, G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<G> DerefMut for MockGuard<'_, G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, T, G> LockApiReadGuard<'a, T> for MockGuard<'a, G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.get()
+    }
+}
+
+impl<'a, T, G> LockApiWriteGuard<'a, T> for MockGuard<'a, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.get_mut()
+    }
+}
@@ -0,0 +1,11 @@
+mod chaos;
+mod check;
+mod clock;
+#[cfg(feature = "async")]
+mod manual;
+mod mock;
+
+pub use self::{chaos::*, check::*, clock::*, mock::*};
+
+#[cfg(feature = "async")]
+pub use self::manual::*;
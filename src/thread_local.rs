@@ -0,0 +1,172 @@
+use alloc::{
+    boxed::Box,
+    sync::{Arc, Weak},
+    vec::Vec,
+};
+use core::{
+    any::Any,
+    cell::RefCell,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::{
+    error::{LockError, Result},
+    locking::LockApi,
+    poison::PoisonApi,
+};
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    // Lock id to this thread's `Weak<Mutex<T>>`. The lock owns the values, so
+    // dropping it frees them even while the threads live on.
+    static SLOTS: RefCell<HashMap<usize, Box<dyn Any>>> = RefCell::new(HashMap::new());
+}
+
+type Init<T> = Box<dyn Fn() -> T + Send + Sync>;
+
+/// Gives every thread its own instance of `T`, created on first access. The
+/// per-thread values are only contended while being visited by
+/// [`for_each`](ThreadLocalLock::for_each) and friends.
+///
+/// Values of exited threads are kept until the lock is dropped.
+pub struct ThreadLocalLock<T> {
+    id: usize,
+    init: Init<T>,
+    values: Mutex<Vec<Arc<Mutex<T>>>>,
+}
+
+impl<T> ThreadLocalLock<T>
+where
+    T: Send + 'static,
+{
+    pub fn new(init: impl Fn() -> T + Send + Sync + 'static) -> ThreadLocalLock<T> {
+        ThreadLocalLock {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            init: Box::new(init),
+            values: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// The number of threads which have accessed the lock.
+    pub fn len(&self) -> usize {
+        self.values.lock().map_or(0, |values| values.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn for_each(&self, mut f: impl FnMut(&T)) -> Result<()> {
+        self.for_each_mut(|value| f(value))
+    }
+
+    pub fn for_each_mut(&self, mut f: impl FnMut(&mut T)) -> Result<()> {
+        let values = self.values.lock().map_err(|_| LockError::Poisoned)?;
+        for value in values.iter() {
+            f(&mut *value.lock().map_err(|_| LockError::Poisoned)?);
+        }
+        Ok(())
+    }
+
+    pub fn into_values(self) -> Result<Vec<T>> {
+        let values = self.values.into_inner().map_err(|_| LockError::Poisoned)?;
+        values
+            .into_iter()
+            .map(|value| match Arc::try_unwrap(value) {
+                Ok(value) => value.into_inner().map_err(|_| LockError::Poisoned),
+                // Only this lock holds strong references.
+                Err(_) => unreachable!("thread local value is still shared"),
+            })
+            .collect()
+    }
+
+    fn local(&self) -> Result<&Mutex<T>> {
+        let value = SLOTS.with(|slots| {
+            slots
+                .borrow()
+                .get(&self.id)
+                .and_then(|slot| slot.downcast_ref::<Weak<Mutex<T>>>())
+                .and_then(Weak::upgrade)
+        });
+
+        let value = match value {
+            Some(value) => value,
+            None => {
+                let value = Arc::new(Mutex::new((self.init)()));
+                SLOTS.with(|slots| {
+                    slots
+                        .borrow_mut()
+                        .insert(self.id, Box::new(Arc::downgrade(&value)))
+                });
+                self.values
+                    .lock()
+                    .map_err(|_| LockError::Poisoned)?
+                    .push(value.clone());
+                value
+            }
+        };
+
+        // SAFETY: `values` keeps the allocation alive until `self` is dropped
+        // and entries are never removed.
+        Ok(unsafe { &*Arc::as_ptr(&value) })
+    }
+}
+
+impl<T> Default for ThreadLocalLock<T>
+where
+    T: Default + Send + 'static,
+{
+    fn default() -> Self {
+        ThreadLocalLock::new(T::default)
+    }
+}
+
+impl<T> LockApi<T> for ThreadLocalLock<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    type ReadGuard<'a> = MutexGuard<'a, T>;
+
+    type WriteGuard<'a> = MutexGuard<'a, T>;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.local()?.lock().map_err(|_| LockError::Poisoned)
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.local()?.lock().map_err(|_| LockError::Poisoned)
+    }
+
+    /// Every thread starts out with a clone of `inner`.
+    fn new(inner: T) -> Self {
+        ThreadLocalLock::new(move || inner.clone())
+    }
+}
+
+impl<T> PoisonApi for ThreadLocalLock<T> {
+    fn is_poisoned(&self) -> bool {
+        self.values.lock().map_or(true, |values| {
+            values.iter().any(|value| value.is_poisoned())
+        })
+    }
+
+    fn clear_poison(&self) {
+        self.values.clear_poison();
+        if let Ok(values) = self.values.lock() {
+            values.iter().for_each(|value| value.clear_poison());
+        }
+    }
+}
+
+impl<T> core::fmt::Debug for ThreadLocalLock<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ThreadLocalLock")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
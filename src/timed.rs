@@ -0,0 +1,399 @@
+use alloc::{rc::Rc, sync::Arc};
+use core::{cell::RefCell, time::Duration};
+use std::time::Instant;
+
+use crate::{
+    backoff::Backoff,
+    error::{LockError, Result},
+    locking::LockApi,
+    try_lock::TryLockApi,
+};
+
+/// Acquisition bounded in time. Fails with [`LockError::Timeout`] when the lock
+/// could not be taken before the deadline.
+///
+/// The `_until` variants take an absolute deadline, so several acquisitions
+/// can share one overall budget. The default methods retry the `try_`
+/// variants with an exponential backoff; backends which can park a thread
+/// with a deadline override them.
+pub trait TimedLockApi<T>: TryLockApi<T> {
+    fn read_until(&self, deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+        retry_until(deadline, || self.try_read())
+    }
+
+    fn write_until(&self, deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+        retry_until(deadline, || self.try_write())
+    }
+
+    fn read_timeout(&self, timeout: Duration) -> Result<Self::ReadGuard<'_>> {
+        match crate::clock::now().checked_add(timeout) {
+            Some(deadline) => self.read_until(deadline),
+            None => self.read(),
+        }
+    }
+
+    fn write_timeout(&self, timeout: Duration) -> Result<Self::WriteGuard<'_>> {
+        match crate::clock::now().checked_add(timeout) {
+            Some(deadline) => self.write_until(deadline),
+            None => self.write(),
+        }
+    }
+}
+
+fn retry_until<G>(deadline: Instant, mut attempt: impl FnMut() -> Result<G>) -> Result<G> {
+    let mut snooze = Backoff::Exponential { limit: 6 }.start();
+    loop {
+        match attempt() {
+            Err(LockError::WouldBlock) => {}
+            ret => return ret,
+        }
+        if crate::clock::now() >= deadline {
+            return Err(LockError::Timeout);
+        }
+        snooze.snooze();
+    }
+}
+
+/// Applies a timeout to every blocking acquisition of the inner lock, so a
+/// bound on lock waits is a property of the locket rather than of each call
+/// site. `read` and `write` fail with [`LockError::Timeout`] once it passes.
+pub struct Timed<L> {
+    inner: L,
+    timeout: Duration,
+}
+
+impl<L> Timed<L> {
+    /// The timeout of lockets created through [`LockApi::new`].
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(1);
+
+    pub const fn with_timeout(inner: L, timeout: Duration) -> Timed<L> {
+        Timed { inner, timeout }
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    // The timeout, capped at the crate-wide maximum wait.
+    fn effective_timeout(&self) -> Duration {
+        #[cfg(feature = "max-wait")]
+        if let Some(max) = crate::config::max_wait() {
+            return self.timeout.min(max);
+        }
+        self.timeout
+    }
+}
+
+impl<L, T> LockApi<T> for Timed<L>
+where
+    L: TimedLockApi<T>,
+{
+    type ReadGuard<'a>
+        = L::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = L::WriteGuard<'a>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.inner.read_timeout(self.effective_timeout())
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.inner.write_timeout(self.effective_timeout())
+    }
+
+    fn new(inner: T) -> Self {
+        Timed::with_timeout(L::new(inner), Self::DEFAULT_TIMEOUT)
+    }
+}
+
+impl<L, T> TryLockApi<T> for Timed<L>
+where
+    L: TimedLockApi<T>,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.inner.try_read()
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.inner.try_write()
+    }
+}
+
+// An explicit deadline replaces the default timeout.
+impl<L, T> TimedLockApi<T> for Timed<L>
+where
+    L: TimedLockApi<T>,
+{
+    fn read_until(&self, deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+        self.inner.read_until(deadline)
+    }
+
+    fn write_until(&self, deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+        self.inner.write_until(deadline)
+    }
+}
+
+impl<L> core::fmt::Debug for Timed<L>
+where
+    L: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Timed")
+            .field("inner", &self.inner)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+/// Makes every blocking acquisition of the inner lock honor the crate-wide
+/// [`max_wait`](crate::config::max_wait), failing with [`LockError::Timeout`]
+/// once it passes, and wait as long as needed while none is set. Wrap the
+/// backend with it, e.g. `Traced<Bounded<L>>`, to bound the acquisitions of
+/// an instrumented locket.
+#[cfg(feature = "max-wait")]
+pub struct Bounded<L> {
+    inner: L,
+}
+
+#[cfg(feature = "max-wait")]
+impl<L> Bounded<L> {
+    pub const fn wrap(inner: L) -> Bounded<L> {
+        Bounded { inner }
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+}
+
+#[cfg(feature = "max-wait")]
+impl<L, T> LockApi<T> for Bounded<L>
+where
+    L: TimedLockApi<T>,
+{
+    type ReadGuard<'a>
+        = L::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = L::WriteGuard<'a>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        match crate::config::max_wait() {
+            Some(max) => self.inner.read_timeout(max),
+            None => self.inner.read(),
+        }
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        match crate::config::max_wait() {
+            Some(max) => self.inner.write_timeout(max),
+            None => self.inner.write(),
+        }
+    }
+
+    fn new(inner: T) -> Self {
+        Bounded::wrap(L::new(inner))
+    }
+}
+
+#[cfg(feature = "max-wait")]
+impl<L, T> TryLockApi<T> for Bounded<L>
+where
+    L: TimedLockApi<T>,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.inner.try_read()
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.inner.try_write()
+    }
+}
+
+// An explicit deadline is kept even past the maximum wait.
+#[cfg(feature = "max-wait")]
+impl<L, T> TimedLockApi<T> for Bounded<L>
+where
+    L: TimedLockApi<T>,
+{
+    fn read_until(&self, deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+        self.inner.read_until(deadline)
+    }
+
+    fn write_until(&self, deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+        self.inner.write_until(deadline)
+    }
+}
+
+#[cfg(feature = "max-wait")]
+impl<L> core::fmt::Debug for Bounded<L>
+where
+    L: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Bounded")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<L, T> TimedLockApi<T> for Arc<L>
+where
+    L: TimedLockApi<T>,
+    for<'a> L: 'a,
+{
+    fn read_until(&self, deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+        (**self).read_until(deadline)
+    }
+
+    fn write_until(&self, deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+        (**self).write_until(deadline)
+    }
+}
+
+impl<L, T> TimedLockApi<T> for Rc<L>
+where
+    L: TimedLockApi<T>,
+    for<'a> L: 'a,
+{
+    fn read_until(&self, deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+        (**self).read_until(deadline)
+    }
+
+    fn write_until(&self, deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+        (**self).write_until(deadline)
+    }
+}
+
+// Nobody else can release a `RefCell` while we wait, so do not wait at all.
+// `AtomicRefCell` borrows conflicting with ours are bugs, so neither does it.
+impl<T> TimedLockApi<T> for RefCell<T>
+where
+    for<'a> T: 'a,
+{
+    fn read_until(&self, _deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+        self.try_read()
+    }
+
+    fn write_until(&self, _deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+        self.try_write()
+    }
+}
+
+#[cfg(feature = "atomic_refcell")]
+impl<T> TimedLockApi<T> for atomic_refcell::AtomicRefCell<T>
+where
+    for<'a> T: 'a,
+{
+    fn read_until(&self, _deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+        self.try_read()
+    }
+
+    fn write_until(&self, _deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+        self.try_write()
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+mod parking_lot_impl {
+    use super::TimedLockApi;
+    use crate::error::{LockError, Result};
+    use parking_lot::{FairMutex, Mutex, RwLock};
+    use std::time::Instant;
+
+    impl<T> TimedLockApi<T> for Mutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        fn read_until(&self, deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+            self.try_lock_until(deadline).ok_or(LockError::Timeout)
+        }
+
+        fn write_until(&self, deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+            self.try_lock_until(deadline).ok_or(LockError::Timeout)
+        }
+    }
+
+    impl<T> TimedLockApi<T> for FairMutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        fn read_until(&self, deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+            self.try_lock_until(deadline).ok_or(LockError::Timeout)
+        }
+
+        fn write_until(&self, deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+            self.try_lock_until(deadline).ok_or(LockError::Timeout)
+        }
+    }
+
+    impl<T> TimedLockApi<T> for RwLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        fn read_until(&self, deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+            self.try_read_until(deadline).ok_or(LockError::Timeout)
+        }
+
+        fn write_until(&self, deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+            self.try_write_until(deadline).ok_or(LockError::Timeout)
+        }
+    }
+}
+
+#[cfg(feature = "spin")]
+mod spin_impl {
+    use super::TimedLockApi;
+    use spin::{mutex::Mutex, relax::RelaxStrategy, rwlock::RwLock};
+
+    impl<T, R> TimedLockApi<T> for Mutex<T, R>
+    where
+        R: RelaxStrategy,
+        for<'a> T: 'a,
+        for<'a> R: 'a,
+    {
+    }
+
+    impl<T, R> TimedLockApi<T> for RwLock<T, R>
+    where
+        R: RelaxStrategy,
+        for<'a> T: 'a,
+        for<'a> R: 'a,
+    {
+    }
+}
+
+#[cfg(feature = "std-lock")]
+mod std_impl {
+    use super::TimedLockApi;
+    use std::sync::{Mutex, RwLock};
+
+    impl<T> TimedLockApi<T> for Mutex<T> where for<'a> T: 'a {}
+
+    impl<T> TimedLockApi<T> for RwLock<T> where for<'a> T: 'a {}
+}
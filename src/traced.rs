@@ -0,0 +1,309 @@
+use core::{
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+use std::time::Instant;
+
+use crate::{
+    error::{LockError, Result},
+    locking::{AccessMode, LockApi, LockApiReadGuard, LockApiWriteGuard},
+    timed::TimedLockApi,
+    try_lock::TryLockApi,
+};
+
+fn micros(duration: Duration) -> u64 {
+    duration.as_micros().try_into().unwrap_or(u64::MAX)
+}
+
+fn attempt(name: &'static str, mode: AccessMode) -> Instant {
+    tracing::trace!(locket = name, mode = mode.as_str(), "acquiring lock");
+    crate::clock::now()
+}
+
+// Emits the outcome of an acquisition started at `start`, returning the time
+// it finished.
+fn finished(
+    name: &'static str,
+    mode: AccessMode,
+    start: Instant,
+    error: Option<&LockError>,
+) -> Instant {
+    let now = crate::clock::now();
+    let wait_us = micros(now - start);
+    match error {
+        None => tracing::trace!(
+            locket = name,
+            mode = mode.as_str(),
+            wait_us,
+            "lock acquired"
+        ),
+        Some(err) => tracing::debug!(
+            locket = name,
+            mode = mode.as_str(),
+            wait_us,
+            error = %err,
+            "lock acquisition failed"
+        ),
+    }
+    now
+}
+
+fn released(name: &'static str, mode: AccessMode, acquired: Instant) {
+    tracing::trace!(
+        locket = name,
+        mode = mode.as_str(),
+        held_us = micros(crate::clock::now().saturating_duration_since(acquired)),
+        "lock released"
+    );
+}
+
+fn acquired<G>(
+    name: &'static str,
+    mode: AccessMode,
+    start: Instant,
+    result: Result<G>,
+) -> Result<TracedGuard<G>> {
+    let now = finished(name, mode, start, result.as_ref().err());
+    result.map(|guard| TracedGuard {
+        guard,
+        name,
+        mode,
+        acquired: now,
+    })
+}
+
+/// Emits `tracing` events for acquire attempts, acquisitions and releases of the
+/// inner lock, tagged with the locket name and wait/hold times in microseconds.
+pub struct Traced<L> {
+    inner: L,
+    name: &'static str,
+}
+
+impl<L> Traced<L> {
+    pub const fn with_name(inner: L, name: &'static str) -> Traced<L> {
+        Traced { inner, name }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+}
+
+impl<L, T> LockApi<T> for Traced<L>
+where
+    L: LockApi<T>,
+{
+    type ReadGuard<'a>
+        = TracedGuard<L::ReadGuard<'a>>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = TracedGuard<L::WriteGuard<'a>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        let start = attempt(self.name, AccessMode::Read);
+        let result = self.inner.read();
+        acquired(self.name, AccessMode::Read, start, result)
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        let start = attempt(self.name, AccessMode::Write);
+        let result = self.inner.write();
+        acquired(self.name, AccessMode::Write, start, result)
+    }
+
+    fn new(inner: T) -> Self {
+        Traced::with_name(L::new(inner), core::any::type_name::<L>())
+    }
+}
+
+impl<L, T> TryLockApi<T> for Traced<L>
+where
+    L: TryLockApi<T>,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        let start = attempt(self.name, AccessMode::Read);
+        acquired(self.name, AccessMode::Read, start, self.inner.try_read())
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        let start = attempt(self.name, AccessMode::Write);
+        acquired(self.name, AccessMode::Write, start, self.inner.try_write())
+    }
+}
+
+impl<L, T> TimedLockApi<T> for Traced<L>
+where
+    L: TimedLockApi<T>,
+{
+    fn read_until(&self, deadline: Instant) -> Result<Self::ReadGuard<'_>> {
+        let start = attempt(self.name, AccessMode::Read);
+        acquired(
+            self.name,
+            AccessMode::Read,
+            start,
+            self.inner.read_until(deadline),
+        )
+    }
+
+    fn write_until(&self, deadline: Instant) -> Result<Self::WriteGuard<'_>> {
+        let start = attempt(self.name, AccessMode::Write);
+        acquired(
+            self.name,
+            AccessMode::Write,
+            start,
+            self.inner.write_until(deadline),
+        )
+    }
+}
+
+pub struct TracedGuard<G> {
+    guard: G,
+    name: &'static str,
+    mode: AccessMode,
+    acquired: Instant,
+}
+
+impl<G> Drop for TracedGuard<G> {
+    fn drop(&mut self) {
+        released(self.name, self.mode, self.acquired);
+    }
+}
+
+impl<G> Deref for TracedGuard<G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<G> DerefMut for TracedGuard<G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, T, G> LockApiReadGuard<'a, T> for TracedGuard<G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.get()
+    }
+}
+
+impl<'a, T, G> LockApiWriteGuard<'a, T> for TracedGuard<G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.get_mut()
+    }
+}
+
+#[cfg(feature = "async")]
+pub use self::async_impl::TracedFuture;
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{acquired, attempt, Traced, TracedGuard};
+    use crate::{async_locking::AsyncLockApi, error::Result, locking::AccessMode};
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{ready, Context, Poll},
+    };
+    use pin_project_lite::pin_project;
+    use std::time::Instant;
+
+    impl<L, T> AsyncLockApi<T> for Traced<L>
+    where
+        L: AsyncLockApi<T>,
+    {
+        type ReadGuard<'a>
+            = TracedGuard<L::ReadGuard<'a>>
+        where
+            Self: 'a;
+
+        type WriteGuard<'a>
+            = TracedGuard<L::WriteGuard<'a>>
+        where
+            Self: 'a;
+
+        type ReadFuture<'a>
+            = TracedFuture<L::ReadFuture<'a>>
+        where
+            Self: 'a;
+
+        type WriteFuture<'a>
+            = TracedFuture<L::WriteFuture<'a>>
+        where
+            Self: 'a;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            TracedFuture {
+                future: self.inner.read(),
+                name: self.name,
+                mode: AccessMode::Read,
+                start: None,
+            }
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            TracedFuture {
+                future: self.inner.write(),
+                name: self.name,
+                mode: AccessMode::Write,
+                start: None,
+            }
+        }
+
+        fn new(inner: T) -> Self {
+            Traced::with_name(L::new(inner), core::any::type_name::<L>())
+        }
+    }
+
+    pin_project! {
+        pub struct TracedFuture<F> {
+            #[pin]
+            future: F,
+            name: &'static str,
+            mode: AccessMode,
+            start: Option<Instant>,
+        }
+    }
+
+    impl<F, G> Future for TracedFuture<F>
+    where
+        F: Future<Output = Result<G>>,
+    {
+        type Output = Result<TracedGuard<G>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            let start = *this
+                .start
+                .get_or_insert_with(|| attempt(this.name, *this.mode));
+            let result = ready!(this.future.poll(cx));
+            Poll::Ready(acquired(this.name, *this.mode, start, result))
+        }
+    }
+}
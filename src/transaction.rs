@@ -0,0 +1,79 @@
+use core::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+};
+
+/// All-or-nothing writes. Implemented for every [`LockApi`].
+pub trait TransactionApi<T>: LockApi<T> {
+    /// Write locks and stages changes on a clone of the value. They are
+    /// applied by [`Transaction::commit`] and discarded otherwise.
+    fn write_transaction(&self) -> Result<Transaction<'_, T, Self::WriteGuard<'_>>>
+    where
+        T: Clone,
+    {
+        let guard = self.write()?;
+        Ok(Transaction {
+            staged: guard.get().clone(),
+            guard,
+            _lock: PhantomData,
+        })
+    }
+}
+
+impl<T, L> TransactionApi<T> for L where L: LockApi<T> {}
+
+/// Holds the write lock for its whole lifetime, so nothing can change the
+/// value between staging and commit.
+pub struct Transaction<'a, T, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    guard: G,
+    staged: T,
+    _lock: PhantomData<&'a ()>,
+}
+
+impl<'a, T, G> Transaction<'a, T, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    /// The value as it was when the transaction started.
+    pub fn original(&self) -> &T {
+        self.guard.get()
+    }
+
+    pub fn commit(self) {
+        let Transaction {
+            mut guard, staged, ..
+        } = self;
+        *guard.get_mut() = staged;
+    }
+
+    /// Discards the staged changes. Dropping the transaction does the same.
+    pub fn rollback(self) {}
+}
+
+impl<'a, T, G> Deref for Transaction<'a, T, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.staged
+    }
+}
+
+impl<'a, T, G> DerefMut for Transaction<'a, T, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.staged
+    }
+}
@@ -0,0 +1,284 @@
+#[cfg(feature = "alloc")]
+use alloc::{rc::Rc, sync::Arc};
+use core::cell::RefCell;
+
+use crate::{error::Result, locking::LockApi};
+
+/// Non-blocking acquisition. Fails with [`LockError::WouldBlock`] when the lock
+/// is held in a conflicting mode.
+///
+/// [`LockError::WouldBlock`]: crate::LockError::WouldBlock
+pub trait TryLockApi<T>: LockApi<T> {
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>>;
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<L, T> TryLockApi<T> for Arc<L>
+where
+    L: TryLockApi<T>,
+    for<'a> L: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        (**self).try_read()
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        (**self).try_write()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L, T> TryLockApi<T> for Rc<L>
+where
+    L: TryLockApi<T>,
+    for<'a> L: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        (**self).try_read()
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        (**self).try_write()
+    }
+}
+
+// RefCell never blocks, so its plain accessors already behave like this.
+impl<T> TryLockApi<T> for RefCell<T>
+where
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        LockApi::read(self)
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        LockApi::write(self)
+    }
+}
+
+#[cfg(feature = "atomic_refcell")]
+impl<T> TryLockApi<T> for atomic_refcell::AtomicRefCell<T>
+where
+    for<'a> T: 'a,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        LockApi::read(self)
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        LockApi::write(self)
+    }
+}
+
+#[cfg(feature = "parking_lot")]
+mod parking_lot_impl {
+    use super::TryLockApi;
+    use crate::error::{LockError, Result};
+    use parking_lot::{FairMutex, Mutex, RwLock};
+
+    impl<T> TryLockApi<T> for Mutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+            self.try_lock().ok_or(LockError::WouldBlock)
+        }
+
+        fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+            self.try_lock().ok_or(LockError::WouldBlock)
+        }
+    }
+
+    impl<T> TryLockApi<T> for FairMutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+            self.try_lock().ok_or(LockError::WouldBlock)
+        }
+
+        fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+            self.try_lock().ok_or(LockError::WouldBlock)
+        }
+    }
+
+    impl<T> TryLockApi<T> for RwLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+            RwLock::try_read(self).ok_or(LockError::WouldBlock)
+        }
+
+        fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+            RwLock::try_write(self).ok_or(LockError::WouldBlock)
+        }
+    }
+}
+
+#[cfg(feature = "spin")]
+mod spin_impl {
+    use super::TryLockApi;
+    use crate::error::{LockError, Result};
+    use spin::{mutex::Mutex, relax::RelaxStrategy, rwlock::RwLock};
+
+    impl<T, R> TryLockApi<T> for Mutex<T, R>
+    where
+        R: RelaxStrategy,
+        for<'a> T: 'a,
+        for<'a> R: 'a,
+    {
+        fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+            self.try_lock().ok_or(LockError::WouldBlock)
+        }
+
+        fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+            self.try_lock().ok_or(LockError::WouldBlock)
+        }
+    }
+
+    impl<T, R> TryLockApi<T> for RwLock<T, R>
+    where
+        R: RelaxStrategy,
+        for<'a> T: 'a,
+        for<'a> R: 'a,
+    {
+        fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+            RwLock::try_read(self).ok_or(LockError::WouldBlock)
+        }
+
+        fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+            RwLock::try_write(self).ok_or(LockError::WouldBlock)
+        }
+    }
+}
+
+#[cfg(feature = "std-lock")]
+fn try_lock_error<G>(err: std::sync::TryLockError<G>) -> crate::error::LockError {
+    match err {
+        std::sync::TryLockError::Poisoned(_) => crate::error::LockError::Poisoned,
+        std::sync::TryLockError::WouldBlock => crate::error::LockError::WouldBlock,
+    }
+}
+
+#[cfg(feature = "std-lock")]
+mod std_impl {
+    use super::{try_lock_error, TryLockApi};
+    use crate::error::Result;
+    use std::sync::{Mutex, RwLock};
+
+    impl<T> TryLockApi<T> for Mutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+            self.try_lock().map_err(try_lock_error)
+        }
+
+        fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+            self.try_lock().map_err(try_lock_error)
+        }
+    }
+
+    impl<T> TryLockApi<T> for RwLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+            RwLock::try_read(self).map_err(try_lock_error)
+        }
+
+        fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+            RwLock::try_write(self).map_err(try_lock_error)
+        }
+    }
+}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32", target_feature = "atomics"))]
+mod wasm_impl {
+    use super::{try_lock_error, TryLockApi};
+    use crate::error::Result;
+    use wasm_sync::{Mutex, RwLock};
+
+    impl<T> TryLockApi<T> for Mutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+            self.try_lock().map_err(try_lock_error)
+        }
+
+        fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+            self.try_lock().map_err(try_lock_error)
+        }
+    }
+
+    impl<T> TryLockApi<T> for RwLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+            RwLock::try_read(self).map_err(try_lock_error)
+        }
+
+        fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+            RwLock::try_write(self).map_err(try_lock_error)
+        }
+    }
+}
+
+#[cfg(all(loom, feature = "std-lock"))]
+mod loom_impl {
+    use super::{try_lock_error, TryLockApi};
+    use crate::error::Result;
+    use loom::sync::{Mutex, RwLock};
+
+    impl<T> TryLockApi<T> for Mutex<T>
+    where
+        for<'a> T: 'a,
+    {
+        fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+            self.try_lock().map_err(try_lock_error)
+        }
+
+        fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+            self.try_lock().map_err(try_lock_error)
+        }
+    }
+
+    impl<T> TryLockApi<T> for RwLock<T>
+    where
+        for<'a> T: 'a,
+    {
+        fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+            RwLock::try_read(self).map_err(try_lock_error)
+        }
+
+        fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+            RwLock::try_write(self).map_err(try_lock_error)
+        }
+    }
+}
+
+#[cfg(feature = "portable-atomic")]
+mod portable_atomic_impl {
+    use super::TryLockApi;
+    use crate::error::Result;
+    use portable_atomic_util::Arc;
+
+    impl<L, T> TryLockApi<T> for Arc<L>
+    where
+        L: TryLockApi<T>,
+        for<'a> L: 'a,
+    {
+        fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+            (**self).try_read()
+        }
+
+        fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+            (**self).try_write()
+        }
+    }
+}
@@ -1,32 +1,59 @@
+#[cfg(feature = "alloc")]
 use alloc::{
     rc::{Rc, Weak as RcWeak},
     sync::{Arc, Weak as ArcWeak},
 };
 
+use crate::error::LockError;
+
+/// A shared handle which can be downgraded to a weak one. The reference
+/// counts are here too, for generic code such as cache eviction which should
+/// not name the pointer type.
 pub trait Downgrade {
     type Output: Upgrade<Output = Self>;
     fn downgrade(&self) -> Self::Output;
+    fn strong_count(&self) -> usize;
+    fn weak_count(&self) -> usize;
 }
 
+#[cfg(feature = "alloc")]
 impl<T> Downgrade for Arc<T> {
     type Output = ArcWeak<T>;
     fn downgrade(&self) -> Self::Output {
         Arc::downgrade(self)
     }
+    fn strong_count(&self) -> usize {
+        Arc::strong_count(self)
+    }
+    fn weak_count(&self) -> usize {
+        Arc::weak_count(self)
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<T> Downgrade for Rc<T> {
     type Output = RcWeak<T>;
     fn downgrade(&self) -> Self::Output {
         Rc::downgrade(self)
     }
+    fn strong_count(&self) -> usize {
+        Rc::strong_count(self)
+    }
+    fn weak_count(&self) -> usize {
+        Rc::weak_count(self)
+    }
 }
 
 pub trait Upgrade {
     type Output;
     fn upgrade(&self) -> Option<Self::Output>;
+    /// Like [`upgrade`](Upgrade::upgrade), failing with [`LockError::Dropped`].
+    fn try_upgrade(&self) -> Result<Self::Output, LockError> {
+        self.upgrade().ok_or(LockError::Dropped)
+    }
 }
 
+#[cfg(feature = "alloc")]
 impl<T> Upgrade for ArcWeak<T> {
     type Output = Arc<T>;
     fn upgrade(&self) -> Option<Self::Output> {
@@ -34,6 +61,7 @@ impl<T> Upgrade for ArcWeak<T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<T> Upgrade for RcWeak<T> {
     type Output = Rc<T>;
     fn upgrade(&self) -> Option<Self::Output> {
@@ -41,6 +69,30 @@ impl<T> Upgrade for RcWeak<T> {
     }
 }
 
+/// Recovers the value behind a shared pointer when it is the only strong handle.
+/// An associated function, like `Arc::try_unwrap`, so it does not shadow methods
+/// of the pointee.
+pub trait TryUnwrap: Sized {
+    type Inner;
+    fn try_unwrap(this: Self) -> Result<Self::Inner, Self>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TryUnwrap for Arc<T> {
+    type Inner = T;
+    fn try_unwrap(this: Self) -> Result<Self::Inner, Self> {
+        Arc::try_unwrap(this)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> TryUnwrap for Rc<T> {
+    type Inner = T;
+    fn try_unwrap(this: Self) -> Result<Self::Inner, Self> {
+        Rc::try_unwrap(this)
+    }
+}
+
 pub trait Lockable {
     type Guard<'a>
     where
@@ -48,6 +100,59 @@ pub trait Lockable {
     fn lock(&self) -> Self::Guard<'_>;
 }
 
+/// A [`Lockable`] which can also be locked without blocking.
+pub trait TryLockable: Lockable {
+    fn try_lock(&self) -> Option<Self::Guard<'_>>;
+}
+
+#[cfg(feature = "alloc")]
+impl<L> Lockable for Arc<L>
+where
+    L: Lockable,
+{
+    type Guard<'a>
+        = L::Guard<'a>
+    where
+        Self: 'a;
+    fn lock(&self) -> Self::Guard<'_> {
+        (**self).lock()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L> Lockable for Rc<L>
+where
+    L: Lockable,
+{
+    type Guard<'a>
+        = L::Guard<'a>
+    where
+        Self: 'a;
+    fn lock(&self) -> Self::Guard<'_> {
+        (**self).lock()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L> TryLockable for Arc<L>
+where
+    L: TryLockable,
+{
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        (**self).try_lock()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<L> TryLockable for Rc<L>
+where
+    L: TryLockable,
+{
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        (**self).try_lock()
+    }
+}
+
 #[cfg(feature = "std")]
 impl Lockable for std::io::Stdout {
     type Guard<'a> = std::io::StdoutLock<'a>;
@@ -55,3 +160,74 @@ impl Lockable for std::io::Stdout {
         self.lock()
     }
 }
+
+#[cfg(feature = "std")]
+impl Lockable for std::io::Stderr {
+    type Guard<'a> = std::io::StderrLock<'a>;
+    fn lock(&self) -> Self::Guard<'_> {
+        self.lock()
+    }
+}
+
+#[cfg(feature = "std")]
+impl Lockable for std::io::Stdin {
+    type Guard<'a> = std::io::StdinLock<'a>;
+    fn lock(&self) -> Self::Guard<'_> {
+        self.lock()
+    }
+}
+
+#[cfg(feature = "portable-atomic")]
+mod portable_atomic_impl {
+    use super::{Downgrade, Lockable, TryLockable, TryUnwrap, Upgrade};
+    use portable_atomic_util::{Arc, Weak};
+
+    impl<T> Downgrade for Arc<T> {
+        type Output = Weak<T>;
+        fn downgrade(&self) -> Self::Output {
+            Arc::downgrade(self)
+        }
+        fn strong_count(&self) -> usize {
+            Arc::strong_count(self)
+        }
+        fn weak_count(&self) -> usize {
+            Arc::weak_count(self)
+        }
+    }
+
+    impl<T> Upgrade for Weak<T> {
+        type Output = Arc<T>;
+        fn upgrade(&self) -> Option<Self::Output> {
+            Weak::upgrade(self)
+        }
+    }
+
+    impl<T> TryUnwrap for Arc<T> {
+        type Inner = T;
+        fn try_unwrap(this: Self) -> Result<Self::Inner, Self> {
+            Arc::try_unwrap(this)
+        }
+    }
+
+    impl<L> Lockable for Arc<L>
+    where
+        L: Lockable,
+    {
+        type Guard<'a>
+            = L::Guard<'a>
+        where
+            Self: 'a;
+        fn lock(&self) -> Self::Guard<'_> {
+            (**self).lock()
+        }
+    }
+
+    impl<L> TryLockable for Arc<L>
+    where
+        L: TryLockable,
+    {
+        fn try_lock(&self) -> Option<Self::Guard<'_>> {
+            (**self).try_lock()
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use core::{
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+};
+
+/// Counts writes to the inner lock. The version is bumped after a write guard
+/// has released the inner lock, and so published its write, while
+/// [`read_versioned`](Versioned::read_versioned) loads it before taking the
+/// read guard. The value read is therefore never older than the version it
+/// comes with, even for backends whose readers do not wait for writers; at
+/// worst a reader sees a new value with an old version and refreshes once
+/// more than needed.
+pub struct Versioned<L> {
+    inner: L,
+    version: AtomicU64,
+}
+
+impl<L> Versioned<L> {
+    pub fn wrap(inner: L) -> Versioned<L> {
+        Versioned {
+            inner,
+            version: AtomicU64::new(0),
+        }
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version.load(Ordering::Acquire)
+    }
+
+    pub fn has_changed_since(&self, version: u64) -> bool {
+        self.version() != version
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    /// Reads the value together with the version it corresponds to.
+    pub fn read_versioned<T>(&self) -> Result<(L::ReadGuard<'_>, u64)>
+    where
+        L: LockApi<T>,
+    {
+        let version = self.version();
+        let guard = self.inner.read()?;
+        Ok((guard, version))
+    }
+
+    fn guard<G>(&self, guard: G) -> VersionedGuard<'_, G> {
+        VersionedGuard {
+            guard: Some(guard),
+            version: &self.version,
+        }
+    }
+}
+
+impl<L, T> LockApi<T> for Versioned<L>
+where
+    L: LockApi<T>,
+{
+    type ReadGuard<'a>
+        = L::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = VersionedGuard<'a, L::WriteGuard<'a>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.inner.read()
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        Ok(self.guard(self.inner.write()?))
+    }
+
+    fn new(inner: T) -> Self {
+        Versioned::wrap(L::new(inner))
+    }
+}
+
+pub struct VersionedGuard<'a, G> {
+    guard: Option<G>,
+    version: &'a AtomicU64,
+}
+
+impl<G> Drop for VersionedGuard<'_, G> {
+    fn drop(&mut self) {
+        drop(self.guard.take());
+        self.version.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+impl<G> Deref for VersionedGuard<'_, G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard.as_ref().unwrap()
+    }
+}
+
+impl<G> DerefMut for VersionedGuard<'_, G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard.as_mut().unwrap()
+    }
+}
+
+impl<'a, T, G> LockApiReadGuard<'a, T> for VersionedGuard<'a, G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.as_ref().unwrap().get()
+    }
+}
+
+impl<'a, T, G> LockApiWriteGuard<'a, T> for VersionedGuard<'a, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.as_mut().unwrap().get_mut()
+    }
+}
+
+#[cfg(feature = "async")]
+pub use self::async_impl::VersionedFuture;
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{Versioned, VersionedGuard};
+    use crate::{async_locking::AsyncLockApi, error::Result};
+    use core::{
+        future::Future,
+        pin::Pin,
+        sync::atomic::AtomicU64,
+        task::{ready, Context, Poll},
+    };
+    use pin_project_lite::pin_project;
+
+    impl<L> Versioned<L> {
+        pub async fn read_versioned_async<T>(&self) -> Result<(L::ReadGuard<'_>, u64)>
+        where
+            L: AsyncLockApi<T>,
+        {
+            let version = self.version();
+            let guard = self.inner.read().await?;
+            Ok((guard, version))
+        }
+    }
+
+    impl<L, T> AsyncLockApi<T> for Versioned<L>
+    where
+        L: AsyncLockApi<T>,
+    {
+        type ReadGuard<'a>
+            = L::ReadGuard<'a>
+        where
+            Self: 'a;
+
+        type WriteGuard<'a>
+            = VersionedGuard<'a, L::WriteGuard<'a>>
+        where
+            Self: 'a;
+
+        type ReadFuture<'a>
+            = L::ReadFuture<'a>
+        where
+            Self: 'a;
+
+        type WriteFuture<'a>
+            = VersionedFuture<'a, L::WriteFuture<'a>>
+        where
+            Self: 'a;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            self.inner.read()
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            VersionedFuture {
+                future: self.inner.write(),
+                version: &self.version,
+            }
+        }
+
+        fn new(inner: T) -> Self {
+            Versioned::wrap(L::new(inner))
+        }
+    }
+
+    pin_project! {
+        pub struct VersionedFuture<'a, F> {
+            #[pin]
+            future: F,
+            version: &'a AtomicU64,
+        }
+    }
+
+    impl<'a, F, G> Future for VersionedFuture<'a, F>
+    where
+        F: Future<Output = Result<G>>,
+    {
+        type Output = Result<VersionedGuard<'a, G>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            let guard = ready!(this.future.poll(cx))?;
+            Poll::Ready(Ok(VersionedGuard {
+                guard: Some(guard),
+                version: this.version,
+            }))
+        }
+    }
+}
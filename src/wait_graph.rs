@@ -0,0 +1,552 @@
+//! A deadlock detector for lockets of any backend, sync or async. Every
+//! [`DeadlockChecked`] locket records who holds it and who waits for it in a
+//! global wait-for graph, and a cycle is reported as soon as a wait closes it.
+//!
+//! Async tasks are told apart when their future runs inside [`scope`]. Other
+//! futures share the thread polling them with unrelated tasks, so their waits
+//! are not recorded, and their holds are attributed to that thread. Waits for
+//! a read lock are assumed not to be held back by other readers.
+
+use alloc::{boxed::Box, sync::Arc, vec::Vec};
+use core::{
+    cell::Cell,
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicU64, Ordering},
+};
+use std::{
+    backtrace::{Backtrace, BacktraceStatus},
+    sync::{Mutex, MutexGuard, PoisonError, RwLock},
+    thread::{self, ThreadId},
+};
+
+use crate::{
+    error::Result,
+    locking::{AccessMode, LockApi, LockApiReadGuard, LockApiWriteGuard},
+    try_lock::TryLockApi,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Waiter {
+    Thread(ThreadId),
+    /// A future running inside [`scope`].
+    Task(u64),
+}
+
+std::thread_local! {
+    static TASK: Cell<Option<u64>> = const { Cell::new(None) };
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+impl Waiter {
+    pub fn current() -> Waiter {
+        match TASK.with(Cell::get) {
+            Some(task) => Waiter::Task(task),
+            None => Waiter::Thread(thread::current().id()),
+        }
+    }
+}
+
+/// One edge of a cycle: `waiter` waits for `lock`, which `holder` holds.
+#[derive(Debug, Clone)]
+pub struct CycleEntry {
+    pub waiter: Waiter,
+    pub lock: &'static str,
+    pub mode: AccessMode,
+    pub waiting_at: Arc<Backtrace>,
+    pub holder: Waiter,
+    pub acquired_at: Arc<Backtrace>,
+}
+
+/// Threads and tasks waiting on each other in a ring. Backtraces are captured
+/// as configured by `RUST_BACKTRACE` and `RUST_LIB_BACKTRACE`.
+#[derive(Debug, Clone)]
+pub struct WaitCycle {
+    pub entries: Vec<CycleEntry>,
+}
+
+impl fmt::Display for WaitCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "deadlock between {} waiters:", self.entries.len())?;
+        for entry in &self.entries {
+            writeln!(
+                f,
+                "{:?} waits to {} {}, held by {:?}",
+                entry.waiter,
+                entry.mode.as_str(),
+                entry.lock,
+                entry.holder
+            )?;
+            if entry.waiting_at.status() == BacktraceStatus::Captured {
+                writeln!(f, "waiting at:\n{}", entry.waiting_at)?;
+            }
+            if entry.acquired_at.status() == BacktraceStatus::Captured {
+                writeln!(f, "acquired at:\n{}", entry.acquired_at)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Hold {
+    lock: usize,
+    waiter: Waiter,
+    mode: AccessMode,
+    backtrace: Arc<Backtrace>,
+}
+
+struct Wait {
+    id: u64,
+    lock: usize,
+    name: &'static str,
+    waiter: Waiter,
+    mode: AccessMode,
+    backtrace: Arc<Backtrace>,
+}
+
+struct Graph {
+    holds: Vec<Hold>,
+    waits: Vec<Wait>,
+}
+
+static GRAPH: Mutex<Graph> = Mutex::new(Graph {
+    holds: Vec::new(),
+    waits: Vec::new(),
+});
+
+type CycleHandler = Box<dyn Fn(&WaitCycle) + Send + Sync>;
+
+static HANDLER: RwLock<Option<CycleHandler>> = RwLock::new(None);
+
+fn graph() -> MutexGuard<'static, Graph> {
+    GRAPH.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Graph {
+    fn find_cycle(&self, start: Waiter) -> Option<WaitCycle> {
+        let mut entries = Vec::new();
+        let mut visited = Vec::new();
+        self.search(start, start, &mut entries, &mut visited)
+            .then_some(WaitCycle { entries })
+    }
+
+    fn search(
+        &self,
+        start: Waiter,
+        current: Waiter,
+        entries: &mut Vec<CycleEntry>,
+        visited: &mut Vec<Waiter>,
+    ) -> bool {
+        for wait in self.waits.iter().filter(|wait| wait.waiter == current) {
+            let holders = self.holds.iter().filter(|hold| {
+                hold.lock == wait.lock
+                    && (wait.mode == AccessMode::Write || hold.mode == AccessMode::Write)
+            });
+            for hold in holders {
+                entries.push(CycleEntry {
+                    waiter: current,
+                    lock: wait.name,
+                    mode: wait.mode,
+                    waiting_at: wait.backtrace.clone(),
+                    holder: hold.waiter,
+                    acquired_at: hold.backtrace.clone(),
+                });
+                if hold.waiter == start {
+                    return true;
+                }
+                if !visited.contains(&hold.waiter) {
+                    visited.push(hold.waiter);
+                    if self.search(start, hold.waiter, entries, visited) {
+                        return true;
+                    }
+                }
+                entries.pop();
+            }
+        }
+        false
+    }
+}
+
+/// Lists the cycles in the wait-for graph right now.
+pub fn cycles() -> Vec<WaitCycle> {
+    let graph = graph();
+    let mut cycles: Vec<WaitCycle> = Vec::new();
+    for wait in &graph.waits {
+        let seen = cycles
+            .iter()
+            .flat_map(|cycle| &cycle.entries)
+            .any(|entry| entry.waiter == wait.waiter);
+        if seen {
+            continue;
+        }
+        if let Some(cycle) = graph.find_cycle(wait.waiter) {
+            cycles.push(cycle);
+        }
+    }
+    cycles
+}
+
+/// Replaces the default handler, which prints cycles to stderr. It runs on
+/// the thread whose wait closed the cycle, before that thread blocks.
+pub fn on_cycle<F>(handler: F)
+where
+    F: Fn(&WaitCycle) + Send + Sync + 'static,
+{
+    *HANDLER.write().unwrap_or_else(PoisonError::into_inner) = Some(Box::new(handler));
+}
+
+fn report(cycle: &WaitCycle) {
+    match &*HANDLER.read().unwrap_or_else(PoisonError::into_inner) {
+        Some(handler) => handler(cycle),
+        None => std::eprintln!("{cycle}"),
+    }
+}
+
+fn wait(lock: usize, name: &'static str, mode: AccessMode) -> u64 {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let waiter = Waiter::current();
+    let cycle = {
+        let mut graph = graph();
+        graph.waits.push(Wait {
+            id,
+            lock,
+            name,
+            waiter,
+            mode,
+            backtrace: Arc::new(Backtrace::capture()),
+        });
+        graph.find_cycle(waiter)
+    };
+    if let Some(cycle) = cycle {
+        report(&cycle);
+    }
+    id
+}
+
+fn cancel(wait: u64) {
+    graph().waits.retain(|entry| entry.id != wait);
+}
+
+// Moves a finished wait over to the holds, or records a hold taken without
+// waiting.
+fn acquired(lock: usize, mode: AccessMode, wait: Option<u64>) -> Waiter {
+    let mut graph = graph();
+    let (waiter, backtrace) =
+        match wait.and_then(|wait| graph.waits.iter().position(|entry| entry.id == wait)) {
+            Some(idx) => {
+                let wait = graph.waits.swap_remove(idx);
+                (wait.waiter, wait.backtrace)
+            }
+            None => (Waiter::current(), Arc::new(Backtrace::capture())),
+        };
+    graph.holds.push(Hold {
+        lock,
+        waiter,
+        mode,
+        backtrace,
+    });
+    waiter
+}
+
+fn released(lock: usize, waiter: Waiter) {
+    let mut graph = graph();
+    if let Some(idx) = graph
+        .holds
+        .iter()
+        .position(|hold| hold.lock == lock && hold.waiter == waiter)
+    {
+        graph.holds.swap_remove(idx);
+    }
+}
+
+/// Takes part in the global wait-for graph, see the [module docs](self).
+/// Every acquisition goes through a global mutex, so this is meant for debug
+/// builds and tests.
+pub struct DeadlockChecked<L> {
+    inner: L,
+    name: &'static str,
+}
+
+impl<L> DeadlockChecked<L> {
+    pub const fn with_name(inner: L, name: &'static str) -> DeadlockChecked<L> {
+        DeadlockChecked { inner, name }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    fn id(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    fn acquire<'a, G>(
+        &'a self,
+        mode: AccessMode,
+        blocking: bool,
+        lock: impl FnOnce(&'a L) -> Result<G>,
+    ) -> Result<DeadlockGuard<G>> {
+        let wait = blocking.then(|| wait(self.id(), self.name, mode));
+        let guard = match lock(&self.inner) {
+            Ok(guard) => guard,
+            Err(err) => {
+                if let Some(wait) = wait {
+                    cancel(wait);
+                }
+                return Err(err);
+            }
+        };
+        Ok(DeadlockGuard {
+            guard,
+            lock: self.id(),
+            waiter: acquired(self.id(), mode, wait),
+        })
+    }
+}
+
+impl<L, T> LockApi<T> for DeadlockChecked<L>
+where
+    L: LockApi<T>,
+{
+    type ReadGuard<'a>
+        = DeadlockGuard<L::ReadGuard<'a>>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = DeadlockGuard<L::WriteGuard<'a>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.acquire(AccessMode::Read, true, |lock| lock.read())
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.acquire(AccessMode::Write, true, |lock| lock.write())
+    }
+
+    fn new(inner: T) -> Self {
+        DeadlockChecked::with_name(L::new(inner), core::any::type_name::<L>())
+    }
+}
+
+impl<L, T> TryLockApi<T> for DeadlockChecked<L>
+where
+    L: TryLockApi<T>,
+{
+    fn try_read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.acquire(AccessMode::Read, false, |lock| lock.try_read())
+    }
+
+    fn try_write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.acquire(AccessMode::Write, false, |lock| lock.try_write())
+    }
+}
+
+pub struct DeadlockGuard<G> {
+    guard: G,
+    lock: usize,
+    waiter: Waiter,
+}
+
+// The hold is forgotten before the inner guard unlocks, so the graph never
+// shows a hold which is already gone.
+impl<G> Drop for DeadlockGuard<G> {
+    fn drop(&mut self) {
+        released(self.lock, self.waiter);
+    }
+}
+
+impl<G> Deref for DeadlockGuard<G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.guard
+    }
+}
+
+impl<G> DerefMut for DeadlockGuard<G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.guard
+    }
+}
+
+impl<'a, T, G> LockApiReadGuard<'a, T> for DeadlockGuard<G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.get()
+    }
+}
+
+impl<'a, T, G> LockApiWriteGuard<'a, T> for DeadlockGuard<G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.get_mut()
+    }
+}
+
+#[cfg(feature = "async")]
+pub use self::async_impl::{scope, DeadlockFuture, Scoped};
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{acquired, cancel, wait, DeadlockChecked, DeadlockGuard, NEXT_ID, TASK};
+    use crate::{async_locking::AsyncLockApi, error::Result, locking::AccessMode};
+    use core::{
+        cell::Cell,
+        future::Future,
+        pin::Pin,
+        sync::atomic::Ordering,
+        task::{ready, Context, Poll},
+    };
+    use pin_project_lite::pin_project;
+
+    /// Runs `future` as its own waiter in the wait-for graph, rather than as
+    /// whichever thread polls it.
+    pub fn scope<F: Future>(future: F) -> Scoped<F> {
+        Scoped {
+            future,
+            task: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    pin_project! {
+        pub struct Scoped<F> {
+            #[pin]
+            future: F,
+            task: u64,
+        }
+    }
+
+    impl<F: Future> Future for Scoped<F> {
+        type Output = F::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            let outer = TASK.with(|task| task.replace(Some(*this.task)));
+            let result = this.future.poll(cx);
+            TASK.with(|task| task.set(outer));
+            result
+        }
+    }
+
+    // Drops the wait of a future cancelled while pending.
+    struct Waiting(u64);
+
+    impl Drop for Waiting {
+        fn drop(&mut self) {
+            cancel(self.0);
+        }
+    }
+
+    impl<L, T> AsyncLockApi<T> for DeadlockChecked<L>
+    where
+        L: AsyncLockApi<T>,
+    {
+        type ReadGuard<'a>
+            = DeadlockGuard<L::ReadGuard<'a>>
+        where
+            Self: 'a;
+
+        type WriteGuard<'a>
+            = DeadlockGuard<L::WriteGuard<'a>>
+        where
+            Self: 'a;
+
+        type ReadFuture<'a>
+            = DeadlockFuture<L::ReadFuture<'a>>
+        where
+            Self: 'a;
+
+        type WriteFuture<'a>
+            = DeadlockFuture<L::WriteFuture<'a>>
+        where
+            Self: 'a;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            DeadlockFuture {
+                future: self.inner.read(),
+                lock: self.id(),
+                name: self.name,
+                mode: AccessMode::Read,
+                waiting: None,
+            }
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            DeadlockFuture {
+                future: self.inner.write(),
+                lock: self.id(),
+                name: self.name,
+                mode: AccessMode::Write,
+                waiting: None,
+            }
+        }
+
+        fn new(inner: T) -> Self {
+            DeadlockChecked::with_name(L::new(inner), core::any::type_name::<L>())
+        }
+    }
+
+    pin_project! {
+        pub struct DeadlockFuture<F> {
+            #[pin]
+            future: F,
+            lock: usize,
+            name: &'static str,
+            mode: AccessMode,
+            waiting: Option<Waiting>,
+        }
+    }
+
+    impl<F, G> Future for DeadlockFuture<F>
+    where
+        F: Future<Output = Result<G>>,
+    {
+        type Output = Result<DeadlockGuard<G>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            // Outside a scope the waiter would be the polling thread, which
+            // other tasks on it may hold the lock as.
+            let id = match TASK.with(Cell::get) {
+                Some(_) => Some(
+                    this.waiting
+                        .get_or_insert_with(|| Waiting(wait(*this.lock, this.name, *this.mode)))
+                        .0,
+                ),
+                None => None,
+            };
+            let result = ready!(this.future.poll(cx));
+            let guard = result.map(|guard| DeadlockGuard {
+                guard,
+                lock: *this.lock,
+                waiter: acquired(*this.lock, *this.mode, id),
+            });
+            // Cancels the wait if the acquisition failed, and does nothing
+            // otherwise.
+            this.waiting.take();
+            Poll::Ready(guard)
+        }
+    }
+}
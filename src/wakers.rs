@@ -0,0 +1,73 @@
+use alloc::vec::Vec;
+use core::{
+    cell::UnsafeCell,
+    hint,
+    sync::atomic::{fence, AtomicBool, AtomicUsize, Ordering},
+    task::Waker,
+};
+
+/// Wakers of futures waiting for a lock which has no wait queue of its own.
+/// A future registers before trying the lock once more, and a guard wakes
+/// every registered future after releasing it.
+pub(crate) struct WakerList {
+    locked: AtomicBool,
+    // Registered wakers, so releasing a lock nobody waits for stays cheap.
+    len: AtomicUsize,
+    wakers: UnsafeCell<Vec<Waker>>,
+}
+
+// SAFETY: the wakers are only touched with `locked` set.
+unsafe impl Send for WakerList {}
+unsafe impl Sync for WakerList {}
+
+impl WakerList {
+    pub(crate) const fn new() -> WakerList {
+        WakerList {
+            locked: AtomicBool::new(false),
+            len: AtomicUsize::new(0),
+            wakers: UnsafeCell::new(Vec::new()),
+        }
+    }
+
+    // The list is held only to push or take wakers, never across a wake.
+    fn with<R>(&self, f: impl FnOnce(&mut Vec<Waker>) -> R) -> R {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        // SAFETY: `locked` is set.
+        let result = f(unsafe { &mut *self.wakers.get() });
+        self.locked.store(false, Ordering::Release);
+        result
+    }
+
+    /// Registers `waker`. The caller has to try the lock again afterwards, so
+    /// a release in between is not missed.
+    pub(crate) fn register(&self, waker: &Waker) {
+        self.with(|wakers| {
+            if !wakers.iter().any(|known| known.will_wake(waker)) {
+                wakers.push(waker.clone());
+            }
+            self.len.store(wakers.len(), Ordering::Relaxed);
+        });
+        // Pairs with the fence in `wake_all`: either the retry sees the
+        // release, or the releaser sees this waker.
+        fence(Ordering::SeqCst);
+    }
+
+    /// Wakes every registered future. Call after releasing the lock.
+    pub(crate) fn wake_all(&self) {
+        fence(Ordering::SeqCst);
+        if self.len.load(Ordering::Relaxed) == 0 {
+            return;
+        }
+        let wakers = self.with(|wakers| {
+            self.len.store(0, Ordering::Relaxed);
+            core::mem::take(wakers)
+        });
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
@@ -0,0 +1,24 @@
+//! Backends that are safe to use on `wasm32-unknown-unknown`.
+//!
+//! Without the `atomics` target feature there is only one thread, so waiting
+//! on a held lock can never succeed; [`BorrowLock`](crate::BorrowLock) reports
+//! the conflict instead. With `atomics` enabled, `wasm_sync` spins on the
+//! browser's main thread, where blocking waits are not allowed, and blocks
+//! normally in workers. On other targets the aliases resolve to std's locks.
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+pub type Mutex<T> = crate::BorrowLock<T>;
+
+#[cfg(all(target_arch = "wasm32", not(target_feature = "atomics")))]
+pub type RwLock<T> = crate::BorrowLock<T>;
+
+#[cfg(not(all(target_arch = "wasm32", not(target_feature = "atomics"))))]
+pub type Mutex<T> = wasm_sync::Mutex<T>;
+
+#[cfg(not(all(target_arch = "wasm32", not(target_feature = "atomics"))))]
+pub type RwLock<T> = wasm_sync::RwLock<T>;
+
+/// Never blocks the thread, so it can be driven by
+/// `wasm_bindgen_futures::spawn_local`.
+#[cfg(feature = "async")]
+pub type AsyncRwLock<T> = crate::BorrowLock<T>;
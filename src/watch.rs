@@ -0,0 +1,351 @@
+use core::{
+    future::Future,
+    ops::{Deref, DerefMut},
+};
+
+use event_listener::{Event, EventListener};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+    versioned::{Versioned, VersionedGuard},
+};
+
+/// A locket whose writes wake subscribers. Each write bumps a version (see
+/// [`Versioned`]); subscribers wait for the version to move past the one they
+/// last saw and then read the value themselves.
+pub struct WatchLocket<L> {
+    inner: Versioned<L>,
+    event: Event,
+}
+
+impl<L> WatchLocket<L> {
+    pub fn wrap(inner: L) -> WatchLocket<L> {
+        WatchLocket {
+            inner: Versioned::wrap(inner),
+            event: Event::new(),
+        }
+    }
+
+    pub fn version(&self) -> u64 {
+        self.inner.version()
+    }
+
+    pub fn get_ref(&self) -> &L {
+        self.inner.get_ref()
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner.into_inner()
+    }
+
+    /// A subscriber borrowing this locket. Wrap the locket in an `Arc` and use
+    /// [`Subscriber::new`] for one which can move into a task.
+    pub fn subscribe(&self) -> Subscriber<&WatchLocket<L>> {
+        Subscriber::new(self)
+    }
+
+    fn guard<G>(&self, guard: G) -> WatchGuard<'_, G> {
+        WatchGuard {
+            guard: Some(guard),
+            event: &self.event,
+        }
+    }
+}
+
+impl<L, T> LockApi<T> for WatchLocket<L>
+where
+    L: LockApi<T>,
+{
+    type ReadGuard<'a>
+        = L::ReadGuard<'a>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = WatchGuard<'a, VersionedGuard<'a, L::WriteGuard<'a>>>
+    where
+        Self: 'a;
+
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.inner.read()
+    }
+
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        Ok(self.guard(self.inner.write()?))
+    }
+
+    fn new(inner: T) -> Self {
+        WatchLocket::wrap(L::new(inner))
+    }
+}
+
+/// Notifies subscribers after the inner guard has been released.
+pub struct WatchGuard<'a, G> {
+    guard: Option<G>,
+    event: &'a Event,
+}
+
+impl<G> Drop for WatchGuard<'_, G> {
+    fn drop(&mut self) {
+        drop(self.guard.take());
+        self.event.notify(usize::MAX);
+    }
+}
+
+impl<G> Deref for WatchGuard<'_, G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard.as_ref().unwrap()
+    }
+}
+
+impl<G> DerefMut for WatchGuard<'_, G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard.as_mut().unwrap()
+    }
+}
+
+impl<'a, T, G> LockApiReadGuard<'a, T> for WatchGuard<'a, G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.as_ref().unwrap().get()
+    }
+}
+
+impl<'a, T, G> LockApiWriteGuard<'a, T> for WatchGuard<'a, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.as_mut().unwrap().get_mut()
+    }
+}
+
+/// Waits for writes to a [`WatchLocket`]. `W` is any handle to the locket, such
+/// as a reference or an `Arc`.
+pub struct Subscriber<W> {
+    watch: W,
+    seen: u64,
+    listener: Option<EventListener>,
+}
+
+impl<W, L> Subscriber<W>
+where
+    W: Deref<Target = WatchLocket<L>>,
+{
+    /// Subscribes to writes made after this call.
+    pub fn new(watch: W) -> Subscriber<W> {
+        let seen = watch.version();
+        Subscriber {
+            watch,
+            seen,
+            listener: None,
+        }
+    }
+
+    /// The version this subscriber has seen.
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    pub fn has_changed(&self) -> bool {
+        self.watch.inner.has_changed_since(self.seen)
+    }
+
+    /// Marks the current version as seen and returns a copy of the value.
+    pub fn snapshot<T>(&mut self) -> Result<T>
+    where
+        L: LockApi<T>,
+        T: Clone,
+    {
+        let (guard, version) = self.watch.inner.read_versioned()?;
+        self.seen = version;
+        Ok(guard.get().clone())
+    }
+
+    /// Resolves with the new version once a write happened after the last one
+    /// seen. Writes made in between are coalesced.
+    pub fn changed(&mut self) -> impl Future<Output = u64> + '_ {
+        core::future::poll_fn(|cx| self.poll_changed(cx))
+    }
+
+    fn poll_changed(&mut self, cx: &mut core::task::Context<'_>) -> core::task::Poll<u64> {
+        use core::{pin::Pin, task::Poll};
+
+        loop {
+            // Register before checking so a write in between is not missed.
+            let listener = self
+                .listener
+                .get_or_insert_with(|| self.watch.event.listen());
+            let version = self.watch.version();
+            if version != self.seen {
+                self.seen = version;
+                self.listener = None;
+                return Poll::Ready(version);
+            }
+            if Pin::new(listener).poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            self.listener = None;
+        }
+    }
+}
+
+impl<W> Clone for Subscriber<W>
+where
+    W: Clone,
+{
+    fn clone(&self) -> Self {
+        Subscriber {
+            watch: self.watch.clone(),
+            seen: self.seen,
+            listener: None,
+        }
+    }
+}
+
+#[cfg(feature = "stream")]
+impl<W, L> futures_core::Stream for Subscriber<W>
+where
+    W: Deref<Target = WatchLocket<L>> + Unpin,
+{
+    type Item = u64;
+
+    fn poll_next(
+        self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Option<u64>> {
+        self.get_mut().poll_changed(cx).map(Some)
+    }
+}
+
+pub use self::async_impl::WatchFuture;
+
+mod async_impl {
+    use super::{WatchGuard, WatchLocket};
+    use crate::{
+        async_locking::AsyncLockApi,
+        error::Result,
+        locking::LockApiReadGuard,
+        versioned::{VersionedFuture, VersionedGuard},
+    };
+    use core::{
+        future::Future,
+        ops::Deref,
+        pin::Pin,
+        task::{ready, Context, Poll},
+    };
+    use event_listener::Event;
+    use pin_project_lite::pin_project;
+
+    impl<W, L> super::Subscriber<W>
+    where
+        W: Deref<Target = WatchLocket<L>>,
+    {
+        pub async fn snapshot_async<T>(&mut self) -> Result<T>
+        where
+            L: AsyncLockApi<T>,
+            T: Clone,
+        {
+            let (guard, version) = self.watch.inner.read_versioned_async().await?;
+            self.seen = version;
+            Ok(guard.get().clone())
+        }
+    }
+
+    impl<L> WatchLocket<L> {
+        /// Resolves with a read guard once `condition` holds for the value.
+        /// The condition is checked under the lock, first right away and then
+        /// after every write, until it holds.
+        pub async fn wait_for<T, F>(&self, mut condition: F) -> Result<L::ReadGuard<'_>>
+        where
+            L: AsyncLockApi<T>,
+            F: FnMut(&T) -> bool,
+        {
+            loop {
+                // Register before checking so a write in between is not missed.
+                let listener = self.event.listen();
+                let guard = AsyncLockApi::read(&self.inner).await?;
+                if condition(guard.get()) {
+                    return Ok(guard);
+                }
+                drop(guard);
+                listener.await;
+            }
+        }
+    }
+
+    impl<L, T> AsyncLockApi<T> for WatchLocket<L>
+    where
+        L: AsyncLockApi<T>,
+    {
+        type ReadGuard<'a>
+            = L::ReadGuard<'a>
+        where
+            Self: 'a;
+
+        type WriteGuard<'a>
+            = WatchGuard<'a, VersionedGuard<'a, L::WriteGuard<'a>>>
+        where
+            Self: 'a;
+
+        type ReadFuture<'a>
+            = L::ReadFuture<'a>
+        where
+            Self: 'a;
+
+        type WriteFuture<'a>
+            = WatchFuture<'a, VersionedFuture<'a, L::WriteFuture<'a>>>
+        where
+            Self: 'a;
+
+        fn read(&self) -> Self::ReadFuture<'_> {
+            AsyncLockApi::read(&self.inner)
+        }
+
+        fn write(&self) -> Self::WriteFuture<'_> {
+            WatchFuture {
+                future: AsyncLockApi::write(&self.inner),
+                event: &self.event,
+            }
+        }
+
+        fn new(inner: T) -> Self {
+            WatchLocket::wrap(L::new(inner))
+        }
+    }
+
+    pin_project! {
+        pub struct WatchFuture<'a, F> {
+            #[pin]
+            future: F,
+            event: &'a Event,
+        }
+    }
+
+    impl<'a, F, G> Future for WatchFuture<'a, F>
+    where
+        F: Future<Output = Result<G>>,
+    {
+        type Output = Result<WatchGuard<'a, G>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            let guard = ready!(this.future.poll(cx))?;
+            Poll::Ready(Ok(WatchGuard {
+                guard: Some(guard),
+                event: this.event,
+            }))
+        }
+    }
+}
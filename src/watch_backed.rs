@@ -0,0 +1,215 @@
+use alloc::boxed::Box;
+use core::{
+    future::Future,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+};
+
+use tokio::sync::{watch, Mutex, MutexGuard};
+
+use crate::{
+    async_locking::AsyncLockApi,
+    error::Result,
+    inner::IntoInner,
+    locking::{LockApiReadGuard, LockApiWriteGuard},
+    poison::PoisonApi,
+};
+
+type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a + Send>>;
+
+/// A read-mostly locket on top of a `tokio::sync::watch` channel. Reads clone
+/// the current value and never wait for writers. Writers take turns, edit a
+/// copy and publish it when the guard is dropped, which also wakes every
+/// receiver from [`WatchBacked::subscribe`]; a guard which was never written
+/// through wakes nobody.
+///
+/// Guards copy because a `watch::Ref` holds the channel's synchronous lock:
+/// it is `!Send` and blocks the sender, so it cannot live across `.await`
+/// like the guards of [`AsyncLockApi`]. [`modify`](WatchBacked::modify) and
+/// [`modify_if`](WatchBacked::modify_if) edit the value in place instead.
+pub struct WatchBacked<T> {
+    sender: watch::Sender<T>,
+    writer: Mutex<()>,
+}
+
+impl<T> WatchBacked<T> {
+    pub fn new(inner: T) -> WatchBacked<T> {
+        WatchBacked {
+            sender: watch::Sender::new(inner),
+            writer: Mutex::new(()),
+        }
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<T> {
+        self.sender.subscribe()
+    }
+
+    pub fn sender(&self) -> &watch::Sender<T> {
+        &self.sender
+    }
+
+    /// Edits the value in place and wakes every receiver, after waiting for
+    /// other writers.
+    pub async fn modify<F>(&self, modify: F)
+    where
+        F: FnOnce(&mut T),
+    {
+        let _writer = self.writer.lock().await;
+        self.sender.send_modify(modify);
+    }
+
+    /// Like [`modify`](WatchBacked::modify), but only wakes receivers if
+    /// `modify` returns `true`, which is returned.
+    pub async fn modify_if<F>(&self, modify: F) -> bool
+    where
+        F: FnOnce(&mut T) -> bool,
+    {
+        let _writer = self.writer.lock().await;
+        self.sender.send_if_modified(modify)
+    }
+
+    /// Borrows the current value. The borrow holds a synchronous lock which
+    /// blocks writers from publishing, so do not keep it across an `.await`.
+    pub fn borrow(&self) -> watch::Ref<'_, T> {
+        self.sender.borrow()
+    }
+}
+
+impl<T: Default> Default for WatchBacked<T> {
+    fn default() -> Self {
+        WatchBacked::new(T::default())
+    }
+}
+
+impl<T> AsyncLockApi<T> for WatchBacked<T>
+where
+    T: Clone + Send + Sync,
+    for<'a> T: 'a,
+{
+    type ReadGuard<'a> = WatchBackedReadGuard<'a, T>;
+
+    type WriteGuard<'a> = WatchBackedWriteGuard<'a, T>;
+
+    type ReadFuture<'a> = core::future::Ready<Result<Self::ReadGuard<'a>>>;
+
+    type WriteFuture<'a> = BoxFuture<'a, Result<Self::WriteGuard<'a>>>;
+
+    fn read(&self) -> Self::ReadFuture<'_> {
+        core::future::ready(Ok(WatchBackedReadGuard {
+            value: self.sender.borrow().clone(),
+            _lock: PhantomData,
+        }))
+    }
+
+    fn write(&self) -> Self::WriteFuture<'_> {
+        Box::pin(async move {
+            let writer = self.writer.lock().await;
+            Ok(WatchBackedWriteGuard {
+                value: Some(self.sender.borrow().clone()),
+                modified: false,
+                sender: &self.sender,
+                _writer: writer,
+            })
+        })
+    }
+
+    fn new(inner: T) -> Self {
+        WatchBacked::new(inner)
+    }
+}
+
+impl<T> IntoInner<T> for WatchBacked<T>
+where
+    T: Clone,
+{
+    fn into_inner(self) -> Result<T> {
+        Ok(self.sender.borrow().clone())
+    }
+}
+
+impl<T> PoisonApi for WatchBacked<T> {}
+
+impl<T> core::fmt::Debug for WatchBacked<T>
+where
+    T: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("WatchBacked")
+            .field("data", &*self.sender.borrow())
+            .finish()
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for watch::Ref<'a, T> {
+    fn get(&self) -> &T {
+        self
+    }
+}
+
+/// Holds a copy of the value taken when the guard was created, so it can be
+/// kept across `.await` points.
+pub struct WatchBackedReadGuard<'a, T> {
+    value: T,
+    _lock: PhantomData<&'a WatchBacked<T>>,
+}
+
+impl<T> Deref for WatchBackedReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for WatchBackedReadGuard<'a, T> {
+    fn get(&self) -> &T {
+        &self.value
+    }
+}
+
+pub struct WatchBackedWriteGuard<'a, T> {
+    value: Option<T>,
+    modified: bool,
+    sender: &'a watch::Sender<T>,
+    _writer: MutexGuard<'a, ()>,
+}
+
+impl<T> Drop for WatchBackedWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        // Do not publish a value a panicking writer may have left half done.
+        if std::thread::panicking() {
+            return;
+        }
+        if let Some(value) = self.value.take().filter(|_| self.modified) {
+            self.sender.send_modify(|current| *current = value);
+        }
+    }
+}
+
+impl<T> Deref for WatchBackedWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.value.as_ref().unwrap()
+    }
+}
+
+impl<T> DerefMut for WatchBackedWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        self.modified = true;
+        self.value.as_mut().unwrap()
+    }
+}
+
+impl<'a, T> LockApiReadGuard<'a, T> for WatchBackedWriteGuard<'a, T> {
+    fn get(&self) -> &T {
+        self.deref()
+    }
+}
+
+impl<'a, T> LockApiWriteGuard<'a, T> for WatchBackedWriteGuard<'a, T> {
+    fn get_mut(&mut self) -> &mut T {
+        self.deref_mut()
+    }
+}
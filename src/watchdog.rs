@@ -0,0 +1,341 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+    panic::Location,
+    time::Duration,
+};
+use std::{
+    sync::{Condvar, Mutex, Once, PoisonError},
+    time::Instant,
+};
+
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard, LockApiWriteGuard},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StallKind {
+    /// The acquisition had to wait longer than the threshold.
+    Wait,
+    /// The guard was held longer than the threshold.
+    Hold,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Stall {
+    pub kind: StallKind,
+    pub duration: Duration,
+    pub location: &'static Location<'static>,
+}
+
+impl fmt::Display for Stall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.kind {
+            StallKind::Wait => "waited",
+            StallKind::Hold => "held",
+        };
+        write!(
+            f,
+            "lock {what} for {:?} (acquired at {})",
+            self.duration, self.location
+        )
+    }
+}
+
+type StallHandler = Arc<dyn Fn(&Stall) + Send + Sync>;
+
+fn emit(handler: Option<&StallHandler>, stall: &Stall) {
+    match handler {
+        Some(handler) => handler(stall),
+        None => std::eprintln!("{stall}"),
+    }
+}
+
+/// An acquisition which has not finished yet.
+struct Pending {
+    id: u64,
+    start: Instant,
+    deadline: Instant,
+    location: &'static Location<'static>,
+    handler: Option<StallHandler>,
+}
+
+struct Waits {
+    next_id: u64,
+    pending: Vec<Pending>,
+}
+
+/// Reports acquisitions which are still blocked past their threshold, from a
+/// thread shared by every watchdog and started with the first acquisition.
+/// It works in real time: a thread's mock clock could not wake it, so waits
+/// are timed with `Instant` from start to report.
+struct Monitor {
+    waits: Mutex<Waits>,
+    changed: Condvar,
+}
+
+static MONITOR: Monitor = Monitor {
+    waits: Mutex::new(Waits {
+        next_id: 0,
+        pending: Vec::new(),
+    }),
+    changed: Condvar::new(),
+};
+
+impl Monitor {
+    fn waits(&self) -> std::sync::MutexGuard<'_, Waits> {
+        self.waits.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Starts watching an acquisition, unless its deadline is out of reach.
+    fn watch(
+        &'static self,
+        threshold: Duration,
+        location: &'static Location<'static>,
+        handler: Option<StallHandler>,
+    ) -> Option<u64> {
+        static STARTED: Once = Once::new();
+        let start = Instant::now();
+        let deadline = start.checked_add(threshold)?;
+        STARTED.call_once(|| {
+            // Without the thread, waits are still reported once acquired.
+            let _ = std::thread::Builder::new()
+                .name("locket-watchdog".into())
+                .spawn(|| self.run());
+        });
+        let mut waits = self.waits();
+        let id = waits.next_id;
+        waits.next_id += 1;
+        waits.pending.push(Pending {
+            id,
+            start,
+            deadline,
+            location,
+            handler,
+        });
+        drop(waits);
+        self.changed.notify_one();
+        Some(id)
+    }
+
+    /// Stops watching an acquisition. Returns false if the monitor already
+    /// reported it.
+    fn finish(&self, id: u64) -> bool {
+        let mut waits = self.waits();
+        match waits.pending.iter().position(|pending| pending.id == id) {
+            Some(idx) => {
+                waits.pending.swap_remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn run(&self) {
+        let mut waits = self.waits();
+        loop {
+            let now = Instant::now();
+            let (due, pending) = core::mem::take(&mut waits.pending)
+                .into_iter()
+                .partition::<Vec<_>, _>(|pending| pending.deadline <= now);
+            waits.pending = pending;
+            if !due.is_empty() {
+                // Handlers run without the list, so they may take locks.
+                drop(waits);
+                for pending in due {
+                    let stall = Stall {
+                        kind: StallKind::Wait,
+                        duration: now.saturating_duration_since(pending.start),
+                        location: pending.location,
+                    };
+                    emit(pending.handler.as_ref(), &stall);
+                }
+                waits = self.waits();
+                continue;
+            }
+            let next = waits.pending.iter().map(|pending| pending.deadline).min();
+            waits = match next {
+                Some(deadline) => {
+                    let timeout = deadline.saturating_duration_since(now);
+                    self.changed
+                        .wait_timeout(waits, timeout)
+                        .unwrap_or_else(PoisonError::into_inner)
+                        .0
+                }
+                None => self
+                    .changed
+                    .wait(waits)
+                    .unwrap_or_else(PoisonError::into_inner),
+            };
+        }
+    }
+}
+
+/// Reports acquisitions which waited, or guards which were held, for longer than
+/// a threshold. A wait is reported as soon as it passes the threshold, while
+/// the acquisition is still blocked, so deadlocks show up too; a hold once the
+/// guard is dropped.
+///
+/// Waits still blocked are timed in real time. Waits reported once acquired,
+/// and holds, are measured with the thread's clock, which tests can replace
+/// with [`set_thread_clock`](crate::testing::set_thread_clock).
+pub struct Watchdog<L> {
+    inner: L,
+    threshold: Duration,
+    on_stall: Option<StallHandler>,
+}
+
+impl<L> Watchdog<L> {
+    pub const DEFAULT_THRESHOLD: Duration = Duration::from_secs(1);
+
+    pub fn with_threshold(inner: L, threshold: Duration) -> Watchdog<L> {
+        Watchdog {
+            inner,
+            threshold,
+            on_stall: None,
+        }
+    }
+
+    /// Replaces the default handler, which prints stalls to stderr.
+    pub fn on_stall<F>(mut self, handler: F) -> Watchdog<L>
+    where
+        F: Fn(&Stall) + Send + Sync + 'static,
+    {
+        self.on_stall = Some(Arc::new(handler));
+        self
+    }
+
+    pub fn threshold(&self) -> Duration {
+        self.threshold
+    }
+
+    pub fn get_ref(&self) -> &L {
+        &self.inner
+    }
+
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+
+    fn report(&self, kind: StallKind, duration: Duration, location: &'static Location<'static>) {
+        if duration <= self.threshold {
+            return;
+        }
+        let stall = Stall {
+            kind,
+            duration,
+            location,
+        };
+        emit(self.on_stall.as_ref(), &stall);
+    }
+
+    fn acquire<'a, G>(
+        &'a self,
+        location: &'static Location<'static>,
+        lock: impl FnOnce(&'a L) -> Result<G>,
+    ) -> Result<WatchdogGuard<'a, L, G>> {
+        let watched = MONITOR.watch(self.threshold, location, self.on_stall.clone());
+        let start = crate::clock::now();
+        let guard = lock(&self.inner);
+        let acquired = crate::clock::now();
+        // Report waits which passed the threshold before the monitor got to them.
+        if watched.is_none_or(|id| MONITOR.finish(id)) {
+            self.report(StallKind::Wait, acquired - start, location);
+        }
+        let guard = guard?;
+        Ok(WatchdogGuard {
+            guard: Some(guard),
+            watchdog: self,
+            acquired,
+            location,
+        })
+    }
+}
+
+impl<L, T> LockApi<T> for Watchdog<L>
+where
+    L: LockApi<T>,
+{
+    type ReadGuard<'a>
+        = WatchdogGuard<'a, L, L::ReadGuard<'a>>
+    where
+        Self: 'a;
+
+    type WriteGuard<'a>
+        = WatchdogGuard<'a, L, L::WriteGuard<'a>>
+    where
+        Self: 'a;
+
+    #[track_caller]
+    fn read(&self) -> Result<Self::ReadGuard<'_>> {
+        self.acquire(Location::caller(), |lock| lock.read())
+    }
+
+    #[track_caller]
+    fn write(&self) -> Result<Self::WriteGuard<'_>> {
+        self.acquire(Location::caller(), |lock| lock.write())
+    }
+
+    fn new(inner: T) -> Self {
+        Watchdog::with_threshold(L::new(inner), Self::DEFAULT_THRESHOLD)
+    }
+}
+
+pub struct WatchdogGuard<'a, L, G> {
+    guard: Option<G>,
+    watchdog: &'a Watchdog<L>,
+    acquired: Instant,
+    location: &'static Location<'static>,
+}
+
+impl<L, G> Drop for WatchdogGuard<'_, L, G> {
+    fn drop(&mut self) {
+        // Release the inner lock first, so a slow handler doesn't extend the hold.
+        drop(self.guard.take());
+        self.watchdog.report(
+            StallKind::Hold,
+            crate::clock::now().saturating_duration_since(self.acquired),
+            self.location,
+        );
+    }
+}
+
+impl<L, G> Deref for WatchdogGuard<'_, L, G>
+where
+    G: Deref,
+{
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        self.guard.as_ref().unwrap()
+    }
+}
+
+impl<L, G> DerefMut for WatchdogGuard<'_, L, G>
+where
+    G: DerefMut,
+{
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.guard.as_mut().unwrap()
+    }
+}
+
+impl<'a, T, L, G> LockApiReadGuard<'a, T> for WatchdogGuard<'a, L, G>
+where
+    G: LockApiReadGuard<'a, T>,
+{
+    fn get(&self) -> &T {
+        self.guard.as_ref().unwrap().get()
+    }
+}
+
+impl<'a, T, L, G> LockApiWriteGuard<'a, T> for WatchdogGuard<'a, L, G>
+where
+    G: LockApiWriteGuard<'a, T>,
+{
+    fn get_mut(&mut self) -> &mut T {
+        self.guard.as_mut().unwrap().get_mut()
+    }
+}
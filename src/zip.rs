@@ -0,0 +1,76 @@
+use crate::{
+    error::Result,
+    locking::{LockApi, LockApiReadGuard},
+    multi::acquire2,
+};
+
+/// Reads two lockets together, acquiring them in the same order as
+/// [`lock2`](crate::lock2) so the combined view is consistent.
+pub fn zip<'a, A, B>(a: &'a A, b: &'a B) -> Zip<'a, A, B> {
+    Zip { a, b }
+}
+
+pub struct Zip<'a, A, B> {
+    a: &'a A,
+    b: &'a B,
+}
+
+impl<'a, A, B> Zip<'a, A, B> {
+    pub fn read<TA, TB>(&self) -> Result<ZipGuard<A::ReadGuard<'a>, B::ReadGuard<'a>>>
+    where
+        A: LockApi<TA>,
+        B: LockApi<TB>,
+    {
+        let (a, b) = acquire2(
+            (self.a, self.a.lock_id()),
+            (self.b, self.b.lock_id()),
+            |a| a.read(),
+            |b| b.read(),
+        )?;
+        Ok(ZipGuard { a, b })
+    }
+}
+
+pub struct ZipGuard<GA, GB> {
+    a: GA,
+    b: GB,
+}
+
+impl<GA, GB> ZipGuard<GA, GB> {
+    pub fn get<'a, TA, TB>(&self) -> (&TA, &TB)
+    where
+        GA: LockApiReadGuard<'a, TA>,
+        GB: LockApiReadGuard<'a, TB>,
+    {
+        (self.a.get(), self.b.get())
+    }
+
+    pub fn into_inner(self) -> (GA, GB) {
+        (self.a, self.b)
+    }
+}
+
+#[cfg(feature = "async")]
+mod async_impl {
+    use super::{Zip, ZipGuard};
+    use crate::{async_locking::AsyncLockApi, error::Result, multi::acquire2_async};
+
+    impl<'a, A, B> Zip<'a, A, B> {
+        pub async fn read_async<TA, TB>(
+            &self,
+        ) -> Result<ZipGuard<A::ReadGuard<'a>, B::ReadGuard<'a>>>
+        where
+            A: AsyncLockApi<TA>,
+            B: AsyncLockApi<TB>,
+        {
+            let (a, b) = acquire2_async(
+                (self.a, self.a.lock_id()),
+                (self.b, self.b.lock_id()),
+                |a| a.read(),
+                |b| b.read(),
+            )
+            .await?;
+            Ok(ZipGuard { a, b })
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::sync::Arc;
+
+use locket::{testing::LockCheck, AsyncLockApi, AsyncMonitor};
+use tokio::sync::{Notify, RwLock};
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn lock_check() {
+    LockCheck::new()
+        .shared_reads(true)
+        .run_async::<AsyncMonitor<_, RwLock<_>, Notify>>();
+}
+
+#[test]
+fn wait_until_rechecks_after_each_notification() {
+    let queue = Arc::new(AsyncMonitor::<Vec<u32>, RwLock<_>, Notify>::new(Vec::new()));
+    let runtime = runtime();
+    let consumers: Vec<_> = (0..4)
+        .map(|_| {
+            let queue = queue.clone();
+            runtime.spawn(async move {
+                let mut sum = 0;
+                for _ in 0..25 {
+                    let mut jobs = queue.wait_until(|jobs| !jobs.is_empty()).await.unwrap();
+                    sum += jobs.pop().unwrap();
+                }
+                sum
+            })
+        })
+        .collect();
+
+    let consumed: u32 = runtime.block_on(async {
+        for job in 1..=100 {
+            queue.write_and_notify(|jobs| jobs.push(job)).await.unwrap();
+            tokio::task::yield_now().await;
+        }
+        let mut consumed = 0;
+        for consumer in consumers {
+            consumed += consumer.await.unwrap();
+        }
+        consumed
+    });
+    assert_eq!(consumed, 5050);
+    runtime.block_on(async {
+        assert!(AsyncLockApi::read(&*queue).await.unwrap().is_empty());
+    });
+}
@@ -0,0 +1,29 @@
+use std::time::Duration;
+
+use locket::{AsyncTimedLockApi, LockError, PolicyRwLock, TimedLockApi};
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+}
+
+// `PolicyRwLock` is both a timed and an async lock, so both traits in scope
+// must not make the calls ambiguous.
+#[test]
+fn both_timed_traits_in_scope() {
+    let lock = PolicyRwLock::new(0);
+    let short = Duration::from_millis(10);
+
+    *lock.write_timeout(short).unwrap() += 1;
+    runtime().block_on(async {
+        *lock.write_timeout_async(short).await.unwrap() += 1;
+        let held = lock.read_timeout(short).unwrap();
+        assert_eq!(*held, 2);
+        assert!(matches!(
+            lock.write_timeout_async(short).await,
+            Err(LockError::Timeout)
+        ));
+        assert_eq!(*lock.read_timeout_async(short).await.unwrap(), 2);
+    });
+}
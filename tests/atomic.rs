@@ -0,0 +1,57 @@
+use std::{
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    thread,
+};
+
+use locket::{AtomicLock, AtomicValue, LockApi, LockError, Pod, TryLockApi};
+
+// `Probe` does not fit in an atomic, so `LockCheck` cannot run on this lock.
+#[test]
+fn writers_are_exclusive() {
+    let lock = AtomicLock::new(0u64);
+    let writing = AtomicBool::new(false);
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..1000 {
+                    let mut guard = LockApi::write(&lock).unwrap();
+                    assert!(!writing.swap(true, Ordering::SeqCst), "two writers");
+                    *guard += 1;
+                    thread::yield_now();
+                    writing.store(false, Ordering::SeqCst);
+                }
+            });
+        }
+    });
+    assert_eq!(lock.into_inner(), 4000);
+}
+
+#[test]
+fn writes_publish_on_drop() {
+    let lock = AtomicLock::new(1u32);
+    let mut guard = LockApi::write(&lock).unwrap();
+    *guard = 2;
+    assert_eq!(lock.load(), 1);
+    assert!(matches!(lock.try_write(), Err(LockError::WouldBlock)));
+    drop(guard);
+    assert_eq!(*LockApi::read(&lock).unwrap(), 2);
+}
+
+#[test]
+fn pod_values_round_trip() {
+    let lock = AtomicLock::new(Pod([1u8, 2, 3]));
+    LockApi::write(&lock).unwrap().0[1] = 7;
+    assert_eq!(lock.load(), Pod([1, 7, 3]));
+}
+
+// `load` is safe and takes any atomic, so every bit pattern has to be a valid
+// value; `Pod<bool>` is rejected at compile time, see the `Pod` docs.
+#[test]
+fn pod_load_accepts_any_bits() {
+    let atomic = AtomicU64::new(u64::MAX);
+    assert_eq!(<Pod<u16> as AtomicValue>::load(&atomic), Pod(u16::MAX));
+    assert_eq!(
+        <Pod<[i8; 3]> as AtomicValue>::load(&atomic),
+        Pod([-1, -1, -1])
+    );
+}
@@ -0,0 +1,55 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::RwLock,
+    thread,
+};
+
+use locket::{testing::LockCheck, Batched, LockApi};
+
+#[test]
+fn lock_check() {
+    LockCheck::new()
+        .shared_reads(true)
+        .run::<Batched<RwLock<_>, _>>();
+}
+
+#[test]
+fn apply_returns_each_result() {
+    let lock = Batched::<RwLock<u64>, _>::new(0);
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let mut last = 0;
+                for _ in 0..500 {
+                    let seen = lock
+                        .apply(|value| {
+                            *value += 1;
+                            *value
+                        })
+                        .unwrap();
+                    assert!(seen > last, "{seen} after {last}");
+                    last = seen;
+                }
+            });
+        }
+        scope.spawn(|| {
+            for _ in 0..100 {
+                *LockApi::write(&lock).unwrap() += 1;
+            }
+        });
+    });
+    assert_eq!(*LockApi::read(&lock).unwrap(), 2100);
+}
+
+#[test]
+fn panics_reach_the_caller() {
+    let lock = Batched::<RwLock<u64>, _>::new(1);
+    let panicked = catch_unwind(AssertUnwindSafe(|| {
+        lock.apply(|_| -> () { panic!("bad mutation") })
+    }));
+    assert_eq!(
+        panicked.unwrap_err().downcast_ref::<&str>(),
+        Some(&"bad mutation")
+    );
+    assert_eq!(lock.apply(|value| *value).unwrap(), 1);
+}
@@ -0,0 +1,35 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use locket::{config, testing::MockClock, Bounded, IntentLocket, LockApi, LockError, Metrics};
+
+// The only test in this binary touching the crate-wide maximum wait.
+#[test]
+fn bounded_honors_the_max_wait() {
+    let clock = MockClock::new().with_step(Duration::from_millis(10));
+    let _installed = clock.install();
+    let lock = Metrics::wrap(Bounded::wrap(Mutex::new(0)));
+    let held = LockApi::write(lock.get_ref().get_ref()).unwrap();
+
+    config::set_max_wait(Duration::from_secs(30));
+    let started = Instant::now();
+    let result = LockApi::write(&lock);
+    config::clear_max_wait();
+
+    assert!(matches!(result, Err(LockError::Timeout)));
+    assert!(clock.elapsed() >= Duration::from_secs(30));
+    assert!(started.elapsed() < Duration::from_secs(30));
+    drop(held);
+    assert!(LockApi::write(&lock).is_ok());
+}
+
+// Wrappers only need `LockApi`, whether or not `max-wait` is enabled.
+#[test]
+fn wrappers_take_untimed_locks() {
+    let lock = Metrics::wrap(IntentLocket::new(1));
+    *LockApi::write(&lock).unwrap() += 1;
+    assert_eq!(*LockApi::read(&lock).unwrap(), 2);
+    assert_eq!(lock.snapshot().acquisitions(), 2);
+}
@@ -0,0 +1,84 @@
+use std::{rc::Rc, sync::Arc, thread, time::Duration};
+
+use locket::{
+    BuildError, BuildOption, Hooked, LockApi, Locket, Metrics, Named, PolicyRwLock, RwPolicy,
+    TryLockApi, Watchdog,
+};
+
+#[test]
+fn options_reach_the_backend() {
+    let lock: Arc<PolicyRwLock<u32>> = Locket::builder()
+        .fair(true)
+        .max_readers(3)
+        .build(0)
+        .unwrap();
+    assert_eq!(lock.policy(), RwPolicy::PhaseFair);
+    assert_eq!(lock.max_readers(), Some(3));
+
+    let lock: Rc<PolicyRwLock<u32>> = Locket::builder().build(0).unwrap();
+    assert_eq!(lock.policy(), RwPolicy::WritePreferring);
+    assert_eq!(lock.max_readers(), None);
+}
+
+#[test]
+fn max_readers_is_enforced() {
+    let lock: Arc<PolicyRwLock<u32>> = Locket::builder().max_readers(2).build(0).unwrap();
+    let first = LockApi::read(&lock).unwrap();
+    let second = lock.try_read().unwrap();
+    assert!(lock.try_read().is_err());
+
+    let waiting = {
+        let lock = lock.clone();
+        thread::spawn(move || *LockApi::read(&lock).unwrap())
+    };
+    thread::sleep(Duration::from_millis(20));
+    assert!(!waiting.is_finished());
+    drop(first);
+    assert_eq!(waiting.join().unwrap(), 0);
+    drop(second);
+}
+
+#[test]
+fn built_lock_is_usable() {
+    let lock: Arc<Named<PolicyRwLock<Vec<u32>>>> =
+        Locket::builder().name("numbers").build(vec![]).unwrap();
+    assert_eq!(lock.name(), "numbers");
+    LockApi::write(&lock).unwrap().push(1);
+    assert_eq!(*LockApi::read(&lock).unwrap(), [1]);
+}
+
+#[test]
+fn options_without_a_layer_are_rejected() {
+    let built: Result<Arc<PolicyRwLock<u32>>, _> = Locket::builder().name("numbers").build(0);
+    assert_eq!(
+        built.err(),
+        Some(BuildError {
+            option: BuildOption::Name
+        })
+    );
+
+    let built: Result<Arc<Named<PolicyRwLock<u32>>>, _> = Locket::builder().traced(true).build(0);
+    assert_eq!(built.err().map(|err| err.option), Some(BuildOption::Traced));
+
+    let built: Result<Arc<Named<std::sync::RwLock<u32>>>, _> =
+        Locket::builder().fair(false).build(0);
+    assert_eq!(built.err().map(|err| err.option), Some(BuildOption::Policy));
+
+    let built: Result<Arc<Named<std::sync::RwLock<u32>>>, _> =
+        Locket::builder().traced(false).build(0);
+    assert!(built.is_ok());
+}
+
+#[test]
+fn instrumentation_layers_pass_options_through() {
+    let lock: Arc<Metrics<Watchdog<Hooked<PolicyRwLock<u32>>>>> = Locket::builder()
+        .name("counter")
+        .max_readers(1)
+        .build(0)
+        .unwrap();
+    let hooked = lock.get_ref().get_ref();
+    assert_eq!(hooked.name(), "counter");
+    assert_eq!(hooked.get_ref().max_readers(), Some(1));
+    *LockApi::write(&lock).unwrap() += 1;
+    assert_eq!(lock.snapshot().acquisitions(), 1);
+}
@@ -0,0 +1,86 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use locket::{
+    testing::MockClock, HookContext, Hooked, LeasedLocket, LockApi, LockError, Metrics, Timed,
+};
+
+#[test]
+fn timed_gives_up_at_the_mock_deadline() {
+    let clock = MockClock::new().with_step(Duration::from_millis(10));
+    let _installed = clock.install();
+    let lock = Timed::with_timeout(Mutex::new(0), Duration::from_secs(60));
+
+    let _held = LockApi::write(lock.get_ref()).unwrap();
+    let started = Instant::now();
+    assert!(matches!(LockApi::write(&lock), Err(LockError::Timeout)));
+    assert!(clock.elapsed() >= Duration::from_secs(60));
+    assert!(started.elapsed() < Duration::from_secs(60));
+}
+
+#[test]
+fn timed_acquires_a_free_lock() {
+    let _installed = MockClock::new().install();
+    let lock: Timed<Mutex<u32>> = LockApi::new(1);
+    *LockApi::write(&lock).unwrap() += 1;
+    assert_eq!(*LockApi::read(&lock).unwrap(), 2);
+}
+
+#[test]
+fn lease_runs_out_on_the_mock_clock() {
+    let clock = MockClock::new();
+    let _installed = clock.install();
+    let lock = LeasedLocket::new(1, Duration::from_secs(10));
+
+    let first = lock.write().unwrap();
+    first.with(|value| *value += 1).unwrap();
+    assert!(matches!(lock.try_write(), Err(LockError::WouldBlock)));
+
+    clock.advance(Duration::from_secs(5));
+    first.renew().unwrap();
+    clock.advance(Duration::from_secs(9));
+    assert!(!first.is_revoked());
+
+    clock.advance(Duration::from_secs(2));
+    let second = lock.try_write().unwrap();
+    assert!(first.is_revoked());
+    assert!(matches!(first.with(|_| ()), Err(LockError::Expired)));
+    assert_eq!(second.with(|value| *value).unwrap(), 2);
+    assert_eq!(lock.revocations(), 1);
+}
+
+#[test]
+fn metrics_measure_the_mock_clock() {
+    let clock = MockClock::new();
+    let _installed = clock.install();
+    let lock = Metrics::wrap(Mutex::new(0));
+
+    let guard = LockApi::write(&lock).unwrap();
+    clock.advance(Duration::from_millis(3));
+    drop(guard);
+
+    let snapshot = lock.snapshot();
+    assert_eq!(snapshot.write_acquisitions, 1);
+    assert_eq!(snapshot.wait.max(), Duration::ZERO);
+    assert_eq!(snapshot.hold.max(), Duration::from_millis(3));
+}
+
+#[test]
+fn hooks_see_the_mock_clock() {
+    let clock = MockClock::new().with_step(Duration::from_millis(1));
+    let _installed = clock.install();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let lock = {
+        let seen = seen.clone();
+        Hooked::with_name(Mutex::new(0), "hooked")
+            .on_release(move |context: &HookContext| seen.lock().unwrap().push(*context))
+    };
+
+    drop(LockApi::read(&lock).unwrap());
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].wait, Duration::from_millis(1));
+    assert_eq!(seen[0].held, Some(Duration::from_millis(1)));
+}
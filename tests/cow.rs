@@ -0,0 +1,43 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::Arc,
+};
+
+use locket::{testing::LockCheck, CowLocket, LockApi};
+
+#[test]
+fn lock_check() {
+    LockCheck::new()
+        .shared_reads(true)
+        .snapshot_reads(true)
+        .run::<CowLocket<_>>();
+}
+
+#[test]
+fn clones_on_first_mutation_only() {
+    let lock = CowLocket::from(vec![1]);
+    let before = lock.load().unwrap();
+    drop(LockApi::write(&lock).unwrap());
+    assert!(Arc::ptr_eq(&lock.load().unwrap(), &before));
+
+    let mut write = LockApi::write(&lock).unwrap();
+    write.push(2);
+    assert_eq!(*LockApi::read(&lock).unwrap(), [1]);
+    drop(write);
+    assert_eq!(*before, [1]);
+    assert_eq!(*lock.load().unwrap(), [1, 2]);
+}
+
+#[test]
+fn panicking_writes_are_discarded() {
+    let lock = CowLocket::from(1);
+    let panicked = catch_unwind(AssertUnwindSafe(|| {
+        let mut write = LockApi::write(&lock).unwrap();
+        *write = 2;
+        panic!("interrupted write");
+    }));
+    assert!(panicked.is_err());
+    assert_eq!(*lock.load().unwrap(), 1);
+    *LockApi::write(&lock).unwrap() += 2;
+    assert_eq!(*lock.load().unwrap(), 3);
+}
@@ -0,0 +1,66 @@
+use std::{
+    sync::{Arc, Barrier},
+    thread,
+    time::{Duration, Instant},
+};
+
+use locket::{
+    deadlock::{check_deadlocks, WaitedLock},
+    LockApi, Named,
+};
+use parking_lot::Mutex;
+
+#[test]
+fn reports_name_the_awaited_locks() {
+    let named = Arc::new(Named::with_name(Mutex::new(0), "named"));
+    let plain = Arc::new(Mutex::new(0));
+    let ids = (named.get_ref().lock_id(), plain.lock_id());
+
+    // The two threads deadlock for good; they are left behind when the test
+    // process exits.
+    let barrier = Arc::new(Barrier::new(2));
+    {
+        let (named, plain, barrier) = (named.clone(), plain.clone(), barrier.clone());
+        thread::spawn(move || {
+            let _held = LockApi::write(&*named).unwrap();
+            barrier.wait();
+            drop(LockApi::write(&*plain));
+        });
+    }
+    thread::spawn(move || {
+        let _held = LockApi::write(&*plain).unwrap();
+        barrier.wait();
+        drop(LockApi::write(&*named));
+    });
+
+    let started = Instant::now();
+    let report = loop {
+        if let Some(report) = check_deadlocks().pop() {
+            break report;
+        }
+        assert!(
+            started.elapsed() < Duration::from_secs(10),
+            "no deadlock found"
+        );
+        thread::sleep(Duration::from_millis(10));
+    };
+    let mut waited: Vec<_> = report
+        .threads
+        .iter()
+        .map(|thread| thread.waiting_for)
+        .collect();
+    waited.sort_by_key(|lock| lock.map(|lock| lock.id == ids.1));
+    assert_eq!(
+        waited,
+        [
+            Some(WaitedLock {
+                id: ids.0,
+                name: Some("named"),
+            }),
+            Some(WaitedLock {
+                id: ids.1,
+                name: None,
+            }),
+        ]
+    );
+}
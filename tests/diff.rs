@@ -0,0 +1,135 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, RwLock, RwLockReadGuard, RwLockWriteGuard,
+};
+
+use locket::{
+    testing::{LockCheck, Probe},
+    Change, Diffed, DiffedGuard, LockApi, MappedLocket, MappedReadGuard, MappedWriteGuard, Tracked,
+    TrackedMap, TrackedVec,
+};
+
+// A probe recording each write as a change, so `LockCheck` runs through
+// `Diffed` and its delivery on every write.
+struct TrackedProbe {
+    probe: Probe,
+    changes: Vec<Change<()>>,
+}
+
+impl Tracked for TrackedProbe {
+    type Key = ();
+
+    fn take_changes(&mut self) -> Vec<Change<()>> {
+        std::mem::take(&mut self.changes)
+    }
+}
+
+type Inner = Diffed<RwLock<TrackedProbe>, TrackedProbe>;
+
+struct DiffedProbe(MappedLocket<Inner, TrackedProbe, Probe>);
+
+impl LockApi<Probe> for DiffedProbe {
+    type ReadGuard<'a> =
+        MappedReadGuard<'a, RwLockReadGuard<'a, TrackedProbe>, TrackedProbe, Probe>;
+
+    type WriteGuard<'a> = MappedWriteGuard<
+        'a,
+        DiffedGuard<'a, RwLock<TrackedProbe>, TrackedProbe, RwLockWriteGuard<'a, TrackedProbe>>,
+        TrackedProbe,
+        Probe,
+    >;
+
+    fn read(&self) -> locket::Result<Self::ReadGuard<'_>> {
+        self.0.read()
+    }
+
+    fn write(&self) -> locket::Result<Self::WriteGuard<'_>> {
+        self.0.write()
+    }
+
+    fn new(probe: Probe) -> Self {
+        let diffed = Inner::new(TrackedProbe {
+            probe,
+            changes: Vec::new(),
+        });
+        DiffedProbe(MappedLocket::new(
+            diffed,
+            |tracked| &tracked.probe,
+            |tracked| {
+                tracked.changes.push(Change::Updated(()));
+                &mut tracked.probe
+            },
+        ))
+    }
+}
+
+#[test]
+fn lock_check() {
+    LockCheck::new().shared_reads(true).run::<DiffedProbe>();
+}
+
+#[test]
+fn map_changes_reach_subscribers_and_observers() {
+    let observed = Arc::new(AtomicUsize::new(0));
+    let diffed = Diffed::<RwLock<TrackedMap<&str, u32>>, _>::new(TrackedMap::new()).on_change({
+        let observed = observed.clone();
+        move |changes| {
+            observed.fetch_add(changes.len(), Ordering::SeqCst);
+        }
+    });
+    let changes = diffed.subscribe();
+
+    {
+        let mut map = LockApi::write(&diffed).unwrap();
+        map.insert("a", 1);
+        map.insert("b", 2);
+        map.insert("a", 3);
+    }
+    // Reads and writes changing nothing deliver nothing.
+    drop(LockApi::read(&diffed).unwrap());
+    assert_eq!(LockApi::write(&diffed).unwrap().remove(&"c"), None);
+    {
+        let mut map = LockApi::write(&diffed).unwrap();
+        *map.get_mut(&"b").unwrap() += 1;
+        map.remove(&"a");
+    }
+
+    assert_eq!(
+        *changes.try_recv().unwrap(),
+        [
+            Change::Inserted("a"),
+            Change::Inserted("b"),
+            Change::Updated("a")
+        ]
+    );
+    assert_eq!(
+        *changes.try_recv().unwrap(),
+        [Change::Updated("b"), Change::Removed("a")]
+    );
+    assert!(changes.try_recv().is_err());
+    assert_eq!(observed.load(Ordering::SeqCst), 5);
+    assert_eq!(LockApi::read(&diffed).unwrap().get("b"), Some(&3));
+}
+
+#[test]
+fn vec_changes_are_indices() {
+    let mut vec = TrackedVec::from(vec![1, 2]);
+    vec.push(3);
+    vec.insert(0, 0);
+    *vec.get_mut(1).unwrap() = 10;
+    vec.remove(2);
+    vec.truncate(1);
+    assert_eq!(*vec, [0]);
+    assert_eq!(
+        vec.take_changes(),
+        [
+            Change::Inserted(2),
+            Change::Inserted(0),
+            Change::Updated(1),
+            Change::Removed(2),
+            Change::Removed(2),
+            Change::Removed(1),
+        ]
+    );
+    assert!(vec.take_changes().is_empty());
+}
@@ -0,0 +1,50 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    thread,
+};
+
+use locket::{testing::LockCheck, DoubleBuffered, LockApi, LockError, TryLockApi};
+
+#[test]
+fn lock_check() {
+    LockCheck::new()
+        .shared_reads(true)
+        .snapshot_reads(true)
+        .run::<DoubleBuffered<_>>();
+}
+
+#[test]
+fn lock_check_async() {
+    LockCheck::new()
+        .shared_reads(true)
+        .snapshot_reads(true)
+        .run_async::<DoubleBuffered<_>>();
+}
+
+#[test]
+fn readers_see_the_last_published_write() {
+    let lock = DoubleBuffered::new(vec![1]);
+    let mut write = LockApi::write(&lock).unwrap();
+    write.push(2);
+    thread::scope(|scope| {
+        scope.spawn(|| assert_eq!(*LockApi::read(&lock).unwrap(), [1]));
+    });
+    assert!(matches!(lock.try_write(), Err(LockError::WouldBlock)));
+    drop(write);
+    assert_eq!(*LockApi::read(&lock).unwrap(), [1, 2]);
+    assert_eq!(lock.into_inner(), [1, 2]);
+}
+
+#[test]
+fn panicking_writes_are_not_published() {
+    let lock = DoubleBuffered::new(1);
+    let panicked = catch_unwind(AssertUnwindSafe(|| {
+        let mut write = LockApi::write(&lock).unwrap();
+        *write = 2;
+        panic!("interrupted write");
+    }));
+    assert!(panicked.is_err());
+    assert_eq!(*LockApi::read(&lock).unwrap(), 1);
+    *LockApi::write(&lock).unwrap() += 2;
+    assert_eq!(*LockApi::read(&lock).unwrap(), 3);
+}
@@ -0,0 +1,82 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use locket::{EpochLocket, LockApi, LockError, TryLockApi};
+
+#[test]
+fn readers_keep_their_version() {
+    let lock = EpochLocket::new(vec![1]);
+    let read = LockApi::read(&lock).unwrap();
+    LockApi::write(&lock).unwrap().push(2);
+    assert_eq!(*read, [1]);
+    drop(read);
+    assert_eq!(*LockApi::read(&lock).unwrap(), [1, 2]);
+}
+
+#[test]
+fn writers_are_serialized() {
+    let lock = EpochLocket::new(0u64);
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..1000 {
+                    *LockApi::write(&lock).unwrap() += 1;
+                    let _ = *LockApi::read(&lock).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(lock.into_inner(), 4000);
+}
+
+#[test]
+fn try_write_fails_while_written() {
+    let lock = EpochLocket::new(0);
+    let _write = LockApi::write(&lock).unwrap();
+    assert!(matches!(lock.try_write(), Err(LockError::WouldBlock)));
+    assert!(lock.try_read().is_ok());
+}
+
+#[test]
+fn panicking_writer_keeps_the_old_version() {
+    let lock = EpochLocket::new(1);
+    let result = thread::scope(|scope| {
+        scope
+            .spawn(|| {
+                let mut write = LockApi::write(&lock).unwrap();
+                *write = 2;
+                panic!("writer failed");
+            })
+            .join()
+    });
+    assert!(result.is_err());
+    assert_eq!(*LockApi::read(&lock).unwrap(), 1);
+}
+
+#[derive(Clone)]
+struct Counted(Arc<AtomicUsize>);
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn into_inner_does_not_drop_the_value() {
+    let drops = Arc::new(AtomicUsize::new(0));
+    let lock = EpochLocket::new(Counted(drops.clone()));
+    let value = lock.into_inner();
+    assert_eq!(drops.load(Ordering::SeqCst), 0);
+    drop(value);
+    assert_eq!(drops.load(Ordering::SeqCst), 1);
+
+    let lock = EpochLocket::new(Counted(drops.clone()));
+    drop(lock);
+    assert_eq!(drops.load(Ordering::SeqCst), 2);
+}
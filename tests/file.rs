@@ -0,0 +1,39 @@
+use locket::{testing::LockCheck, FileLock, LockApi, LockError, TryLockApi};
+
+#[test]
+fn lock_check() {
+    LockCheck::new().shared_reads(true).run::<FileLock<_>>();
+}
+
+// Each `open` has a file handle of its own, so two of them on one path
+// exclude each other like two processes would.
+#[test]
+fn handles_on_one_path_exclude_each_other() {
+    let path = std::env::temp_dir().join(format!("locket-test-{}.lock", std::process::id()));
+    let first = FileLock::open(&path, 1).unwrap();
+    let second = FileLock::open(&path, 2).unwrap();
+
+    let write = first.try_write().unwrap();
+    assert!(matches!(second.try_read(), Err(LockError::WouldBlock)));
+    assert!(matches!(second.try_write(), Err(LockError::WouldBlock)));
+    drop(write);
+
+    let read = first.try_read().unwrap();
+    let other = first.try_read().unwrap();
+    assert_eq!(*second.try_read().unwrap(), 2);
+    assert!(matches!(second.try_write(), Err(LockError::WouldBlock)));
+    drop((read, other));
+    assert!(second.try_write().is_ok());
+
+    assert_eq!(first.path(), path);
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn new_removes_its_file() {
+    let lock = <FileLock<u32> as LockApi<u32>>::new(0);
+    let path = lock.path().to_path_buf();
+    assert!(path.exists());
+    drop(lock);
+    assert!(!path.exists());
+}
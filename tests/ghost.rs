@@ -0,0 +1,36 @@
+use std::thread;
+
+use locket::{GhostCell, GhostToken, LockApiReadGuard, LockApiWriteGuard};
+
+#[test]
+fn token_borrows_guard_cells() {
+    GhostToken::scope(|mut token| {
+        let a = GhostCell::new(1);
+        let b = GhostCell::new(2);
+        {
+            let mut guard = a.write(&mut token);
+            *guard.get_mut() += 10;
+        }
+        *b.write(&mut token) += *a.read(&token);
+        assert_eq!(*a.read(&token).get(), 11);
+        assert_eq!(b.into_inner(), 13);
+    });
+}
+
+#[test]
+fn shared_token_reads_from_threads() {
+    GhostToken::scope(|mut token| {
+        let cells: Vec<_> = (0..4).map(GhostCell::new).collect();
+        for cell in &cells {
+            *cell.write(&mut token) *= 2;
+        }
+        let token = &token;
+        let sums: Vec<i32> = thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| cells.iter().map(|cell| *cell.read(token)).sum()))
+                .collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(sums, [12; 4]);
+    });
+}
@@ -0,0 +1,45 @@
+use std::sync::{Arc, Mutex};
+
+use locket::{LockApi, Locket};
+
+#[test]
+fn into_writer_needs_the_only_handle() {
+    let lock = Arc::new(Mutex::new(1));
+    let other = lock.clone();
+    let Err(lock) = lock.into_writer() else {
+        panic!("other handles exist")
+    };
+    drop(other);
+
+    let Ok(writer) = lock.into_writer() else {
+        panic!("only handle")
+    };
+    *writer.write().unwrap() = 2;
+    assert_eq!(*LockApi::read(&*writer.into_inner()).unwrap(), 2);
+}
+
+// A weak handle could be upgraded into a second writer.
+#[test]
+fn into_writer_rejects_live_weak_handles() {
+    let lock = Arc::new(Mutex::new(1));
+    let weak = Arc::downgrade(&lock);
+    let Err(lock) = lock.into_writer() else {
+        panic!("other handles exist")
+    };
+    assert!(lock.clone().split().is_err());
+    drop(weak);
+    assert!(lock.into_writer().is_ok());
+}
+
+#[test]
+fn split_halves_reunite() {
+    let Ok((writer, reader)) = Arc::new(Mutex::new(1)).split() else {
+        panic!("only handle")
+    };
+    *writer.write().unwrap() += 1;
+    assert_eq!(*reader.read().unwrap(), 2);
+    let Ok(lock) = writer.reunite(reader) else {
+        panic!("halves of one locket")
+    };
+    assert_eq!(Arc::strong_count(&lock), 1);
+}
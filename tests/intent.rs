@@ -0,0 +1,61 @@
+use locket::{
+    testing::{LockCheck, Probe},
+    IntentLocket, IntentReadGuard, IntentWriteGuard, LockApi, LockError, TryLockApi,
+};
+
+#[test]
+fn root() {
+    LockCheck::new().shared_reads(true).run::<IntentLocket<_>>();
+}
+
+// The child's guards also take intent locks on the root.
+#[test]
+fn child() {
+    struct Child(IntentLocket<Probe>);
+
+    impl LockApi<Probe> for Child {
+        type ReadGuard<'a> = IntentReadGuard<'a, Probe>;
+
+        type WriteGuard<'a> = IntentWriteGuard<'a, Probe>;
+
+        fn read(&self) -> locket::Result<Self::ReadGuard<'_>> {
+            LockApi::read(&self.0)
+        }
+
+        fn write(&self) -> locket::Result<Self::WriteGuard<'_>> {
+            LockApi::write(&self.0)
+        }
+
+        fn new(inner: Probe) -> Self {
+            Child(IntentLocket::new(()).child(inner))
+        }
+    }
+
+    LockCheck::new().shared_reads(true).run::<Child>();
+}
+
+#[test]
+fn guards_cover_their_subtree() {
+    let root = IntentLocket::new("root");
+    let left = root.child(1);
+    let right = root.child(2);
+    let leaf = left.child(3);
+
+    let write = left.try_write().unwrap();
+    assert!(right.try_write().is_ok());
+    assert!(matches!(leaf.try_read(), Err(LockError::WouldBlock)));
+    assert!(matches!(root.try_read(), Err(LockError::WouldBlock)));
+    assert!(matches!(root.try_write(), Err(LockError::WouldBlock)));
+    drop(write);
+
+    let read = root.try_read().unwrap();
+    assert!(leaf.try_read().is_ok());
+    assert!(matches!(right.try_write(), Err(LockError::WouldBlock)));
+    drop(read);
+
+    let write = root.try_write().unwrap();
+    assert!(matches!(leaf.try_read(), Err(LockError::WouldBlock)));
+    drop(write);
+    assert!(leaf.try_write().is_ok());
+    assert!(root.is_root() && !leaf.is_root());
+}
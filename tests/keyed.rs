@@ -0,0 +1,72 @@
+use std::{
+    sync::{mpsc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    thread,
+};
+
+use locket::{
+    testing::{LockCheck, Probe},
+    KeyedGuard, KeyedLocket, LockApi,
+};
+
+// Runs `LockCheck` against the lock behind a single key.
+struct OneKey(KeyedLocket<u8, Probe, RwLock<Probe>>);
+
+impl LockApi<Probe> for OneKey {
+    type ReadGuard<'a> = KeyedGuard<'a, u8, Probe, RwLock<Probe>, RwLockReadGuard<'a, Probe>>;
+
+    type WriteGuard<'a> = KeyedGuard<'a, u8, Probe, RwLock<Probe>, RwLockWriteGuard<'a, Probe>>;
+
+    fn read(&self) -> locket::Result<Self::ReadGuard<'_>> {
+        self.0.read(0)
+    }
+
+    fn write(&self) -> locket::Result<Self::WriteGuard<'_>> {
+        self.0.write(0)
+    }
+
+    fn new(inner: Probe) -> Self {
+        let keyed = KeyedLocket::new();
+        *keyed.write(0).unwrap() = inner;
+        OneKey(keyed)
+    }
+}
+
+#[test]
+fn one_key() {
+    LockCheck::new().shared_reads(true).run::<OneKey>();
+}
+
+#[test]
+fn keys_are_locked_separately() {
+    let keyed = KeyedLocket::<&str, u32, RwLock<u32>>::with_init(|key| key.len() as u32);
+    let mut a = keyed.write("a").unwrap();
+    thread::scope(|scope| {
+        let keyed = &keyed;
+        let (done, finished) = mpsc::channel();
+        scope.spawn(move || {
+            *keyed.write("bc").unwrap() += 1;
+            done.send(()).unwrap();
+        });
+        finished.recv().unwrap();
+    });
+    *a += 1;
+    assert_eq!(*a.key(), "a");
+    drop(a);
+    assert_eq!(*keyed.read("a").unwrap(), 2);
+    assert_eq!(*keyed.read("bc").unwrap(), 3);
+    assert_eq!(keyed.len(), 2);
+}
+
+#[test]
+fn pruning_drops_unlocked_keys() {
+    let keyed = KeyedLocket::<u8, u32, RwLock<u32>>::new().pruning(true);
+    let first = keyed.read(1).unwrap();
+    let second = keyed.read(1).unwrap();
+    *keyed.write(2).unwrap() = 5;
+    assert!(!keyed.contains_key(&2));
+    drop(first);
+    assert!(keyed.contains_key(&1));
+    drop(second);
+    assert!(keyed.is_empty());
+    assert_eq!(*keyed.read(2).unwrap(), 0);
+}
@@ -0,0 +1,51 @@
+use std::{thread, time::Duration};
+
+use locket::{LeasedLocket, LockError};
+
+// `LeasedLocket` hands out its value through closures rather than `LockApi`
+// guards, so this does the `LockCheck` hammering by hand.
+#[test]
+fn writes_are_exclusive_and_none_is_lost() {
+    let lock = LeasedLocket::new((0u64, 0u64), Duration::from_secs(60));
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for i in 0..1000 {
+                    if i % 4 == 0 {
+                        let guard = lock.write().unwrap();
+                        guard.with(|probe| probe.0 += 1).unwrap();
+                        thread::yield_now();
+                        guard.with(|probe| probe.1 += 1).unwrap();
+                    } else {
+                        lock.read(|probe| assert_eq!(probe.0, probe.1, "read a half-done write"))
+                            .unwrap();
+                    }
+                }
+            });
+        }
+    });
+    assert_eq!(lock.read(|probe| *probe).unwrap(), (1000, 1000));
+    assert_eq!(lock.revocations(), 0);
+}
+
+#[test]
+fn waiters_proceed_when_a_lease_runs_out() {
+    let lock = LeasedLocket::new(0, Duration::from_millis(50));
+    let stuck = lock.write().unwrap();
+    assert!(matches!(
+        lock.write_timeout(Duration::from_millis(1)),
+        Err(LockError::Timeout)
+    ));
+
+    let next = lock.write().unwrap();
+    next.with(|value| *value = 1).unwrap();
+    assert!(stuck.is_revoked());
+    assert!(matches!(stuck.with(|_| ()), Err(LockError::Expired)));
+    assert!(matches!(stuck.renew(), Err(LockError::Expired)));
+    // Dropping the revoked guard does not release the new lease.
+    drop(stuck);
+    assert!(matches!(lock.try_write(), Err(LockError::WouldBlock)));
+    drop(next);
+    assert_eq!(lock.read(|value| *value).unwrap(), 1);
+    assert_eq!(lock.revocations(), 1);
+}
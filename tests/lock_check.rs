@@ -0,0 +1,99 @@
+use std::sync::Arc;
+
+use locket::{
+    testing::{check_lock_api, LockCheck, Probe},
+    Locket, PolicyRwLock, RawLocket, RawSpinRwLock, SeqLock,
+};
+
+#[test]
+fn std_mutex() {
+    check_lock_api::<std::sync::Mutex<_>>();
+}
+
+#[test]
+fn std_rwlock() {
+    LockCheck::new()
+        .shared_reads(true)
+        .run::<std::sync::RwLock<_>>();
+}
+
+#[test]
+fn parking_lot_mutex() {
+    check_lock_api::<parking_lot::Mutex<_>>();
+}
+
+#[test]
+fn parking_lot_fair_mutex() {
+    check_lock_api::<parking_lot::FairMutex<_>>();
+}
+
+#[test]
+fn parking_lot_rwlock() {
+    LockCheck::new()
+        .shared_reads(true)
+        .run::<parking_lot::RwLock<_>>();
+}
+
+#[test]
+fn spin_mutex() {
+    check_lock_api::<spin::Mutex<_>>();
+}
+
+#[test]
+fn spin_rwlock() {
+    LockCheck::new().shared_reads(true).run::<spin::RwLock<_>>();
+}
+
+#[test]
+fn seqlock() {
+    LockCheck::new()
+        .shared_reads(true)
+        .snapshot_reads(true)
+        .run::<SeqLock<_>>();
+}
+
+#[test]
+fn policy_rwlock() {
+    LockCheck::new().shared_reads(true).run::<PolicyRwLock<_>>();
+}
+
+// `LockCheck` creates its lock through `LockApi::new`, so check that what the
+// builder produces behaves the same by running the checks through it.
+#[test]
+fn built_policy_rwlock() {
+    struct Built<const FAIR: bool>(Arc<PolicyRwLock<Probe>>);
+
+    impl<const FAIR: bool> locket::LockApi<Probe> for Built<FAIR> {
+        type ReadGuard<'a> = locket::PolicyReadGuard<'a, Probe>;
+
+        type WriteGuard<'a> = locket::PolicyWriteGuard<'a, Probe>;
+
+        fn read(&self) -> locket::Result<Self::ReadGuard<'_>> {
+            locket::LockApi::read(&*self.0)
+        }
+
+        fn write(&self) -> locket::Result<Self::WriteGuard<'_>> {
+            locket::LockApi::write(&*self.0)
+        }
+
+        fn new(inner: Probe) -> Self {
+            let lock: Arc<PolicyRwLock<Probe>> = Locket::builder()
+                .fair(FAIR)
+                .max_readers(2)
+                .build(inner)
+                .unwrap();
+            assert_eq!(lock.max_readers(), Some(2));
+            Built(lock)
+        }
+    }
+
+    LockCheck::new().shared_reads(true).run::<Built<false>>();
+    LockCheck::new().shared_reads(true).run::<Built<true>>();
+}
+
+#[test]
+fn raw_locket() {
+    LockCheck::new()
+        .shared_reads(true)
+        .run::<RawLocket<RawSpinRwLock, _>>();
+}
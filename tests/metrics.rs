@@ -0,0 +1,38 @@
+use std::{sync::Mutex, thread, time::Duration};
+
+use locket::{LockApi, Metrics, PolicyRwLock};
+
+#[test]
+fn blocked_reads_of_a_mutex_are_contended() {
+    let lock = Metrics::wrap(Mutex::new(0));
+    let held = LockApi::read(&lock).unwrap();
+    thread::scope(|scope| {
+        scope.spawn(|| drop(LockApi::read(&lock).unwrap()));
+        thread::sleep(Duration::from_millis(20));
+        drop(held);
+    });
+    let snapshot = lock.snapshot();
+    assert_eq!(snapshot.read_acquisitions, 2);
+    assert_eq!(snapshot.contended_acquisitions, 1);
+}
+
+#[test]
+fn shared_reads_are_not_contended() {
+    let lock = Metrics::wrap(PolicyRwLock::new(0));
+    let first = LockApi::read(&lock).unwrap();
+    let second = LockApi::read(&lock).unwrap();
+    drop((first, second));
+    assert_eq!(lock.snapshot().contended_acquisitions, 0);
+}
+
+#[test]
+fn writes_behind_readers_are_contended() {
+    let lock = Metrics::wrap(PolicyRwLock::new(0));
+    let held = LockApi::read(&lock).unwrap();
+    thread::scope(|scope| {
+        scope.spawn(|| *LockApi::write(&lock).unwrap() += 1);
+        thread::sleep(Duration::from_millis(20));
+        drop(held);
+    });
+    assert_eq!(lock.snapshot().contended_acquisitions, 1);
+}
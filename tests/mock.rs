@@ -0,0 +1,56 @@
+use std::thread;
+
+use locket::{
+    testing::{LockCheck, MockEventKind, MockLock},
+    LockApi, LockError,
+};
+
+#[test]
+fn lock_check() {
+    LockCheck::new().shared_reads(true).run::<MockLock<_>>();
+}
+
+#[test]
+fn same_thread_reentry_fails_instead_of_deadlocking() {
+    let lock = MockLock::new(0);
+
+    let read = LockApi::read(&lock).unwrap();
+    assert!(matches!(LockApi::write(&lock), Err(LockError::WouldBlock)));
+    // Reading twice does not wait for itself.
+    drop(LockApi::read(&lock).unwrap());
+    drop(read);
+
+    let write = LockApi::write(&lock).unwrap();
+    assert!(matches!(LockApi::read(&lock), Err(LockError::WouldBlock)));
+    assert!(matches!(LockApi::write(&lock), Err(LockError::WouldBlock)));
+    drop(write);
+
+    *LockApi::write(&lock).unwrap() += 1;
+    assert_eq!(lock.writes(), 2);
+    assert_eq!(lock.count(MockEventKind::WriteAttempt), 4);
+    lock.assert_released();
+}
+
+#[test]
+fn other_threads_still_wait() {
+    let lock = MockLock::new(0);
+    let read = LockApi::read(&lock).unwrap();
+    thread::scope(|scope| {
+        let writer = scope.spawn(|| *LockApi::write(&lock).unwrap() += 1);
+        while lock.count(MockEventKind::WriteAttempt) == 0 {
+            thread::yield_now();
+        }
+        assert!(!writer.is_finished());
+        drop(read);
+    });
+    assert_eq!(lock.into_inner(), 1);
+}
+
+#[test]
+#[should_panic(expected = "write attempted")]
+fn reentry_is_still_reported() {
+    let lock = MockLock::new(0);
+    let _read = LockApi::read(&lock).unwrap();
+    let _ = LockApi::write(&lock);
+    lock.assert_no_write_while_reading();
+}
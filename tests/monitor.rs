@@ -0,0 +1,40 @@
+use std::{sync::RwLock, thread};
+
+use locket::{testing::LockCheck, LockApi, Monitor};
+
+#[test]
+fn lock_check() {
+    LockCheck::new()
+        .shared_reads(true)
+        .run::<Monitor<_, RwLock<_>>>();
+}
+
+#[test]
+fn consumers_wait_for_producers() {
+    let queue = Monitor::<Vec<u32>, RwLock<_>>::new(Vec::new());
+    let consumed: u32 = thread::scope(|scope| {
+        let consumers: Vec<_> = (0..4)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut sum = 0;
+                    for _ in 0..25 {
+                        let mut jobs = queue
+                            .wait_while(LockApi::write(&queue).unwrap(), |jobs| jobs.is_empty())
+                            .unwrap();
+                        sum += jobs.pop().unwrap();
+                    }
+                    sum
+                })
+            })
+            .collect();
+        for job in 1..=100 {
+            queue.write_and_notify(|jobs| jobs.push(job)).unwrap();
+        }
+        consumers
+            .into_iter()
+            .map(|consumer| consumer.join().unwrap())
+            .sum()
+    });
+    assert_eq!(consumed, 5050);
+    assert!(LockApi::read(&queue).unwrap().is_empty());
+}
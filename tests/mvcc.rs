@@ -0,0 +1,36 @@
+use locket::{testing::LockCheck, LockApi, LockError, MvccLocket, TryLockApi};
+
+#[test]
+fn lock_check() {
+    LockCheck::new()
+        .shared_reads(true)
+        .snapshot_reads(true)
+        .run::<MvccLocket<_>>();
+}
+
+#[test]
+fn readers_keep_their_version() {
+    let lock = MvccLocket::new(vec![1]);
+    let before = LockApi::read(&lock).unwrap();
+    let mut write = LockApi::write(&lock).unwrap();
+    write.push(2);
+    assert_eq!(*LockApi::read(&lock).unwrap(), [1]);
+    assert!(matches!(lock.try_write(), Err(LockError::WouldBlock)));
+    drop(write);
+
+    let after = LockApi::read(&lock).unwrap();
+    assert_eq!((before.version(), &*before), (0, &vec![1]));
+    assert_eq!((after.version(), &*after), (1, &vec![1, 2]));
+    assert_eq!(lock.version(), 1);
+}
+
+#[test]
+fn aborted_writes_are_not_committed() {
+    let lock = MvccLocket::new(1);
+    let mut write = LockApi::write(&lock).unwrap();
+    *write = 2;
+    write.abort();
+    assert_eq!((lock.version(), *lock.load()), (0, 1));
+    *LockApi::write(&lock).unwrap() += 2;
+    assert_eq!((lock.version(), *lock.load()), (1, 3));
+}
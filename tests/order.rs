@@ -0,0 +1,47 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::Mutex,
+};
+
+use locket::{LockApi, Ordered};
+
+#[test]
+fn levels_are_taken_in_increasing_order() {
+    let low = Ordered::with_level(Mutex::new(0), 1);
+    let high = Ordered::with_level(Mutex::new(0), 2);
+
+    let first = LockApi::write(&low).unwrap();
+    let second = LockApi::write(&high).unwrap();
+    drop((second, first));
+
+    // Released levels no longer count.
+    drop(LockApi::write(&high).unwrap());
+    drop(LockApi::write(&low).unwrap());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn inversions_panic_in_debug_builds() {
+    let low = Ordered::with_level(Mutex::new(0), 1);
+    let high = Ordered::with_level(Mutex::new(0), 2);
+    let other = Ordered::with_level(Mutex::new(0), 2);
+
+    let held = LockApi::write(&high).unwrap();
+    for lock in [&low, &other] {
+        let panicked = catch_unwind(AssertUnwindSafe(|| drop(LockApi::write(lock))));
+        assert!(panicked.is_err());
+    }
+    drop(held);
+    drop(LockApi::write(&low).unwrap());
+}
+
+#[cfg(not(debug_assertions))]
+#[test]
+fn inversions_proceed_in_release_builds() {
+    let low = Ordered::with_level(Mutex::new(0), 1);
+    let high = Ordered::with_level(Mutex::new(0), 2);
+
+    let _held = LockApi::write(&high).unwrap();
+    *LockApi::write(&low).unwrap() += 1;
+    assert_eq!(*LockApi::read(&low).unwrap(), 1);
+}
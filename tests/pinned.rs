@@ -0,0 +1,74 @@
+use std::{
+    future::Future,
+    marker::PhantomPinned,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+use locket::{LockApi, Locket, PinnedLocket};
+
+// Pending on the first poll, so the future is polled through two guards.
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if std::mem::replace(&mut self.0, true) {
+            return Poll::Ready(());
+        }
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+#[test]
+fn polls_a_pinned_future_in_place() {
+    let lock: PinnedLocket<Arc<Mutex<_>>> = PinnedLocket::new(async {
+        YieldOnce(false).await;
+        42
+    });
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut guard = lock.write_pinned().unwrap();
+    assert_eq!(guard.as_mut().poll(&mut cx), Poll::Pending);
+    drop(guard);
+    let other = lock.clone();
+    let mut guard = other.write_pinned().unwrap();
+    assert_eq!(guard.as_mut().poll(&mut cx), Poll::Ready(42));
+}
+
+// Records its own address on every look, which must not change.
+struct Anchored {
+    at: Option<usize>,
+    _pin: PhantomPinned,
+}
+
+impl Anchored {
+    fn check(self: Pin<&mut Self>) {
+        // SAFETY: nothing is moved out.
+        let this = unsafe { self.get_unchecked_mut() };
+        let here = this as *const Anchored as usize;
+        assert_eq!(*this.at.get_or_insert(here), here, "value moved");
+    }
+}
+
+#[test]
+fn value_stays_put() {
+    let lock: PinnedLocket<Arc<parking_lot::Mutex<_>>> = PinnedLocket::new(Anchored {
+        at: None,
+        _pin: PhantomPinned,
+    });
+    for _ in 0..3 {
+        lock.clone().write_pinned().unwrap().as_mut().check();
+    }
+    assert!(lock.read().unwrap().at.is_some());
+}
+
+#[test]
+fn unpin_values_use_lock_api() {
+    let lock = <Arc<Mutex<_>> as Locket<_>>::pin(1);
+    *LockApi::write(&lock).unwrap() += 1;
+    lock.write_pinned().unwrap().set(5);
+    assert_eq!(*LockApi::read(&lock).unwrap(), 5);
+}
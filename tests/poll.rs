@@ -0,0 +1,72 @@
+use std::{
+    future::poll_fn,
+    sync::Arc,
+    task::{ready, Poll},
+};
+
+use locket::{OwnedPollLock, Result};
+use tokio::sync::RwLock;
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn owned_poll_lock_moves_into_a_task() {
+    let runtime = runtime();
+    let lock = Arc::new(RwLock::new(0));
+    let held = runtime.block_on(lock.clone().write_owned());
+
+    let mut poll = OwnedPollLock::<u32, _>::new(lock.clone());
+    let task = async move {
+        poll_fn(move |cx| -> Poll<Result<u32>> {
+            let mut guard = ready!(poll.poll_write(cx))?;
+            *guard += 1;
+            Poll::Ready(Ok(*guard))
+        })
+        .await
+    };
+
+    let task = runtime.spawn(task);
+    runtime.block_on(async {
+        tokio::task::yield_now().await;
+        assert!(!task.is_finished());
+        drop(held);
+        assert_eq!(task.await.unwrap().unwrap(), 1);
+    });
+    assert_eq!(Arc::strong_count(&lock), 1);
+}
+
+#[test]
+fn owned_guards_keep_the_lock_alive() {
+    let mut poll = OwnedPollLock::<u32, _>::new(Arc::new(RwLock::new(5)));
+    let guard = runtime()
+        .block_on(poll_fn(|cx| poll.poll_read(cx)))
+        .unwrap();
+    let lock = poll.into_lock();
+    assert_eq!(Arc::strong_count(&lock), 2);
+    drop(lock);
+    assert_eq!(*guard, 5);
+    assert_eq!(Arc::strong_count(&guard.unlock()), 1);
+}
+
+#[test]
+fn cancel_gives_up_a_pending_acquisition() {
+    let lock = Arc::new(RwLock::new(0));
+    let mut poll = OwnedPollLock::<u32, _>::new(lock.clone());
+    runtime().block_on(async {
+        let held = lock.read().await;
+        poll_fn(|cx| {
+            assert!(poll.poll_write(cx).is_pending());
+            Poll::Ready(())
+        })
+        .await;
+        assert!(poll.is_pending());
+        poll.cancel();
+        assert!(!poll.is_pending());
+        drop(held);
+        assert!(lock.try_write().is_ok());
+    });
+}
@@ -0,0 +1,47 @@
+use std::{sync::RwLock, thread};
+
+use locket::{testing::LockCheck, LockApi, Queued};
+
+#[test]
+fn lock_check() {
+    LockCheck::new()
+        .shared_reads(true)
+        .run::<Queued<RwLock<_>, _>>();
+}
+
+#[test]
+fn mutations_wait_for_the_guard() {
+    let lock = Queued::<RwLock<Vec<u32>>, _>::new(vec![]);
+    lock.send(|value| value.push(1)).unwrap();
+    assert_eq!(lock.pending(), 0);
+
+    let read = LockApi::read(&lock).unwrap();
+    lock.send(|value| value.push(2)).unwrap();
+    lock.send(|value| value.push(3)).unwrap();
+    assert_eq!(lock.pending(), 2);
+    assert_eq!(*read, [1]);
+    drop(read);
+    assert_eq!(lock.pending(), 0);
+    assert_eq!(*LockApi::read(&lock).unwrap(), [1, 2, 3]);
+}
+
+#[test]
+fn no_mutation_is_lost() {
+    let lock = Queued::<RwLock<u64>, _>::new(0);
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for i in 0..1000 {
+                    if i % 10 == 0 {
+                        *LockApi::write(&lock).unwrap() += 1;
+                    } else {
+                        lock.send(|value| *value += 1).unwrap();
+                    }
+                }
+            });
+        }
+    });
+    // The last guard dropped applied whatever was queued while it was held.
+    assert_eq!(lock.pending(), 0);
+    assert_eq!(*LockApi::read(&lock).unwrap(), 4000);
+}
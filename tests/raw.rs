@@ -0,0 +1,78 @@
+use std::thread;
+
+use locket::{LockApi, LockError, LockStats, RawLockApi, RawLocket, RawSpinRwLock, TryLockApi};
+
+#[test]
+fn raw_lock_excludes() {
+    let raw = RawSpinRwLock::new();
+    raw.lock_shared();
+    assert!(raw.try_lock_shared());
+    assert!(!raw.try_lock());
+    // SAFETY: two shared locks are held.
+    unsafe {
+        raw.unlock_shared();
+        raw.unlock_shared();
+    }
+    assert!(!raw.is_locked());
+
+    raw.lock();
+    assert!(raw.is_locked_exclusive());
+    assert!(!raw.try_lock_shared());
+    assert!(!raw.try_lock());
+    // SAFETY: the exclusive lock is held.
+    unsafe { raw.unlock() };
+    assert!(raw.try_lock());
+    // SAFETY: as above.
+    unsafe { raw.unlock() };
+}
+
+#[test]
+fn guards_release_the_raw_lock() {
+    let lock = RawLocket::<RawSpinRwLock, _>::new(0);
+    {
+        let _read = LockApi::read(&lock).unwrap();
+        let _other = lock.try_read().unwrap();
+        assert_eq!(lock.reader_count(), None);
+        assert!(matches!(lock.try_write(), Err(LockError::WouldBlock)));
+    }
+    {
+        let mut write = LockApi::write(&lock).unwrap();
+        *write += 1;
+        assert!(matches!(lock.try_read(), Err(LockError::WouldBlock)));
+    }
+    assert!(!lock.raw().is_locked());
+    assert_eq!(lock.into_inner(), 1);
+}
+
+#[test]
+fn unchecked_guards_take_over_the_raw_lock() {
+    let lock = RawLocket::<RawSpinRwLock, _>::new(0);
+    lock.raw().lock();
+    // SAFETY: the exclusive lock was just taken.
+    let mut guard = unsafe { lock.make_write_guard_unchecked() };
+    *guard = 5;
+    drop(guard);
+
+    lock.raw().lock_shared();
+    // SAFETY: a shared lock was just taken.
+    let guard = unsafe { lock.make_read_guard_unchecked() };
+    assert_eq!(*guard, 5);
+    assert!(!lock.raw().try_lock());
+    drop(guard);
+    assert!(!lock.raw().is_locked());
+}
+
+#[test]
+fn counts_across_threads() {
+    let lock = RawLocket::<RawSpinRwLock, _>::new(0u64);
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..1000 {
+                    *LockApi::write(&lock).unwrap() += 1;
+                }
+            });
+        }
+    });
+    assert_eq!(*LockApi::read(&lock).unwrap(), 4000);
+}
@@ -0,0 +1,49 @@
+use std::sync::Arc;
+
+use locket::{LockApi, PolicyRwLock, RecursionChecked, RwPolicy, TryLockApi};
+
+#[test]
+fn second_read_of_read_preferring_lock_is_allowed() {
+    let lock = RecursionChecked::with_rw_policy(
+        PolicyRwLock::with_policy(0, RwPolicy::ReadPreferring),
+        "counter",
+    );
+    let _first = LockApi::read(&lock).unwrap();
+    let _second = LockApi::read(&lock).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "recursive read of counter")]
+fn second_read_of_write_preferring_lock_is_reported() {
+    let lock = RecursionChecked::with_rw_policy(PolicyRwLock::new(0), "counter");
+    let _first = LockApi::read(&lock).unwrap();
+    let _second = LockApi::read(&lock).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "recursive read of counter")]
+fn second_read_of_unknown_policy_is_reported() {
+    let lock = RecursionChecked::with_name(
+        PolicyRwLock::with_policy(0, RwPolicy::ReadPreferring),
+        "counter",
+    );
+    let _first = LockApi::read(&lock).unwrap();
+    let _second = LockApi::read(&lock).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "recursive write of shared")]
+fn checkers_around_one_lock_see_each_other() {
+    let lock = Arc::new(PolicyRwLock::new(0));
+    let first = RecursionChecked::with_name(lock.clone(), "shared");
+    let second = RecursionChecked::with_name(lock, "shared");
+    let _read = LockApi::read(&first).unwrap();
+    let _write = LockApi::write(&second);
+}
+
+#[test]
+fn try_locks_are_not_reported() {
+    let lock = RecursionChecked::with_name(PolicyRwLock::new(0), "counter");
+    let _write = LockApi::write(&lock).unwrap();
+    assert!(lock.try_read().is_err());
+}
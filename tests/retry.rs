@@ -0,0 +1,35 @@
+use std::{
+    cell::RefCell,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use locket::{config, retry, testing::MockClock, Backoff, LockApi, LockError};
+
+#[test]
+fn attempts_bound_the_retries() {
+    let cell = RefCell::new(0);
+    let _held = cell.borrow_mut();
+    let result = retry(Backoff::Spin).attempts(5).read(&cell);
+    assert!(matches!(result, Err(LockError::WouldBlock)));
+}
+
+// The only test in this binary touching the crate-wide maximum wait.
+#[test]
+fn max_wait_ends_unbounded_retries_on_the_mock_clock() {
+    let clock = MockClock::new().with_step(Duration::from_millis(10));
+    let _installed = clock.install();
+    config::set_max_wait(Duration::from_secs(30));
+
+    let lock = Mutex::new(0);
+    let held = LockApi::write(&lock).unwrap();
+    let started = Instant::now();
+    let result = retry(Backoff::Spin).write(&lock);
+    config::clear_max_wait();
+
+    assert!(matches!(result, Err(LockError::Timeout)));
+    assert!(clock.elapsed() >= Duration::from_secs(30));
+    assert!(started.elapsed() < Duration::from_secs(30));
+    drop(held);
+    assert!(retry(Backoff::Spin).write(&lock).is_ok());
+}
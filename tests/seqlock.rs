@@ -0,0 +1,51 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+};
+
+use locket::{LockApi, LockError, SeqLock, TryLockApi};
+
+#[test]
+fn reads_are_never_torn() {
+    let lock = SeqLock::new([0u64; 8]);
+    let done = AtomicBool::new(false);
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            for _ in 0..10_000 {
+                let mut guard = LockApi::write(&lock).unwrap();
+                for word in guard.iter_mut() {
+                    *word += 1;
+                }
+            }
+            done.store(true, Ordering::Release);
+        });
+        for _ in 0..2 {
+            scope.spawn(|| {
+                while !done.load(Ordering::Acquire) {
+                    let words = *LockApi::read(&lock).unwrap();
+                    assert!(words.iter().all(|word| *word == words[0]), "{words:?}");
+                }
+            });
+        }
+    });
+    assert_eq!(lock.load(), [10_000; 8]);
+}
+
+#[test]
+fn writers_exclude_each_other() {
+    let lock = SeqLock::new(1u32);
+    let mut guard = LockApi::write(&lock).unwrap();
+    assert!(matches!(lock.try_write(), Err(LockError::WouldBlock)));
+    *guard = 2;
+    drop(guard);
+    assert_eq!(*lock.try_write().unwrap(), 2);
+}
+
+#[test]
+fn read_guards_are_snapshots() {
+    let lock = SeqLock::new(1u32);
+    let read = LockApi::read(&lock).unwrap();
+    *LockApi::write(&lock).unwrap() = 2;
+    assert_eq!(*read, 1);
+    assert_eq!(lock.into_inner(), 2);
+}
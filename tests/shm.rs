@@ -0,0 +1,70 @@
+use std::{fs, path::PathBuf, thread};
+
+use locket::{LockApi, LockError, ShmLock, TryLockApi};
+
+struct TempPath(PathBuf);
+
+impl TempPath {
+    fn new(name: &str) -> TempPath {
+        let path = std::env::temp_dir().join(format!("locket-{name}-{}", std::process::id()));
+        fs::remove_file(&path).ok();
+        TempPath(path)
+    }
+}
+
+impl Drop for TempPath {
+    fn drop(&mut self) {
+        fs::remove_file(&self.0).ok();
+    }
+}
+
+#[test]
+fn mappings_share_value_and_lock() {
+    let path = TempPath::new("share");
+    let first = ShmLock::open(&path.0, 7u64).unwrap();
+    let second = ShmLock::open(&path.0, 0u64).unwrap();
+    assert_eq!(*LockApi::read(&second).unwrap(), 7);
+
+    let mut guard = LockApi::write(&first).unwrap();
+    assert!(matches!(second.try_read(), Err(LockError::WouldBlock)));
+    *guard = 8;
+    drop(guard);
+
+    let _read = LockApi::read(&first).unwrap();
+    assert!(matches!(second.try_write(), Err(LockError::WouldBlock)));
+    assert_eq!(*second.try_read().unwrap(), 8);
+}
+
+#[test]
+fn rejects_a_segment_of_another_size() {
+    let path = TempPath::new("size");
+    let _lock = ShmLock::open(&path.0, 0u64).unwrap();
+    let err = ShmLock::open(&path.0, [0u64; 4]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn counts_across_mappings() {
+    let path = TempPath::new("count");
+    let locks: Vec<_> = (0..4)
+        .map(|_| ShmLock::open(&path.0, 0u64).unwrap())
+        .collect();
+    thread::scope(|scope| {
+        for lock in &locks {
+            scope.spawn(move || {
+                for _ in 0..1000 {
+                    *LockApi::write(lock).unwrap() += 1;
+                }
+            });
+        }
+    });
+    assert_eq!(*LockApi::read(&locks[0]).unwrap(), 4000);
+}
+
+#[test]
+fn anonymous_segments_are_private() {
+    let lock = <ShmLock<u32> as LockApi<u32>>::new(1);
+    assert!(lock.path().is_none());
+    *LockApi::write(&lock).unwrap() += 1;
+    assert_eq!(*LockApi::read(&lock).unwrap(), 2);
+}
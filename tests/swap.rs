@@ -0,0 +1,48 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::Arc,
+};
+
+use locket::{testing::LockCheck, LockApi, LockError, SwapLock, TryLockApi};
+
+#[test]
+fn lock_check() {
+    LockCheck::new()
+        .shared_reads(true)
+        .snapshot_reads(true)
+        .run::<SwapLock<_>>();
+}
+
+#[test]
+fn readers_keep_their_snapshot() {
+    let lock = SwapLock::new(vec![1]);
+    let before = LockApi::read(&lock).unwrap();
+    let mut write = LockApi::write(&lock).unwrap();
+    write.push(2);
+    assert_eq!(*LockApi::read(&lock).unwrap(), [1]);
+    assert!(matches!(lock.try_write(), Err(LockError::WouldBlock)));
+    drop(write);
+    assert_eq!(*before, [1]);
+    assert_eq!(*lock.load(), [1, 2]);
+
+    let snapshot = before.snapshot();
+    drop(before);
+    lock.store(vec![3]);
+    assert_eq!(*snapshot, [1]);
+    assert_eq!(*LockApi::read(&lock).unwrap(), [3]);
+}
+
+#[test]
+fn panicking_writes_are_not_published() {
+    let lock = SwapLock::new(1);
+    let published = lock.load();
+    let panicked = catch_unwind(AssertUnwindSafe(|| {
+        let mut write = LockApi::write(&lock).unwrap();
+        *write = 2;
+        panic!("interrupted write");
+    }));
+    assert!(panicked.is_err());
+    assert!(Arc::ptr_eq(&lock.load(), &published));
+    *LockApi::write(&lock).unwrap() += 2;
+    assert_eq!(*lock.load(), 3);
+}
@@ -0,0 +1,41 @@
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+};
+
+use locket::{DoubleBuffered, LockApi, Versioned};
+
+// `DoubleBuffered` readers never wait for a writer, so they would see a new
+// version paired with the old value if the version moved first.
+#[test]
+fn value_is_never_older_than_its_version() {
+    let lock = Versioned::wrap(DoubleBuffered::new(0u64));
+    let done = AtomicBool::new(false);
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            for _ in 0..20_000 {
+                *LockApi::write(&lock).unwrap() += 1;
+            }
+            done.store(true, Ordering::Release);
+        });
+        scope.spawn(|| {
+            while !done.load(Ordering::Acquire) {
+                let (guard, version) = lock.read_versioned().unwrap();
+                assert!(*guard >= version, "value {} at version {version}", *guard);
+            }
+        });
+    });
+    assert_eq!(lock.version(), 20_000);
+    assert!(!lock.has_changed_since(20_000));
+}
+
+#[test]
+fn writes_bump_the_version() {
+    let lock = Versioned::wrap(std::sync::Mutex::new(0));
+    let seen = lock.version();
+    assert!(!lock.has_changed_since(seen));
+    *LockApi::write(&lock).unwrap() = 1;
+    assert!(lock.has_changed_since(seen));
+    let (guard, version) = lock.read_versioned().unwrap();
+    assert_eq!((*guard, version), (1, seen + 1));
+}
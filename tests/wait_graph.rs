@@ -0,0 +1,59 @@
+use std::{
+    sync::{Barrier, Mutex, RwLock},
+    thread,
+    time::Duration,
+};
+
+use locket::{
+    testing::LockCheck,
+    wait_graph::{self, DeadlockChecked},
+    LockApi, LockError, Timed,
+};
+
+#[test]
+fn lock_check() {
+    LockCheck::new()
+        .shared_reads(true)
+        .run::<DeadlockChecked<RwLock<_>>>();
+}
+
+#[test]
+fn reports_a_lock_order_inversion() {
+    static REPORTED: Mutex<Vec<Vec<&str>>> = Mutex::new(Vec::new());
+    wait_graph::on_cycle(|cycle| {
+        let locks = cycle.entries.iter().map(|entry| entry.lock).collect();
+        REPORTED.lock().unwrap().push(locks);
+    });
+
+    let timed = |name| {
+        DeadlockChecked::with_name(
+            Timed::with_timeout(parking_lot::Mutex::new(0), Duration::from_millis(200)),
+            name,
+        )
+    };
+    let (first, second) = (timed("first"), timed("second"));
+    let barrier = Barrier::new(2);
+    let cross = |held: &DeadlockChecked<_>, wanted: &DeadlockChecked<_>| {
+        let _held = LockApi::<u32>::write(held).unwrap();
+        barrier.wait();
+        LockApi::<u32>::write(wanted).map(drop)
+    };
+    let results: Vec<_> = thread::scope(|scope| {
+        let threads = [
+            scope.spawn(|| cross(&first, &second)),
+            scope.spawn(|| cross(&second, &first)),
+        ];
+        threads.map(|thread| thread.join().unwrap()).into()
+    });
+
+    // The waits time out instead of hanging, at least one of them.
+    assert!(results
+        .iter()
+        .any(|result| matches!(result, Err(LockError::Timeout))));
+    let reported = REPORTED.lock().unwrap();
+    assert_eq!(reported.len(), 1, "{reported:?}");
+    let mut locks = reported[0].clone();
+    locks.sort();
+    assert_eq!(locks, ["first", "second"]);
+    assert!(wait_graph::cycles().is_empty());
+}
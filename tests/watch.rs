@@ -0,0 +1,93 @@
+use std::{
+    future::Future,
+    pin::pin,
+    sync::{Arc, RwLock},
+    task::{Context, Poll, Waker},
+};
+
+use locket::{testing::LockCheck, AsyncLockApi, LockApi, Subscriber, WatchLocket};
+
+fn runtime() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+}
+
+fn poll_once<F: Future>(future: F) -> Poll<F::Output> {
+    pin!(future).poll(&mut Context::from_waker(Waker::noop()))
+}
+
+#[test]
+fn lock_check() {
+    LockCheck::new()
+        .shared_reads(true)
+        .run::<WatchLocket<RwLock<_>>>();
+}
+
+#[test]
+fn subscribers_see_writes_after_subscribing() {
+    let watch = WatchLocket::<RwLock<u32>>::new(0);
+    *LockApi::write(&watch).unwrap() = 1;
+
+    let mut subscriber = watch.subscribe();
+    assert!(!subscriber.has_changed());
+    assert!(poll_once(subscriber.changed()).is_pending());
+
+    *LockApi::write(&watch).unwrap() = 2;
+    *LockApi::write(&watch).unwrap() = 3;
+    assert!(subscriber.has_changed());
+    // Both writes are coalesced into one change.
+    assert_eq!(poll_once(subscriber.changed()), Poll::Ready(3));
+    assert!(poll_once(subscriber.changed()).is_pending());
+    assert_eq!(subscriber.snapshot::<u32>().unwrap(), 3);
+    assert_eq!(subscriber.seen(), watch.version());
+}
+
+#[test]
+fn changed_wakes_a_waiting_task() {
+    let watch = Arc::new(WatchLocket::<RwLock<u32>>::new(0));
+    let mut subscriber = Subscriber::new(watch.clone());
+    let runtime = runtime();
+    let task = runtime.spawn(async move {
+        subscriber.changed().await;
+        subscriber.snapshot::<u32>().unwrap()
+    });
+    runtime.block_on(async {
+        tokio::task::yield_now().await;
+        assert!(!task.is_finished());
+        *LockApi::write(&*watch).unwrap() = 7;
+        assert_eq!(task.await.unwrap(), 7);
+    });
+}
+
+#[test]
+fn lock_check_async() {
+    LockCheck::new()
+        .shared_reads(true)
+        .run_async::<WatchLocket<tokio::sync::RwLock<_>>>();
+}
+
+#[test]
+fn wait_for_resolves_once_the_condition_holds() {
+    let watch = Arc::new(WatchLocket::<tokio::sync::RwLock<u32>>::new(1));
+    let runtime = runtime();
+    runtime.block_on(async {
+        assert_eq!(*watch.wait_for(|value| *value == 1).await.unwrap(), 1);
+    });
+
+    let task = runtime.spawn({
+        let watch = watch.clone();
+        async move { *watch.wait_for(|value| *value >= 3).await.unwrap() }
+    });
+    runtime.block_on(async {
+        for value in 2..=4 {
+            tokio::task::yield_now().await;
+            // Nothing written so far satisfies the condition.
+            if value <= 3 {
+                assert!(!task.is_finished());
+            }
+            *AsyncLockApi::write(&*watch).await.unwrap() = value;
+        }
+        assert!(task.await.unwrap() >= 3);
+    });
+}
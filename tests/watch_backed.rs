@@ -0,0 +1,71 @@
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use locket::{testing::LockCheck, AsyncLockApi, WatchBacked};
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+fn poll_once<F: Future>(future: F) -> Poll<F::Output> {
+    pin!(future).poll(&mut Context::from_waker(Waker::noop()))
+}
+
+#[test]
+fn lock_check_async() {
+    LockCheck::new()
+        .shared_reads(true)
+        .snapshot_reads(true)
+        .run_async::<WatchBacked<_>>();
+}
+
+#[test]
+fn only_writes_wake_receivers() {
+    let lock = WatchBacked::new(1);
+    let mut receiver = lock.subscribe();
+    block_on(async {
+        let guard = AsyncLockApi::write(&lock).await.unwrap();
+        assert_eq!(*guard, 1);
+        drop(guard);
+        assert!(!receiver.has_changed().unwrap());
+
+        *AsyncLockApi::write(&lock).await.unwrap() += 1;
+        assert!(receiver.has_changed().unwrap());
+        assert_eq!(*receiver.borrow_and_update(), 2);
+    });
+}
+
+#[test]
+fn modify_edits_in_place() {
+    let lock = WatchBacked::new(vec![1]);
+    let mut receiver = lock.subscribe();
+    block_on(async {
+        lock.modify(|value| value.push(2)).await;
+        assert_eq!(*receiver.borrow_and_update(), [1, 2]);
+
+        assert!(!lock.modify_if(|value| value.len() > 2).await);
+        assert!(!receiver.has_changed().unwrap());
+        assert!(lock.modify_if(|value| value.pop().is_some()).await);
+        assert!(receiver.has_changed().unwrap());
+        assert_eq!(*lock.borrow(), [1]);
+    });
+}
+
+#[test]
+fn modify_waits_for_guard_writers() {
+    let lock = WatchBacked::new(0);
+    block_on(async {
+        let mut guard = AsyncLockApi::write(&lock).await.unwrap();
+        *guard = 1;
+        assert!(poll_once(lock.modify(|value| *value += 10)).is_pending());
+        drop(guard);
+        lock.modify(|value| *value += 10).await;
+    });
+    assert_eq!(*lock.borrow(), 11);
+}
@@ -0,0 +1,39 @@
+use std::{
+    sync::{mpsc, Arc},
+    thread,
+    time::Duration,
+};
+
+use locket::{testing::MockClock, LockApi, PolicyRwLock, StallKind, Watchdog};
+
+#[test]
+fn blocked_wait_is_reported_under_a_mock_clock() {
+    let (stalls, reported) = mpsc::channel();
+    let lock = Arc::new(
+        Watchdog::with_threshold(PolicyRwLock::new(0), Duration::from_millis(10)).on_stall(
+            move |stall| {
+                let _ = stalls.send(stall.kind);
+            },
+        ),
+    );
+    let held = LockApi::write(&lock).unwrap();
+
+    let waiter = {
+        let lock = lock.clone();
+        thread::spawn(move || {
+            let clock = MockClock::new();
+            clock.advance(Duration::from_secs(3600));
+            let _clock = clock.install();
+            *LockApi::write(&lock).unwrap() += 1;
+        })
+    };
+    // The monitor reports the wait while it is still blocked.
+    assert_eq!(
+        reported.recv_timeout(Duration::from_secs(5)),
+        Ok(StallKind::Wait)
+    );
+    drop(held);
+    waiter.join().unwrap();
+    // Only the first guard's hold follows, the wait is not reported twice.
+    assert_eq!(reported.try_iter().collect::<Vec<_>>(), [StallKind::Hold]);
+}